@@ -0,0 +1,97 @@
+//! Compares parsing with and without memoization on texts that make the parser backtrack a lot.
+//!
+//! Run with `cargo bench --bench memoize`.
+
+use std::time::{Duration, Instant};
+
+use sneturfahi::{Arena, Cst};
+
+/// A kind of text whose parsing time grows with its depth.
+struct Pathological {
+	name: &'static str,
+	text: fn(usize) -> String,
+	/// The largest depth to measure, kept small enough that parsing without memoization takes at most a few seconds.
+	max_depth: usize,
+}
+
+const PATHOLOGICAL: &[Pathological] = &[
+	Pathological {
+		name: "unclosed relative clauses",
+		text: |depth| format!("mi klama {}brode", "lo broda poi ".repeat(depth)),
+		max_depth: 8,
+	},
+	Pathological {
+		name: "nested abstractions",
+		text: |depth| {
+			format!(
+				"mi djica {}mi klama{}",
+				"lo nu ".repeat(depth),
+				" kei ku".repeat(depth)
+			)
+		},
+		max_depth: 14,
+	},
+	Pathological {
+		name: "unclosed abstractions",
+		text: |depth| format!("{}mi klama", "lo nu ".repeat(depth)),
+		max_depth: 10,
+	},
+];
+
+/// Ordinary texts, to check that memoization does not make them much slower.
+const ORDINARY: &[&str] = &[
+	"mi klama lo zdani",
+	"lo mlatu poi zvati lo purdi cu citka lo finpe .i le nanmu cu viska lo mlatu",
+	"mi djuno lo du'u do prami lo nu klama lo zarci kei ku vau .i ku'i do na djica",
+];
+
+fn time(tokens: &[sneturfahi::lex::Token], memoize: bool) -> (Duration, bool) {
+	let mut arena = Arena::new();
+	let mut runs = 0;
+	let start = Instant::now();
+	let mut ok = false;
+	while runs == 0 || start.elapsed() < Duration::from_millis(200) {
+		arena.reset();
+		ok = if memoize {
+			Cst::parse_memoized(tokens, &arena).is_ok()
+		} else {
+			Cst::parse(tokens, &arena).is_ok()
+		};
+		runs += 1;
+	}
+	(start.elapsed() / runs, ok)
+}
+
+fn report(name: &str, text: &str) {
+	let tokens: Vec<_> = sneturfahi::lex(text).collect::<Result<_, _>>().unwrap();
+	let (plain, plain_ok) = time(&tokens, false);
+	let (memoized, memoized_ok) = time(&tokens, true);
+	assert_eq!(
+		plain_ok, memoized_ok,
+		"memoization changed the result for {text:?}"
+	);
+	println!(
+		"{name:<32} {:>14?} {:>14?} {:>9.2}x",
+		plain,
+		memoized,
+		plain.as_secs_f64() / memoized.as_secs_f64(),
+	);
+}
+
+fn main() {
+	println!(
+		"{:<32} {:>14} {:>14} {:>10}",
+		"text", "plain", "memoized", "speedup"
+	);
+	for pathological in PATHOLOGICAL {
+		for depth in (2..=pathological.max_depth).step_by(2) {
+			report(
+				&format!("{} ({depth})", pathological.name),
+				&(pathological.text)(depth),
+			);
+		}
+	}
+	for (index, text) in ORDINARY.iter().enumerate() {
+		report(&format!("ordinary text {}", index + 1), text);
+	}
+}
@@ -18,6 +18,14 @@ mod paths {
 		quote!(crate::parse::cst::parse_trait::Parse)
 	}
 
+	pub(super) fn node() -> TokenStream {
+		quote!(crate::parse::cst::node)
+	}
+
+	pub(super) fn context() -> TokenStream {
+		quote!(crate::parse::cst::parse_trait::Context)
+	}
+
 	pub(super) fn result() -> TokenStream {
 		quote!(crate::parse::cst::parse_trait::Result)
 	}
@@ -35,14 +43,20 @@ pub fn derive_parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 	let mut input = parse_macro_input!(input as DeriveInput);
 
 	let name = input.ident;
+	let name_str = name.to_string();
 
-	let body = implement_parse(&input.data, &input.attrs, &name);
+	let attrs = ContainerAttributes::get(&input.attrs);
+	let memoize = attrs
+		.memoize
+		.map(|span| implement_memoize(span, &input.generics, &name));
+	let body = implement_parse(&input.data, attrs, &name);
 
 	let trait_path = paths::trait_();
 	let result_path = paths::result();
 	let token_path = paths::token();
+	let context_path = paths::context();
 	let assert_parse = quote_spanned! {Span::mixed_site()=>
-		fn assert_parse<'arena, 'a: 'arena, T: #trait_path<'arena>>() -> impl FnMut(&'a [#token_path], &'arena bumpalo::Bump) -> #result_path<'a, T> {
+		fn assert_parse<'arena, 'a: 'arena, T: #trait_path<'arena>>() -> impl FnMut(&'a [#token_path], &#context_path<'arena>) -> #result_path<'a, T> {
 			T::parse
 		}
 	};
@@ -75,11 +89,17 @@ pub fn derive_parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 		#[automatically_derived]
 		#[allow(unused_qualifications)]
 		impl #params_with_arena #trait_path<'arena> for #name #ty_params {
-			fn parse<'a: 'arena>(input: &'a [#token_path], arena: &'arena bumpalo::Bump) -> #result_path<'a, Self> {
+			fn parse<'a: 'arena>(input: &'a [#token_path], context: &#context_path<'arena>) -> #result_path<'a, Self> {
 				#assert_parse
 
-				#body
+				let result = (|| -> #result_path<'a, Self> { #body })();
+				if result.is_ok() {
+					context.arena.record_parsed(#name_str, std::mem::size_of::<Self>());
+				}
+				result
 			}
+
+			#memoize
 		}
 	};
 
@@ -114,12 +134,36 @@ fn add_trait_bounds(generics: &mut Generics) {
 	}
 }
 
-fn implement_parse(data: &Data, attrs: &[Attribute], ident: &Ident) -> TokenStream {
-	let attrs = ContainerAttributes::get(attrs);
+/// Override `parse_ref` to go through the memo, which stores nodes as `NodeRef`s and so only works for rule types with their own variant.
+fn implement_memoize(span: Span, generics: &Generics, ident: &Ident) -> TokenStream {
+	if generics.type_params().next().is_some() {
+		abort!(span, "`memoize` is not supported for generic rule types");
+	}
 
+	let context_path = paths::context();
+	let result_path = paths::result();
+	let token_path = paths::token();
+	let node_path = paths::node();
+	quote_spanned! {span=>
+		fn parse_ref<'a: 'arena>(input: &'a [#token_path], context: &#context_path<'arena>) -> #result_path<'a, &'arena Self> {
+			context.memoized(
+				input,
+				#node_path::NodeKind::#ident,
+				#node_path::NodeRef::#ident,
+				|node| match node {
+					#node_path::NodeRef::#ident(node) => Some(node),
+					_ => None,
+				},
+				|input| Self::parse(input, context),
+			)
+		}
+	}
+}
+
+fn implement_parse(data: &Data, attrs: ContainerAttributes, ident: &Ident) -> TokenStream {
 	let trait_path = paths::trait_();
 	let inner = if let Some(with) = attrs.with {
-		quote_spanned!(with.span()=> nom::Parser::parse(&mut (#with)(|input| #trait_path::parse(input, arena), arena), input))
+		quote_spanned!(with.span()=> nom::Parser::parse(&mut (#with)(|input| #trait_path::parse(input, context), context.arena), input))
 	} else {
 		match data {
 			Data::Struct(data) => implement_struct(&data.fields, ident, attrs.must_consume, quote!(Self)),
@@ -230,7 +274,7 @@ fn implement_parse(data: &Data, attrs: &[Attribute], ident: &Ident) -> TokenStre
 		let after_nots = attrs.after_nots;
 		let after_nots = after_nots
 			.iter()
-			.map(|not| quote_spanned! {not.span() => nom::combinator::not(|input| assert_parse::<'arena, 'a, #not>()(input, arena))});
+			.map(|not| quote_spanned! {not.span() => nom::combinator::not(|input| assert_parse::<'arena, 'a, #not>()(input, context))});
 		quote! {
 			nom::combinator::map(
 				nom::sequence::tuple((
@@ -328,8 +372,8 @@ fn make_elements(i: &Punctuated<Field, Comma>) -> impl Iterator<Item = TokenStre
 		let ty_span = ty.span();
 
 		let inner = attrs.with.map_or_else(
-			|| quote_spanned!(ty_span=> |input| assert_parse::<'arena, 'a, #ty>()(input, arena)),
-			|with| quote_spanned!(with.span()=> (#with)(|input| #trait_path::parse(input, arena), arena)),
+			|| quote_spanned!(ty_span=> |input| assert_parse::<'arena, 'a, #ty>()(input, context)),
+			|with| quote_spanned!(with.span()=> (#with)(|input| #trait_path::parse(input, context), context.arena)),
 		);
 
 		let actual = if attrs.cut {
@@ -344,10 +388,10 @@ fn make_elements(i: &Punctuated<Field, Comma>) -> impl Iterator<Item = TokenStre
 			let idx = Index::from(nots.len());
 			let nots = nots
 				.iter()
-				.map(|not| quote_spanned!(not.span() => nom::combinator::not(|input| assert_parse::<'arena, 'a, #not>()(input, arena))));
+				.map(|not| quote_spanned!(not.span() => nom::combinator::not(|input| assert_parse::<'arena, 'a, #not>()(input, context))));
 			let after_nots = after_nots
 				.iter()
-				.map(|not| quote_spanned!(not.span() => nom::combinator:not(|input| assert_parse::<'arena, 'a, #not>()(input, arena))));
+				.map(|not| quote_spanned!(not.span() => nom::combinator:not(|input| assert_parse::<'arena, 'a, #not>()(input, context))));
 			quote! {
 				nom::combinator::map(
 					nom::sequence::tuple((
@@ -371,6 +415,7 @@ struct ContainerAttributes {
 	post_conds: Vec<PostCond>,
 	longest: bool,
 	must_consume: bool,
+	memoize: Option<Span>,
 }
 
 impl ContainerAttributes {
@@ -388,6 +433,7 @@ impl ContainerAttributes {
 				}
 				ParseAttribute::Longest => ret.longest = true,
 				ParseAttribute::NonEmpty => ret.must_consume = true,
+				ParseAttribute::Memoize => ret.memoize = Some(span),
 			}
 		}
 
@@ -429,6 +475,9 @@ impl FieldAttributes {
 						"`must_consume` attribute is only allowed on enum variants"
 					)
 				}
+				ParseAttribute::Memoize => {
+					abort!(span, "`memoize` attribute is only allowed on containers")
+				}
 			}
 		}
 
@@ -472,6 +521,7 @@ enum ParseAttribute {
 	PostCond(PostCond),
 	Longest,
 	NonEmpty,
+	Memoize,
 }
 
 fn get_parse_attributes(
@@ -639,9 +689,13 @@ fn parse_meta(meta: Meta) -> (Span, ParseAttribute) {
 					"`must_consume` attribute must be a path attribute"
 				),
 			},
+			Some(ident) if ident == "memoize" => match meta {
+				Meta::Path(..) => ParseAttribute::Memoize,
+				other => abort!(other.span(), "`memoize` attribute must be a path attribute"),
+			},
 			other => abort!(
 				other.span(),
-				"valid attributes are `with`, `longest`, `must_consume`, `not`, `not_after`, and `postcond`"
+				"valid attributes are `with`, `longest`, `must_consume`, `memoize`, `not`, `not_after`, and `postcond`"
 			),
 		},
 	)
@@ -1,10 +1,10 @@
-use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
 use proc_macro_error::abort;
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
-	parse_quote, Attribute, Data, DataEnum, DataStruct, DeriveInput, Fields, GenericParam, Generics,
-	Lit, LitStr, Meta, MetaNameValue, TypeParamBound,
+	parse_quote, Attribute, Data, DataEnum, DataStruct, DeriveInput, Field, Fields, GenericParam,
+	Generics, Lit, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, Path, TypeParamBound,
 };
 
 mod paths {
@@ -19,8 +19,32 @@ mod paths {
 		quote!(crate::parse::tree_node::TreeNodeChild)
 	}
 
-	pub fn location() -> TokenStream {
-		quote!(crate::span::Location)
+	pub fn child_or_elided() -> TokenStream {
+		quote!(crate::parse::tree_node::Child)
+	}
+
+	pub fn selmaho() -> TokenStream {
+		quote!(crate::lex::Selmaho)
+	}
+
+	pub fn visit() -> TokenStream {
+		quote!(crate::parse::cst::visit::Visit)
+	}
+
+	pub fn visitor() -> TokenStream {
+		quote!(crate::parse::cst::visit::Visitor)
+	}
+
+	pub fn fold_with() -> TokenStream {
+		quote!(crate::parse::cst::fold::FoldWith)
+	}
+
+	pub fn fold() -> TokenStream {
+		quote!(crate::parse::cst::fold::Fold)
+	}
+
+	pub fn node_ref() -> TokenStream {
+		quote!(crate::parse::cst::node::NodeRef)
 	}
 }
 
@@ -63,25 +87,35 @@ fn make_fields(fields: &Fields) -> Vec<TokenStream> {
 struct ContainerAttributes {
 	name: Option<LitStr>,
 	passthrough_child: bool,
+	selmaho: Option<Path>,
 }
 
-impl ContainerAttributes {
-	fn get(attrs: &[Attribute]) -> Self {
-		let mut ret = Self::default();
-
-		for attr in attrs.iter().filter(|attr| {
+/// The contents of each `#[tree_node(...)]` attribute in `attrs`.
+fn tree_node_metas(attrs: &[Attribute]) -> impl Iterator<Item = Meta> + '_ {
+	attrs
+		.iter()
+		.filter(|attr| {
 			attr
 				.path
 				.get_ident()
 				.map_or(false, |ident| ident == "tree_node")
-		}) {
+		})
+		.map(|attr| {
 			let inner = match attr.tokens.clone().into_iter().next() {
 				Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
 					group.stream()
 				}
 				other => abort!(other.span(), "expected parenthesized group"),
 			};
-			let meta: Meta = syn::parse2(inner).unwrap();
+			syn::parse2(inner).unwrap()
+		})
+}
+
+impl ContainerAttributes {
+	fn get(attrs: &[Attribute]) -> Self {
+		let mut ret = Self::default();
+
+		for meta in tree_node_metas(attrs) {
 			match meta {
 				Meta::Path(meta)
 					if meta
@@ -97,7 +131,17 @@ impl ContainerAttributes {
 				}) if path.get_ident().map_or(false, |ident| ident == "name") => {
 					ret.name = Some(name);
 				}
-				other => abort!(other.span(), "expected passthrough_child or name"),
+				Meta::List(MetaList { path, nested, .. })
+					if path.get_ident().map_or(false, |ident| ident == "selmaho") =>
+				{
+					match nested.first() {
+						Some(NestedMeta::Meta(Meta::Path(selmaho))) if nested.len() == 1 => {
+							ret.selmaho = Some(selmaho.clone());
+						}
+						_ => abort!(nested.span(), "expected a single selmaho variant"),
+					}
+				}
+				other => abort!(other.span(), "expected passthrough_child, name, or selmaho"),
 			}
 		}
 
@@ -105,11 +149,62 @@ impl ContainerAttributes {
 	}
 }
 
+/// Get the label of `field`, which is set with `#[tree_node(label = "...")]`.
+///
+/// It defaults to the name of the field.
+/// The only field of a tuple variant defaults to the name of the variant in snake case, and the only field of a tuple struct to `inner`.
+fn field_label(variant: Option<&Ident>, fields: &Fields, field: &Field) -> Option<LitStr> {
+	let mut ret = match (&field.ident, variant, fields) {
+		(Some(ident), _, _) => Some(LitStr::new(&ident.to_string(), ident.span())),
+		(None, Some(variant), Fields::Unnamed(fields)) if fields.unnamed.len() == 1 => Some(
+			LitStr::new(&snake_case(&variant.to_string()), variant.span()),
+		),
+		(None, None, Fields::Unnamed(fields)) if fields.unnamed.len() == 1 => {
+			Some(LitStr::new("inner", field.span()))
+		}
+		_ => None,
+	};
+
+	for meta in tree_node_metas(&field.attrs) {
+		match meta {
+			Meta::NameValue(MetaNameValue {
+				path,
+				lit: Lit::Str(label),
+				..
+			}) if path.get_ident().map_or(false, |ident| ident == "label") => {
+				ret = Some(label);
+			}
+			other => abort!(other.span(), "expected label"),
+		}
+	}
+
+	ret
+}
+
+/// Convert a name from `UpperCamelCase` to `snake_case`, such as `LerfuString` to `lerfu_string`.
+fn snake_case(name: &str) -> String {
+	let mut ret = String::with_capacity(name.len() + 4);
+	for (idx, ch) in name.char_indices() {
+		if ch.is_uppercase() {
+			if idx > 0 {
+				ret.push('_');
+			}
+			ret.extend(ch.to_lowercase());
+		} else {
+			ret.push(ch);
+		}
+	}
+	ret
+}
+
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 	let mut input = syn::parse_macro_input!(input as DeriveInput);
 
 	let attrs = ContainerAttributes::get(&input.attrs);
 
+	let visit_impl = visit_impl(&input, &attrs);
+	let fold_impl = fold_impl(&input, &attrs);
+
 	add_trait_bounds(&mut input.generics);
 
 	let name = input.ident;
@@ -119,7 +214,6 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
 	let trait_path = paths::trait_();
 	let child_path = paths::child();
-	let location_path = paths::location();
 	let assert_fn = assert_fn();
 
 	let experimental_impl = implement(&input.data, |fields| {
@@ -129,25 +223,25 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 		}
 	});
 
-	let start_location_impl = implement(&input.data, |fields| {
+	let start_token_impl = implement(&input.data, |fields| {
 		quote! {
 			#assert_fn
 			#(
-				if let Some(location) = #child_path::start_location(#fields) {
-					return Some(location);
+				if let Some(token) = #child_path::start_token(#fields) {
+					return Some(token);
 				}
 			)*
 			None
 		}
 	});
 
-	let end_location_impl = implement(&input.data, |fields| {
+	let end_token_impl = implement(&input.data, |fields| {
 		let fields = fields.iter().rev();
 		quote! {
 			#assert_fn
 			#(
-				if let Some(location) = #child_path::end_location(#fields) {
-					return Some(location);
+				if let Some(token) = #child_path::end_token(#fields) {
+					return Some(token);
 				}
 			)*
 			None
@@ -163,6 +257,46 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 		}
 	});
 
+	let for_each_child_or_elided_impl = implement(&input.data, |fields| {
+		quote! {
+			#assert_fn
+			#(
+				#child_path::invoke_with_self_or_elided(#fields, f);
+			)*
+		}
+	});
+
+	let for_each_labeled_child_impl = implement_raw(&input.data, |variant, raw_fields| {
+		let calls = make_fields(raw_fields)
+			.into_iter()
+			.zip(
+				raw_fields
+					.iter()
+					.map(|field| field_label(variant, raw_fields, field)),
+			)
+			.map(|(field, label)| {
+				let label = match label {
+					Some(label) => quote!(Some(#label)),
+					None => quote!(None),
+				};
+				quote!(#child_path::invoke_with_self(#field, &mut |child| f(#label, child));)
+			});
+		quote! {
+			#assert_fn
+			#(#calls)*
+		}
+	});
+
+	let child_or_elided_path = paths::child_or_elided();
+	let selmaho_path = paths::selmaho();
+	let elidable_terminator_impl = attrs.selmaho.map(|selmaho| {
+		quote! {
+			fn elidable_terminator() -> Option<#selmaho_path> {
+				#selmaho_path::#selmaho.elidable_terminator().map(|_| #selmaho_path::#selmaho)
+			}
+		}
+	});
+
 	if attrs.passthrough_child {
 		quote! {
 			#[automatically_derived]
@@ -171,16 +305,20 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 					#for_each_child_impl
 				}
 
+				fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(#child_or_elided_path<'a>)) {
+					#for_each_child_or_elided_impl
+				}
+
 				fn experimental(&self) -> bool {
 					#experimental_impl
 				}
 
-				fn start_location(&self) -> Option<#location_path> {
-					#start_location_impl
+				fn start_token(&self) -> Option<u32> {
+					#start_token_impl
 				}
 
-				fn end_location(&self) -> Option<#location_path> {
-					#end_location_impl
+				fn end_token(&self) -> Option<u32> {
+					#end_token_impl
 				}
 			}
 
@@ -190,16 +328,20 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 					<#name #ty_generics>::invoke_with_self(self, f);
 				}
 
+				fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(#child_or_elided_path<'a>)) {
+					<#name #ty_generics>::invoke_with_self_or_elided(self, f);
+				}
+
 				fn experimental(&self) -> bool {
 					<#name #ty_generics>::experimental(self)
 				}
 
-				fn start_location(&self) -> Option<#location_path> {
-					<#name #ty_generics>::start_location(self)
+				fn start_token(&self) -> Option<u32> {
+					<#name #ty_generics>::start_token(self)
 				}
 
-				fn end_location(&self) -> Option<#location_path> {
-					<#name #ty_generics>::end_location(self)
+				fn end_token(&self) -> Option<u32> {
+					<#name #ty_generics>::end_token(self)
 				}
 			}
 
@@ -209,18 +351,25 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 					<#name #ty_generics>::invoke_with_self(self, f);
 				}
 
+				fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(#child_or_elided_path<'a>)) {
+					<#name #ty_generics>::invoke_with_self_or_elided(self, f);
+				}
+
 				fn experimental(&self) -> bool {
 					<#name #ty_generics>::experimental(self)
 				}
 
-				fn start_location(&self) -> Option<#location_path> {
-					<#name #ty_generics>::start_location(self)
+				fn start_token(&self) -> Option<u32> {
+					<#name #ty_generics>::start_token(self)
 				}
 
-				fn end_location(&self) -> Option<#location_path> {
-					<#name #ty_generics>::end_location(self)
+				fn end_token(&self) -> Option<u32> {
+					<#name #ty_generics>::end_token(self)
 				}
 			}
+
+			#visit_impl
+			#fold_impl
 		}
 		.into()
 	} else {
@@ -247,6 +396,8 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 			Data::Union(..) => unreachable!(),
 		};
 
+		let node_ref_path = paths::node_ref();
+
 		quote! {
 			#[automatically_derived]
 			impl #impl_generics #trait_path for #name #ty_generics #where_clause {
@@ -258,24 +409,191 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 					#experimental_impl
 				}
 
-				fn start_location(&self) -> Option<#location_path> {
-					#start_location_impl
+				fn start_token(&self) -> Option<u32> {
+					#start_token_impl
 				}
 
-				fn end_location(&self) -> Option<#location_path> {
-					#end_location_impl
+				fn end_token(&self) -> Option<u32> {
+					#end_token_impl
 				}
 
 				fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn #trait_path)) {
 					#for_each_child_impl
 				}
+
+				fn for_each_child_or_elided<'a>(&'a self, f: &mut dyn FnMut(#child_or_elided_path<'a>)) {
+					#for_each_child_or_elided_impl
+				}
+
+				fn for_each_labeled_child<'a>(&'a self, f: &mut dyn FnMut(Option<&'static str>, &'a dyn #trait_path)) {
+					#for_each_labeled_child_impl
+				}
+
+				fn downcast(&self) -> #node_ref_path<'_> {
+					#node_ref_path::#name(self)
+				}
+
+				#elidable_terminator_impl
 			}
+
+			#visit_impl
+			#fold_impl
 		}
 		.into()
 	}
 }
 
-fn implement(input: &Data, struct_fn: impl FnMut(&[TokenStream]) -> TokenStream) -> TokenStream {
+/// The name of the method of `Visitor` or `Fold` that corresponds to this type, as a `paste` segment.
+fn hook_name(prefix: &str, name: &Ident) -> TokenStream {
+	let prefix = format_ident!("{prefix}");
+	quote!([<#prefix _ #name:snake>])
+}
+
+fn visit_impl(input: &DeriveInput, attrs: &ContainerAttributes) -> TokenStream {
+	let visit_path = paths::visit();
+	let visitor_path = paths::visitor();
+	let selmaho_path = paths::selmaho();
+
+	let mut generics = input.generics.clone();
+	for param in &mut generics.params {
+		if let GenericParam::Type(type_param) = param {
+			type_param.bounds.push(parse_quote!(#visit_path<'ast>));
+		}
+	}
+	generics.params.insert(0, parse_quote!('ast));
+	let (impl_generics, _, where_clause) = generics.split_for_impl();
+	let (_, ty_generics, _) = input.generics.split_for_impl();
+	let name = &input.ident;
+
+	let walk_impl = implement_raw(&input.data, |_, fields| {
+		let fields = field_bindings(fields);
+		quote! {
+			#(#visit_path::visit(#fields, visitor);)*
+		}
+	});
+
+	let visit_impl = if let Some(selmaho) = &attrs.selmaho {
+		quote!(visitor.visit_token(#selmaho_path::#selmaho, self);)
+	} else if attrs.passthrough_child {
+		quote!(#visit_path::walk(self, visitor);)
+	} else {
+		let hook = hook_name("visit", name);
+		quote!(paste::paste! { visitor.#hook(self); })
+	};
+
+	quote! {
+		#[automatically_derived]
+		impl #impl_generics #visit_path<'ast> for #name #ty_generics #where_clause {
+			fn visit<V: #visitor_path<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+				#visit_impl
+			}
+
+			#[allow(unused_variables)]
+			fn walk<V: #visitor_path<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+				#walk_impl
+			}
+		}
+	}
+}
+
+fn fold_impl(input: &DeriveInput, attrs: &ContainerAttributes) -> TokenStream {
+	let fold_with_path = paths::fold_with();
+	let fold_path = paths::fold();
+	let selmaho_path = paths::selmaho();
+
+	let output_args: Vec<_> = input
+		.generics
+		.params
+		.iter()
+		.map(|param| match param {
+			GenericParam::Lifetime(..) => quote!('new),
+			GenericParam::Type(type_param) => {
+				let ident = &type_param.ident;
+				quote!(<#ident as #fold_with_path<'new>>::Output)
+			}
+			GenericParam::Const(const_param) => const_param.ident.to_token_stream(),
+		})
+		.collect();
+
+	let mut generics = input.generics.clone();
+	for param in &mut generics.params {
+		if let GenericParam::Type(type_param) = param {
+			type_param.bounds.push(parse_quote!(#fold_with_path<'new>));
+		}
+	}
+	generics.params.insert(0, parse_quote!('new));
+	let (impl_generics, _, where_clause) = generics.split_for_impl();
+	let (_, ty_generics, _) = input.generics.split_for_impl();
+	let name = &input.ident;
+	let output = if output_args.is_empty() {
+		quote!(#name)
+	} else {
+		quote!(#name<#(#output_args),*>)
+	};
+
+	let fold_children_impl = implement_raw(&input.data, |variant, fields| {
+		let constructor = match variant {
+			Some(variant) => quote!(#name::#variant),
+			None => quote!(#name),
+		};
+		let folded = field_bindings(fields)
+			.into_iter()
+			.map(|field| quote!(#fold_with_path::fold_with(#field, folder)));
+		match fields {
+			Fields::Named(named) => {
+				let names = named.named.iter().map(|field| &field.ident);
+				quote!(#constructor { #(#names: #folded),* })
+			}
+			Fields::Unnamed(..) => quote!(#constructor(#(#folded),*)),
+			Fields::Unit => constructor,
+		}
+	});
+
+	let fold_with_impl = if let Some(selmaho) = &attrs.selmaho {
+		quote!(folder.fold_token(#selmaho_path::#selmaho, self))
+	} else if attrs.passthrough_child {
+		quote!(#fold_with_path::fold_children(self, folder))
+	} else {
+		let hook = hook_name("fold", name);
+		quote!(paste::paste! { folder.#hook(self) })
+	};
+
+	quote! {
+		#[automatically_derived]
+		impl #impl_generics #fold_with_path<'new> for #name #ty_generics #where_clause {
+			type Output = #output;
+
+			fn fold_with<F: #fold_path<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+				#fold_with_impl
+			}
+
+			#[allow(unused_variables)]
+			fn fold_children<F: #fold_path<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+				#fold_children_impl
+			}
+		}
+	}
+}
+
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+	(0..fields.len())
+		.map(|idx| format_ident!("field_{idx}"))
+		.collect()
+}
+
+fn implement(
+	input: &Data,
+	mut struct_fn: impl FnMut(&[TokenStream]) -> TokenStream,
+) -> TokenStream {
+	implement_raw(input, |_, fields| struct_fn(&make_fields(fields)))
+}
+
+/// Like `implement`, but pass the variant (for enums) and the raw fields to `struct_fn`.
+/// The fields are bound to `field_0`, `field_1`, etc.
+fn implement_raw(
+	input: &Data,
+	struct_fn: impl FnMut(Option<&Ident>, &Fields) -> TokenStream,
+) -> TokenStream {
 	match &input {
 		Data::Struct(data) => implement_struct(data, struct_fn),
 		Data::Enum(data) => implement_enum(data, struct_fn),
@@ -306,9 +624,9 @@ fn make_destructure(fields: &Fields) -> TokenStream {
 
 fn implement_struct(
 	data: &DataStruct,
-	mut struct_fn: impl FnMut(&[TokenStream]) -> TokenStream,
+	mut struct_fn: impl FnMut(Option<&Ident>, &Fields) -> TokenStream,
 ) -> TokenStream {
-	let body = struct_fn(&make_fields(&data.fields));
+	let body = struct_fn(None, &data.fields);
 	let destructure = make_destructure(&data.fields);
 	quote! {
 		let Self #destructure = self;
@@ -318,7 +636,7 @@ fn implement_struct(
 
 fn implement_enum(
 	data: &DataEnum,
-	mut struct_fn: impl FnMut(&[TokenStream]) -> TokenStream,
+	mut struct_fn: impl FnMut(Option<&Ident>, &Fields) -> TokenStream,
 ) -> TokenStream {
 	let patterns = data.variants.iter().map(|variant| {
 		let ident = &variant.ident;
@@ -328,7 +646,7 @@ fn implement_enum(
 	let arms = data
 		.variants
 		.iter()
-		.map(|variant| struct_fn(&make_fields(&variant.fields)));
+		.map(|variant| struct_fn(Some(&variant.ident), &variant.fields));
 
 	quote! {
 		match self {
@@ -71,8 +71,13 @@ pub fn make_assert_parse_test(input: proc_macro::TokenStream) -> proc_macro::Tok
 		fn #ident() {
 			let sentence = #lit;
 			eprintln!(".i caku jai cipra lodu'u gendra fa lu {:?} li'u", sentence);
+			let tokens = crate::lex(sentence).collect::<Result<Vec<_>, _>>().expect("lexing failed");
 			let arena = crate::Arena::new();
-			crate::Cst::parse(&crate::lex(sentence).collect::<Result<Vec<_>, _>>().expect("lexing failed"), &arena).expect("parsing failed");
+			let memoized = crate::parse_in(sentence, &arena).expect("parsing failed");
+			let plain = crate::Cst::parse(&tokens, &arena).expect("parsing without memoization failed");
+			assert_eq!(format!("{:?}", plain.root()), format!("{:?}", memoized.root()), "memoization changed the tree");
+			// a smoke test: lowering every parsed sentence to the AST must not panic
+			let _ = crate::parse::ast::lower(&memoized, sentence);
 		}
 	}.into()
 }
@@ -0,0 +1,255 @@
+use std::path::PathBuf;
+
+use sneturfahi::parse::select::Selector;
+
+/// Decompose, lex, parse, and check Lojban text.
+///
+/// Input is read from the given files, or from standard input if no files are given.
+#[derive(argh::FromArgs, Debug)]
+pub struct Args {
+	/// enable verbose logging
+	#[argh(switch)]
+	pub verbose: bool,
+	#[argh(subcommand)]
+	pub command: Command,
+}
+
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand)]
+pub enum Command {
+	Decompose(Decompose),
+	Lex(Lex),
+	Parse(Parse),
+	Check(Check),
+	Grep(Grep),
+	Format(Format),
+}
+
+impl Command {
+	pub fn files(&self) -> &[PathBuf] {
+		match self {
+			Self::Decompose(args) => &args.files,
+			Self::Lex(args) => &args.files,
+			Self::Parse(args) => &args.files,
+			Self::Check(args) => &args.files,
+			Self::Grep(args) => &args.files,
+			Self::Format(args) => &args.files,
+		}
+	}
+
+	pub fn batch(&self) -> bool {
+		match self {
+			Self::Decompose(args) => args.batch,
+			Self::Lex(args) => args.batch,
+			Self::Parse(args) => args.batch,
+			Self::Check(args) => args.batch,
+			Self::Grep(args) => args.batch,
+			Self::Format(args) => args.batch,
+		}
+	}
+}
+
+/// split text into words, decomposing compounds like "minajimpe"
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand, name = "decompose")]
+pub struct Decompose {
+	/// output format: text, json, brackets, or camxes
+	#[argh(option, default = "OutputFormat::Text")]
+	pub format: OutputFormat,
+	/// treat each line of the input as a separate text
+	#[argh(switch)]
+	pub batch: bool,
+	/// files to read
+	#[argh(positional)]
+	pub files: Vec<PathBuf>,
+}
+
+/// split text into tokens and classify them by selmaho
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand, name = "lex")]
+pub struct Lex {
+	/// output format: text, json, brackets, or camxes
+	#[argh(option, default = "OutputFormat::Text")]
+	pub format: OutputFormat,
+	/// treat each line of the input as a separate text
+	#[argh(switch)]
+	pub batch: bool,
+	/// files to read
+	#[argh(positional)]
+	pub files: Vec<PathBuf>,
+}
+
+/// parse text and print the concrete syntax tree
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand, name = "parse")]
+pub struct Parse {
+	/// output format: text, json, brackets, camxes, dot, or sexp
+	#[argh(option, default = "TreeFormat::Text")]
+	pub format: TreeFormat,
+	/// collapse nodes that have only one child
+	#[argh(switch)]
+	pub collapse: bool,
+	/// what else to show in camxes format: terminators (elided terminators) or selmaho (the selmaho of each word); can be repeated
+	#[argh(option)]
+	pub show: Vec<Shown>,
+	/// treat each line of the input as a separate text
+	#[argh(switch)]
+	pub batch: bool,
+	/// files to read
+	#[argh(positional)]
+	pub files: Vec<PathBuf>,
+}
+
+impl Parse {
+	/// The options for camxes format, from `--show`.
+	pub fn camxes_options(&self) -> sneturfahi::parse::camxes::Options {
+		sneturfahi::parse::camxes::Options {
+			terminators: self.show.contains(&Shown::Terminators),
+			selmaho: self.show.contains(&Shown::Selmaho),
+		}
+	}
+}
+
+/// check that text parses, printing only errors
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand, name = "check")]
+pub struct Check {
+	/// output format: text or json
+	#[argh(option, default = "ReportFormat::Text")]
+	pub format: ReportFormat,
+	/// treat each line of the input as a separate text
+	#[argh(switch)]
+	pub batch: bool,
+	/// print the nodes parsed for each rule and the memory allocated for each type to standard error after checking
+	#[argh(switch)]
+	pub stats: bool,
+	/// files to read
+	#[argh(positional)]
+	pub files: Vec<PathBuf>,
+}
+
+/// parse text and print the nodes that match a selector
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand, name = "grep")]
+pub struct Grep {
+	/// output format: text or json
+	#[argh(option, default = "ReportFormat::Text")]
+	pub format: ReportFormat,
+	/// treat each line of the input as a separate text
+	#[argh(switch)]
+	pub batch: bool,
+	/// the selector to search for
+	#[argh(positional)]
+	pub selector: Selector,
+	/// files to read
+	#[argh(positional)]
+	pub files: Vec<PathBuf>,
+}
+
+/// reprint text with normalized spacing, pauses, and sentence breaks
+#[derive(argh::FromArgs, Debug)]
+#[argh(subcommand, name = "format")]
+pub struct Format {
+	/// treat each line of the input as a separate text
+	#[argh(switch)]
+	pub batch: bool,
+	/// files to read
+	#[argh(positional)]
+	pub files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+	Text,
+	Json,
+	Brackets,
+	Camxes,
+}
+
+impl std::str::FromStr for OutputFormat {
+	type Err = String;
+
+	fn from_str(raw: &str) -> Result<Self, String> {
+		match raw {
+			"text" => Ok(Self::Text),
+			"json" => Ok(Self::Json),
+			"brackets" => Ok(Self::Brackets),
+			"camxes" => Ok(Self::Camxes),
+			other => Err(format!(
+				"unknown format {other:?}. valid formats are `text`, `json`, `brackets`, and `camxes`"
+			)),
+		}
+	}
+}
+
+/// The formats for the `parse` command, which include some that only make sense for trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeFormat {
+	Text,
+	Json,
+	Brackets,
+	Camxes,
+	Dot,
+	Sexp,
+}
+
+impl std::str::FromStr for TreeFormat {
+	type Err = String;
+
+	fn from_str(raw: &str) -> Result<Self, String> {
+		match raw {
+			"dot" => Ok(Self::Dot),
+			"sexp" => Ok(Self::Sexp),
+			other => match other.parse() {
+				Ok(OutputFormat::Text) => Ok(Self::Text),
+				Ok(OutputFormat::Json) => Ok(Self::Json),
+				Ok(OutputFormat::Brackets) => Ok(Self::Brackets),
+				Ok(OutputFormat::Camxes) => Ok(Self::Camxes),
+				Err(_) => Err(format!(
+					"unknown format {other:?}. valid formats are `text`, `json`, `brackets`, `camxes`, `dot`, and `sexp`"
+				)),
+			},
+		}
+	}
+}
+
+/// The extra information that `parse` can show in camxes format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shown {
+	Terminators,
+	Selmaho,
+}
+
+impl std::str::FromStr for Shown {
+	type Err = String;
+
+	fn from_str(raw: &str) -> Result<Self, String> {
+		match raw {
+			"terminators" => Ok(Self::Terminators),
+			"selmaho" => Ok(Self::Selmaho),
+			other => Err(format!(
+				"unknown value {other:?}. valid values are `terminators` and `selmaho`"
+			)),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+	Text,
+	Json,
+}
+
+impl std::str::FromStr for ReportFormat {
+	type Err = String;
+
+	fn from_str(raw: &str) -> Result<Self, String> {
+		match raw {
+			"text" => Ok(Self::Text),
+			"json" => Ok(Self::Json),
+			other => Err(format!(
+				"unknown format {other:?}. valid formats are `text` and `json`"
+			)),
+		}
+	}
+}
@@ -0,0 +1,387 @@
+//! The `sneturfahi` command-line tool.
+//!
+//! Exit codes: 0 if every input was processed successfully, 1 if any input failed to lex or parse, and 2 if an input could not be read or output could not be written.
+
+#![deny(
+	absolute_paths_not_starting_with_crate,
+	elided_lifetimes_in_paths,
+	explicit_outlives_requirements,
+	future_incompatible,
+	keyword_idents,
+	macro_use_extern_crate,
+	meta_variable_misuse,
+	missing_abi,
+	non_ascii_idents,
+	nonstandard_style,
+	noop_method_call,
+	rust_2018_idioms
+)]
+#![warn(clippy::pedantic, unused_qualifications)]
+#![forbid(unsafe_code)]
+
+use std::io::{BufRead as _, IsTerminal as _, Write as _};
+use std::process::ExitCode;
+
+use args::{Command, OutputFormat, ReportFormat, TreeFormat};
+use sneturfahi::lex::{Selmaho, Token};
+use sneturfahi::parse::select::Match;
+use sneturfahi::span::Location;
+use sneturfahi::Span;
+
+mod args;
+mod output;
+
+/// A single text to process.
+struct Input<'a> {
+	/// Where the text came from: a file name, or `<stdin>`.
+	origin: &'a str,
+	/// The 1-based line number of the text within its origin, in batch mode.
+	line: Option<usize>,
+	text: &'a str,
+}
+
+impl Input<'_> {
+	/// Describe `location` in the style of compiler diagnostics: `origin:line:column`.
+	fn describe(&self, location: Location) -> String {
+		let before = &self.text[..location as usize];
+		let line_in_text = before.matches('\n').count();
+		let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+		format!(
+			"{}:{}:{column}",
+			self.origin,
+			self.line.unwrap_or(1) + line_in_text
+		)
+	}
+}
+
+/// An error in the input text.
+struct Diagnostic {
+	span: Span,
+	message: String,
+}
+
+impl Diagnostic {
+	fn new(error: &sneturfahi::Error, input: &str) -> Self {
+		let span = error.span();
+		let message = match error {
+			sneturfahi::Error::Parse {
+				selmaho: Some(Selmaho::AnyText),
+				..
+			} => format!("{:?} is not a Lojban word", span.slice(input).unwrap()),
+			// the location is printed before the message
+			sneturfahi::Error::Parse { error, .. } => error.to_string(),
+			_ => error.to_string(),
+		};
+		Self { span, message }
+	}
+
+	fn print(&self, input: &Input<'_>) {
+		eprintln!(
+			"{}: error: {}",
+			input.describe(self.span.start),
+			self.message
+		);
+	}
+}
+
+fn lex(input: &Input<'_>) -> Result<Vec<Token>, Diagnostic> {
+	sneturfahi::lex(input.text)
+		.collect::<Result<_, _>>()
+		.map_err(|error| Diagnostic::new(&error.into(), input.text))
+}
+
+fn parse<'arena>(
+	input: &Input<'_>,
+	arena: &'arena sneturfahi::Arena,
+) -> Result<sneturfahi::Cst<'arena>, Diagnostic> {
+	sneturfahi::parse_in(input.text, arena).map_err(|error| Diagnostic::new(&error, input.text))
+}
+
+/// Process one input, writing the result to `out`. Returns whether the input was valid.
+///
+/// `arena` is reset and reused for each input.
+fn process(
+	command: &Command,
+	input: &Input<'_>,
+	arena: &mut sneturfahi::Arena,
+	out: &mut impl std::io::Write,
+) -> std::io::Result<bool> {
+	let text = input.text;
+	if command.batch() && text.trim().is_empty() {
+		if let Some(placeholder) = placeholder(command) {
+			writeln!(out, "{placeholder}")?;
+		}
+		return Ok(true);
+	}
+	arena.reset();
+	let arena = &*arena;
+	let result = match command {
+		Command::Decompose(args) => {
+			let words: Vec<_> = sneturfahi::decompose(text).collect();
+			Ok(output::words(&words, text, args.format))
+		}
+		Command::Lex(args) => lex(input).map(|tokens| output::tokens(&tokens, text, args.format)),
+		Command::Parse(args) => parse(input, arena).map(|cst| {
+			log::info!("size of CST arena: {}", arena.allocated_bytes());
+			output::tree(
+				cst.root(),
+				cst.tokens(),
+				text,
+				args.format,
+				args.collapse,
+				args.camxes_options(),
+			)
+		}),
+		Command::Check(args) => {
+			let result = parse(input, arena).map(drop);
+			return check_report(input, &result, args.format, out);
+		}
+		Command::Grep(args) => {
+			let result = parse(input, arena).map(|cst| {
+				let found = args.selector.select(cst.root(), cst.tokens(), text);
+				found
+					.iter()
+					.map(|found| grep_line(input, found, args.format))
+					.collect::<Vec<_>>()
+			});
+			return match result {
+				Ok(lines) => {
+					for line in lines {
+						writeln!(out, "{line}")?;
+					}
+					Ok(true)
+				}
+				Err(diagnostic) => {
+					diagnostic.print(input);
+					Ok(false)
+				}
+			};
+		}
+		Command::Format(_) => lex(input).map(|tokens| output::formatted(&tokens, text)),
+	};
+
+	match result {
+		Ok(rendered) => {
+			writeln!(out, "{rendered}")?;
+			Ok(true)
+		}
+		Err(diagnostic) => {
+			diagnostic.print(input);
+			if let Some(placeholder) = placeholder(command).filter(|_| command.batch()) {
+				writeln!(out, "{placeholder}")?;
+			}
+			Ok(false)
+		}
+	}
+}
+
+/// The line printed in batch mode in place of the output for a blank or invalid line, to keep the output aligned with the input lines.
+///
+/// This is `None` for commands that do not print one line for each input.
+fn placeholder(command: &Command) -> Option<&'static str> {
+	match command {
+		Command::Decompose(args::Decompose {
+			format: OutputFormat::Json,
+			..
+		})
+		| Command::Lex(args::Lex {
+			format: OutputFormat::Json,
+			..
+		})
+		| Command::Parse(args::Parse {
+			format: TreeFormat::Json,
+			..
+		}) => Some("null"),
+		Command::Decompose(_) | Command::Lex(_) | Command::Parse(_) | Command::Format(_) => Some(""),
+		Command::Check(_) | Command::Grep(_) => None,
+	}
+}
+
+fn check_report(
+	input: &Input<'_>,
+	result: &Result<(), Diagnostic>,
+	format: ReportFormat,
+	out: &mut impl std::io::Write,
+) -> std::io::Result<bool> {
+	match format {
+		ReportFormat::Text => {
+			if let Err(diagnostic) = result {
+				writeln!(
+					out,
+					"{}: error: {}",
+					input.describe(diagnostic.span.start),
+					diagnostic.message
+				)?;
+			}
+		}
+		ReportFormat::Json => {
+			let report = match result {
+				Ok(()) => serde_json::json!({
+					"origin": input.origin,
+					"line": input.line,
+					"ok": true,
+				}),
+				Err(diagnostic) => serde_json::json!({
+					"origin": input.origin,
+					"line": input.line,
+					"ok": false,
+					"error": {
+						"message": diagnostic.message,
+						"span": diagnostic.span,
+					},
+				}),
+			};
+			writeln!(out, "{report}")?;
+		}
+	}
+	Ok(result.is_ok())
+}
+
+/// Describe a node found by the `grep` command.
+fn grep_line(input: &Input<'_>, found: &Match<'_>, format: ReportFormat) -> String {
+	let text = found.span.and_then(|span| span.slice(input.text));
+	match format {
+		ReportFormat::Text => {
+			let location = found.span.map_or_else(
+				|| input.origin.to_owned(),
+				|span| input.describe(span.start),
+			);
+			match text {
+				Some(text) => format!("{location}: {} {text:?}", found.node.name()),
+				None => format!("{location}: {}", found.node.name()),
+			}
+		}
+		ReportFormat::Json => serde_json::json!({
+			"origin": input.origin,
+			"line": input.line,
+			"name": found.node.name(),
+			"label": found.label,
+			"span": found.span,
+			"text": text,
+		})
+		.to_string(),
+	}
+}
+
+/// Print a table of the nodes parsed for each rule and the memory allocated for each type, as recorded by an arena, to standard error.
+fn print_stats(stats: &[sneturfahi::parse::RuleStats]) {
+	let parsed_total: usize = stats.iter().map(|stats| stats.parsed_bytes).sum();
+	let allocated_total: usize = stats.iter().map(|stats| stats.bytes).sum();
+	eprintln!(
+		"{:>12} {:>10} {:>6}  {:>12} {:>10}  rule",
+		"parsed bytes", "parsed", "%", "allocated", "nodes"
+	);
+	for stats in stats {
+		#[allow(clippy::cast_precision_loss)] // only for display
+		let percent = stats.parsed_bytes as f64 / parsed_total.max(1) as f64 * 100.0;
+		eprintln!(
+			"{:>12} {:>10} {percent:>6.2}  {:>12} {:>10}  {}",
+			stats.parsed_bytes, stats.parsed, stats.bytes, stats.nodes, stats.name
+		);
+	}
+	eprintln!(
+		"{parsed_total:>12} {:>10} {:>6.2}  {allocated_total:>12} {:>10}  total",
+		"", 100.0, ""
+	);
+}
+
+/// Call `callback` on each input, reading from `files` or standard input.
+///
+/// In batch mode, each line is its own input, and standard input is read one line at a time.
+/// If standard input is a terminal, each line is processed as soon as it is entered.
+fn for_each_input(
+	command: &Command,
+	mut callback: impl FnMut(&Input<'_>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+	let files = command.files();
+	if files.is_empty() {
+		let mut stdin = std::io::stdin().lock();
+		if stdin.is_terminal() {
+			let mut line = String::new();
+			loop {
+				eprint!("> ");
+				line.clear();
+				if stdin.read_line(&mut line)? == 0 {
+					break;
+				}
+				for_each_in(command, "<stdin>", line.trim().as_bytes(), &mut callback)?;
+			}
+		} else {
+			for_each_in(command, "<stdin>", stdin, &mut callback)?;
+		}
+	} else {
+		for path in files {
+			let text = std::fs::read_to_string(path).map_err(|error| {
+				std::io::Error::new(error.kind(), format!("{}: {error}", path.display()))
+			})?;
+			for_each_in(
+				command,
+				&path.display().to_string(),
+				text.as_bytes(),
+				&mut callback,
+			)?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Call `callback` on the inputs read from `reader`: each of its lines in batch mode, or else all of it.
+fn for_each_in(
+	command: &Command,
+	origin: &str,
+	mut reader: impl std::io::BufRead,
+	callback: &mut impl FnMut(&Input<'_>) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+	if command.batch() {
+		for (idx, line) in reader.lines().enumerate() {
+			callback(&Input {
+				origin,
+				line: Some(idx + 1),
+				text: &line?,
+			})?;
+		}
+		Ok(())
+	} else {
+		let mut text = String::new();
+		reader.read_to_string(&mut text)?;
+		callback(&Input {
+			origin,
+			line: None,
+			text: text.trim_end(),
+		})
+	}
+}
+
+fn main() -> ExitCode {
+	let args: args::Args = argh::from_env();
+
+	if args.verbose {
+		simplelog::SimpleLogger::init(log::LevelFilter::Trace, simplelog::Config::default()).unwrap();
+	}
+
+	let mut arena = match &args.command {
+		Command::Check(check) if check.stats => sneturfahi::Arena::with_stats(),
+		_ => sneturfahi::Arena::new(),
+	};
+	let mut out = std::io::stdout().lock();
+	let mut all_ok = true;
+	let result = for_each_input(&args.command, |input| {
+		all_ok &= process(&args.command, input, &mut arena, &mut out)?;
+		Ok(())
+	})
+	.and_then(|()| out.flush());
+
+	if let Some(stats) = arena.stats() {
+		print_stats(&stats);
+	}
+
+	match result {
+		Ok(()) if all_ok => ExitCode::SUCCESS,
+		Ok(()) => ExitCode::from(1),
+		Err(error) => {
+			eprintln!("sneturfahi: {error}");
+			ExitCode::from(2)
+		}
+	}
+}
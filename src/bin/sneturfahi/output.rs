@@ -0,0 +1,233 @@
+//! Rendering of decomposer, lexer, and parser output in the formats supported by the CLI.
+
+use std::fmt::Write as _;
+
+use sneturfahi::lex::{Selmaho, Token};
+use sneturfahi::parse::serialize::SerializeNode;
+use sneturfahi::parse::tree_node::TreeNode;
+use sneturfahi::parse::{camxes, export};
+use sneturfahi::Span;
+
+use crate::args::{OutputFormat, TreeFormat};
+
+#[derive(serde::Serialize)]
+struct WithText<'a, T> {
+	#[serde(flatten)]
+	inner: T,
+	text: &'a str,
+}
+
+pub fn words(spans: &[Span], input: &str, format: OutputFormat) -> String {
+	let text = |span: &Span| span.slice(input).unwrap();
+	match format {
+		// camxes has no separate decomposition mode
+		OutputFormat::Text | OutputFormat::Camxes => {
+			spans.iter().map(text).collect::<Vec<_>>().join(" ")
+		}
+		OutputFormat::Json => serde_json::to_string(
+			&spans
+				.iter()
+				.map(|span| WithText {
+					inner: span,
+					text: text(span),
+				})
+				.collect::<Vec<_>>(),
+		)
+		.unwrap(),
+		OutputFormat::Brackets => spans
+			.iter()
+			.map(|span| format!("[{}]", text(span)))
+			.collect::<Vec<_>>()
+			.join(" "),
+	}
+}
+
+fn selmaho_label(token: &Token) -> String {
+	format!(
+		"{}{}",
+		token.selmaho.as_repr(),
+		if token.experimental { "*" } else { "" }
+	)
+}
+
+pub fn tokens(tokens: &[Token], input: &str, format: OutputFormat) -> String {
+	let text = |token: &Token| token.span.slice(input).unwrap();
+	match format {
+		OutputFormat::Text => tokens
+			.iter()
+			.map(|token| {
+				format!(
+					"{:?}\t{}\t{}",
+					token.span,
+					selmaho_label(token),
+					text(token)
+				)
+			})
+			.collect::<Vec<_>>()
+			.join("\n"),
+		OutputFormat::Json => serde_json::to_string(
+			&tokens
+				.iter()
+				.map(|token| WithText {
+					inner: token,
+					text: text(token),
+				})
+				.collect::<Vec<_>>(),
+		)
+		.unwrap(),
+		OutputFormat::Brackets => tokens
+			.iter()
+			.map(|token| format!("[{} {}]", selmaho_label(token), text(token)))
+			.collect::<Vec<_>>()
+			.join(" "),
+		OutputFormat::Camxes => camxes::morphology(tokens, input),
+	}
+}
+
+fn node_label(node: &dyn TreeNode) -> String {
+	format!(
+		"{}{}",
+		node.name(),
+		if node.experimental() { "*" } else { "" }
+	)
+}
+
+fn children(node: &dyn TreeNode) -> Vec<&dyn TreeNode> {
+	let mut children = Vec::new();
+	node.for_each_child(&mut |child| children.push(child));
+	children
+}
+
+fn labeled_children(node: &dyn TreeNode) -> Vec<(Option<&'static str>, &dyn TreeNode)> {
+	let mut children = Vec::new();
+	node.for_each_labeled_child(&mut |label, child| children.push((label, child)));
+	children
+}
+
+/// Skip over nodes with exactly one child, if `collapse` is set.
+fn collapsed(mut node: &dyn TreeNode, collapse: bool) -> &dyn TreeNode {
+	if collapse {
+		while let [only_child] = children(node)[..] {
+			node = only_child;
+		}
+	}
+	node
+}
+
+pub fn tree(
+	root: &dyn TreeNode,
+	tokens: &[Token],
+	input: &str,
+	format: TreeFormat,
+	collapse: bool,
+	camxes_options: camxes::Options,
+) -> String {
+	fn text(
+		node: &dyn TreeNode,
+		label: Option<&str>,
+		tokens: &[Token],
+		input: &str,
+		collapse: bool,
+		level: usize,
+		out: &mut String,
+	) {
+		let node = collapsed(node, collapse);
+		write!(out, "{blank:level$}", blank = "").unwrap();
+		if let Some(label) = label {
+			write!(out, "{label}: ").unwrap();
+		}
+		out.push_str(&node_label(node));
+		if let Some(span) = node.span(tokens) {
+			write!(out, " @ {span:?} {:?}", span.slice(input).unwrap()).unwrap();
+		}
+		out.push('\n');
+		for (label, child) in labeled_children(node) {
+			text(child, label, tokens, input, collapse, level + 2, out);
+		}
+	}
+
+	fn brackets(
+		node: &dyn TreeNode,
+		tokens: &[Token],
+		input: &str,
+		collapse: bool,
+		out: &mut String,
+	) {
+		let node = collapsed(node, collapse);
+		let children = children(node);
+		write!(out, "[{}", node_label(node)).unwrap();
+		if children.is_empty() {
+			if let Some(span) = node.span(tokens) {
+				write!(out, " {}", span.slice(input).unwrap()).unwrap();
+			}
+		}
+		for child in children {
+			out.push(' ');
+			brackets(child, tokens, input, collapse, out);
+		}
+		out.push(']');
+	}
+
+	let mut out = String::new();
+	match format {
+		TreeFormat::Text => {
+			text(root, None, tokens, input, collapse, 0, &mut out);
+			// the caller adds the final newline
+			out.pop();
+		}
+		TreeFormat::Json => {
+			out =
+				serde_json::to_string(&SerializeNode::new(root, tokens, input).collapse(collapse)).unwrap();
+		}
+		TreeFormat::Brackets => brackets(root, tokens, input, collapse, &mut out),
+		TreeFormat::Camxes => out = camxes::brackets(root, tokens, input, camxes_options),
+		TreeFormat::Dot => {
+			out = export::dot(root, tokens, input, export::Options { collapse });
+			out.pop();
+		}
+		TreeFormat::Sexp => out = export::sexp(root, tokens, input, export::Options { collapse }),
+	}
+	out
+}
+
+/// Reprint the lexed text with a single space between words, a new line for each sentence, and a blank line for each paragraph.
+///
+/// Pauses are added before words that start with a vowel and around cmevla, since those are required in written Lojban.
+/// Quoted non-Lojban text is reproduced exactly, apart from the spaces around it.
+pub fn formatted(tokens: &[Token], input: &str) -> String {
+	let mut out = String::new();
+	let mut previous = None;
+	for token in tokens {
+		let text = token.span.slice(input).unwrap();
+		// the text of a quote includes the spaces around it, which are replaced by the spaces between words
+		let text = if token.selmaho == Selmaho::AnyText {
+			text.trim()
+		} else {
+			text
+		};
+		if text.is_empty() {
+			continue;
+		}
+		out.push_str(match (previous, token.selmaho) {
+			(None, _) => "",
+			// `ni'o .i` and `ni'oni'o` stay on one line
+			(Some(Selmaho::Niho), Selmaho::I | Selmaho::Niho) => " ",
+			(_, Selmaho::Niho) => "\n\n",
+			(_, Selmaho::I) => "\n",
+			_ => " ",
+		});
+		previous = Some(token.selmaho);
+
+		match token.selmaho {
+			Selmaho::AnyText | Selmaho::ZoiDelimiter => out.push_str(text),
+			Selmaho::Cmevla => write!(out, ".{}.", text.trim_matches(',')).unwrap(),
+			_ => {
+				if text.starts_with(['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U']) {
+					out.push('.');
+				}
+				out.push_str(text);
+			}
+		}
+	}
+	out
+}
@@ -0,0 +1,477 @@
+//! Word-by-word glosses of a text, laid out as in the Leipzig glossing rules.
+//!
+//! [`gloss`] gives three lines for a text: the words as they are [lexed](crate::lex()), so that compounds like `minajimpe` are split into `mi`, `na` and `jimpe`;
+//! the [`Selmaho`] of each word, or its kind for brivla and cmevla; and a short gloss from a [`Dictionary`].
+//! Lujvo are glossed by their rafsi, as in `Lojbanic-language` for `jbobau`, and quotes are kept whole, with the quoted text as its own gloss.
+//!
+//! ```rust
+//! use sneturfahi::dictionary::interlinear::{self, Format};
+//! use sneturfahi::dictionary::Dictionary;
+//!
+//! let glossed = interlinear::gloss("minajimpe lo jbobau", Dictionary::sample()).unwrap();
+//! assert_eq!(
+//! 	glossed.render(Format::Plain),
+//! 	"mi    na   jimpe       lo             jbobau\n\
+//! 	 KOhA  NA   gismu       LE             lujvo\n\
+//! 	 I     not  understand  the.one.which  Lojbanic-language"
+//! );
+//! ```
+
+use std::fmt::Write as _;
+
+use super::Dictionary;
+use crate::lex::{Selmaho, Token};
+use crate::parse::cst::rules::{LohuSumti, ZoSumti, ZoiSumti};
+use crate::parse::cst::visit::Visitor;
+use crate::span::Span;
+
+/// The formats that an [`Interlinear`] can be [rendered](Interlinear::render) in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+	/// Three lines of plain text, with the words aligned in columns by padding with spaces.
+	Plain,
+	/// An HTML `<table>` with a row for each line and a cell for each word.
+	Html,
+}
+
+/// The glosses of a text, from [`gloss`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interlinear {
+	/// The words of the text, in order.
+	pub words: Vec<GlossedWord>,
+}
+
+/// A word of an [`Interlinear`] gloss, or a whole quote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlossedWord {
+	/// The span of the word.
+	pub span: Span,
+	/// The text of the word.
+	pub text: String,
+	/// The selmaho of the word, or `None` for the text of a quote.
+	pub selmaho: Option<Selmaho>,
+	/// The rafsi of a lujvo, without hyphens, or an empty list for other words.
+	pub rafsi: Vec<String>,
+	/// The gloss, with the words of a gloss joined by `.` and those of rafsi by `-`, or an empty string if the word is not in the dictionary.
+	pub gloss: String,
+}
+
+impl GlossedWord {
+	/// The second line of the gloss: the selmaho of the word, or `quote` for the text of a quote.
+	#[must_use]
+	pub fn class(&self) -> &'static str {
+		self.selmaho.map_or("quote", Selmaho::as_repr)
+	}
+}
+
+/// Gloss each word of `input` with `dictionary`.
+///
+/// Quotes are found in the tree of the text.
+/// If the text does not parse, its words are still glossed, and quotes are found from the tokens alone: the word after `zo`, the words between `lo'u` and `le'u`, and the text of `zoi` and similar quotes.
+/// Words that are not in the dictionary have an empty gloss, except cmevla, which are their own glosses, and lujvo, whose rafsi are glossed separately.
+///
+/// # Errors
+/// Returns an error if the text cannot be lexed.
+pub fn gloss(input: &str, dictionary: &Dictionary) -> Result<Interlinear, crate::Error> {
+	let arena = crate::Arena::new();
+	let (tokens, quotes) = match crate::parse_in(input, &arena) {
+		Ok(cst) => {
+			let mut quotes = Quotes {
+				tokens: cst.tokens(),
+				spans: Vec::new(),
+			};
+			cst.visit(&mut quotes);
+			(cst.tokens().to_vec(), quotes.spans)
+		}
+		Err(crate::Error::Parse { .. }) => {
+			let tokens = crate::lex(input).collect::<Result<Vec<_>, _>>()?;
+			let quotes = quoted_tokens(&tokens);
+			(tokens, quotes)
+		}
+		Err(error) => return Err(error),
+	};
+
+	let mut words: Vec<GlossedWord> = Vec::new();
+	for token in &tokens {
+		let quote = quotes
+			.iter()
+			.find(|quote| quote.start <= token.span.start && token.span.end <= quote.end);
+		let word = if let Some(&quote) = quote {
+			if words.last().is_some_and(|last| last.span == quote) {
+				continue;
+			}
+			let text = quote.slice(input).unwrap_or_default().to_owned();
+			GlossedWord {
+				span: quote,
+				gloss: format!("\"{text}\""),
+				text,
+				selmaho: None,
+				rafsi: Vec::new(),
+			}
+		} else {
+			let text = token.span.slice(input).unwrap_or_default().to_owned();
+			let rafsi = if token.selmaho == Selmaho::Lujvo {
+				split_rafsi(&super::normalize(&text)).unwrap_or_default()
+			} else {
+				Vec::new()
+			};
+			let gloss = if !rafsi.is_empty() {
+				rafsi
+					.iter()
+					.map(|rafsi| {
+						dictionary
+							.rafsi(rafsi)
+							.and_then(|entry| entry.glosses.first())
+							.map_or_else(|| rafsi.clone(), |gloss| join(gloss))
+					})
+					.collect::<Vec<_>>()
+					.join("-")
+			} else if let Some(gloss) = dictionary
+				.get(&text)
+				.and_then(|entry| entry.glosses.first())
+			{
+				join(gloss)
+			} else if token.selmaho == Selmaho::Cmevla {
+				text.clone()
+			} else {
+				String::new()
+			};
+			GlossedWord {
+				span: token.span,
+				text,
+				selmaho: Some(token.selmaho),
+				rafsi,
+				gloss,
+			}
+		};
+		words.push(word);
+	}
+	Ok(Interlinear { words })
+}
+
+impl Interlinear {
+	/// Render the three lines of the gloss.
+	///
+	/// ```rust
+	/// use sneturfahi::dictionary::interlinear::{self, Format};
+	/// use sneturfahi::dictionary::Dictionary;
+	///
+	/// let glossed = interlinear::gloss("zo coi", Dictionary::sample()).unwrap();
+	/// assert_eq!(
+	/// 	glossed.render(Format::Html),
+	/// 	"<table class=\"interlinear\">\
+	/// 	 <tr class=\"words\"><td>zo</td><td class=\"quote\">coi</td></tr>\
+	/// 	 <tr class=\"classes\"><td>ZO</td><td class=\"quote\">quote</td></tr>\
+	/// 	 <tr class=\"glosses\"><td></td><td class=\"quote\">&quot;coi&quot;</td></tr>\
+	/// 	 </table>"
+	/// );
+	/// ```
+	#[must_use]
+	pub fn render(&self, format: Format) -> String {
+		match format {
+			Format::Plain => self.plain(),
+			Format::Html => self.html(),
+		}
+	}
+
+	fn lines(&self) -> [Vec<&str>; 3] {
+		[
+			self.words.iter().map(|word| word.text.as_str()).collect(),
+			self.words.iter().map(GlossedWord::class).collect(),
+			self.words.iter().map(|word| word.gloss.as_str()).collect(),
+		]
+	}
+
+	fn plain(&self) -> String {
+		let lines = self.lines();
+		let widths = (0..self.words.len())
+			.map(|column| {
+				lines
+					.iter()
+					.map(|line| line[column].chars().count())
+					.max()
+					.unwrap_or_default()
+			})
+			.collect::<Vec<_>>();
+		lines
+			.iter()
+			.map(|line| {
+				let mut out = String::new();
+				for (cell, width) in line.iter().zip(&widths) {
+					write!(out, "{cell:width$}  ").unwrap();
+				}
+				out.trim_end().to_owned()
+			})
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+
+	fn html(&self) -> String {
+		let mut out = String::from("<table class=\"interlinear\">");
+		for (line, class) in self.lines().iter().zip(["words", "classes", "glosses"]) {
+			write!(out, "<tr class=\"{class}\">").unwrap();
+			for (cell, word) in line.iter().zip(&self.words) {
+				let quote = if word.selmaho.is_none() {
+					" class=\"quote\""
+				} else {
+					""
+				};
+				write!(out, "<td{quote}>{}</td>", escape(cell)).unwrap();
+			}
+			out.push_str("</tr>");
+		}
+		out.push_str("</table>");
+		out
+	}
+}
+
+impl std::fmt::Display for Interlinear {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		formatter.write_str(&self.plain())
+	}
+}
+
+/// Collects the spans of quoted text, which is glossed as a single unit.
+struct Quotes<'tokens> {
+	tokens: &'tokens [Token],
+	spans: Vec<Span>,
+}
+
+impl<'ast> Visitor<'ast> for Quotes<'_> {
+	fn visit_zoi_sumti(&mut self, node: &'ast ZoiSumti) {
+		self.spans.push(node.text.0.get(self.tokens).span);
+	}
+
+	fn visit_lohu_sumti(&mut self, node: &'ast LohuSumti<'_>) {
+		if let (Some(first), Some(last)) = (node.inner.first(), node.inner.last()) {
+			let first = first.token.get(self.tokens).span;
+			let last = last.token.get(self.tokens).span;
+			self.spans.push(Span::new(first.start, last.end));
+		}
+	}
+
+	fn visit_zo_sumti(&mut self, node: &'ast ZoSumti) {
+		self.spans.push(node.quoted.token.get(self.tokens).span);
+	}
+}
+
+/// The spans of quoted text in `tokens`, for text that does not parse.
+fn quoted_tokens(tokens: &[Token]) -> Vec<Span> {
+	let mut spans = Vec::new();
+	let mut tokens = tokens.iter();
+	while let Some(token) = tokens.next() {
+		match token.selmaho {
+			Selmaho::Zo => spans.extend(tokens.next().map(|quoted| quoted.span)),
+			Selmaho::AnyText => spans.push(token.span),
+			Selmaho::Lohu => {
+				let inner = tokens
+					.by_ref()
+					.take_while(|token| token.selmaho != Selmaho::Lehu)
+					.map(|token| token.span)
+					.collect::<Vec<_>>();
+				if let (Some(first), Some(last)) = (inner.first(), inner.last()) {
+					spans.push(Span::new(first.start, last.end));
+				}
+			}
+			_ => {}
+		}
+	}
+	spans
+}
+
+/// Join the words of a gloss with `.`, as the Leipzig rules do for glosses of several words.
+fn join(gloss: &str) -> String {
+	gloss.split_whitespace().collect::<Vec<_>>().join(".")
+}
+
+fn escape(text: &str) -> String {
+	text
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+/// The consonant-vowel shape of some letters, such as `CVC` for `sel` and `CV'V` for `pa'i`.
+fn shape(letters: &str) -> String {
+	letters
+		.chars()
+		.map(|letter| match letter {
+			'a' | 'e' | 'i' | 'o' | 'u' => 'V',
+			'\'' | 'y' => letter,
+			_ => 'C',
+		})
+		.collect()
+}
+
+/// Split a normalized lujvo into its rafsi, dropping the `y`, `r` and `n` hyphens, or `None` if it is not made of rafsi.
+fn split_rafsi(lujvo: &str) -> Option<Vec<String>> {
+	let mut rafsi = Vec::new();
+	split_rafsi_from(lujvo, true, &mut rafsi).then_some(rafsi)
+}
+
+fn split_rafsi_from(rest: &str, first: bool, rafsi: &mut Vec<String>) -> bool {
+	for length in [5, 4, 3] {
+		let Some((candidate, after)) = rest.split_at_checked(length) else {
+			continue;
+		};
+		let hyphenated = after.starts_with('y');
+		let (fits, short) = match shape(candidate).as_str() {
+			"CVCCV" | "CCVCV" => (after.is_empty() || hyphenated, false),
+			"CVCC" | "CCVC" => (hyphenated, false),
+			"CV'V" => (true, true),
+			"CVV" => (["ai", "ei", "oi", "au"].contains(&&candidate[1..]), true),
+			"CCV" => (true, false),
+			"CVC" => (!after.is_empty(), false),
+			_ => (false, false),
+		};
+		if !fits {
+			continue;
+		}
+		if after.is_empty() {
+			if !rafsi.is_empty() {
+				rafsi.push(candidate.to_owned());
+				return true;
+			}
+			continue;
+		}
+		rafsi.push(candidate.to_owned());
+		let mut afters = vec![after.strip_prefix('y').unwrap_or(after)];
+		// a CVV rafsi at the start may be followed by an `r` or `n` hyphen
+		if first && short {
+			afters.extend(
+				["r", "n"]
+					.iter()
+					.filter_map(|hyphen| after.strip_prefix(hyphen)),
+			);
+		}
+		if afters
+			.into_iter()
+			.any(|after| !after.is_empty() && split_rafsi_from(after, false, rafsi))
+		{
+			return true;
+		}
+		rafsi.pop();
+	}
+	false
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn describe(input: &str) -> String {
+		gloss(input, Dictionary::sample())
+			.unwrap()
+			.words
+			.iter()
+			.map(|word| format!("{}/{}/{}", word.text, word.class(), word.gloss))
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:literal) => {
+			#[test]
+			fn $name() {
+				assert_eq!(describe($input), $expected);
+			}
+		};
+	}
+
+	make_test!(
+		words,
+		"mi klama le zarci",
+		"mi/KOhA/I klama/gismu/come le/LE/the.described zarci/gismu/market"
+	);
+	make_test!(
+		compound,
+		"minajimpe",
+		"mi/KOhA/I na/NA/not jimpe/gismu/understand"
+	);
+	make_test!(
+		lujvo,
+		"mi selpa'i do",
+		"mi/KOhA/I selpa'i/lujvo/2nd.conversion-love do/KOhA/you"
+	);
+	make_test!(
+		unknown_rafsi,
+		"mi klagau",
+		"mi/KOhA/I klagau/lujvo/come-gau"
+	);
+	make_test!(
+		cmevla,
+		"la .djan. klama",
+		"la/LA/named djan/cmevla/djan klama/gismu/come"
+	);
+	make_test!(
+		zo,
+		"zo klama valsi",
+		"zo/ZO/ klama/quote/\"klama\" valsi/gismu/word"
+	);
+	make_test!(
+		zoi,
+		"zoi gy hello world gy",
+		"zoi/ZOI/ gy/(zoi delimiter)/ hello world/quote/\"hello world\" gy/(zoi delimiter)/"
+	);
+	make_test!(
+		lohu,
+		"lo'u mi klama le'u cu valsi",
+		"lo'u/LOhU/ mi klama/quote/\"mi klama\" le'u/LEhU/ cu/CU/selbri.separator valsi/gismu/word"
+	);
+
+	#[test]
+	fn rafsi() {
+		let split = |lujvo: &str| split_rafsi(lujvo).map(|rafsi| rafsi.join(" "));
+		assert_eq!(split("selpa'i").as_deref(), Some("sel pa'i"));
+		assert_eq!(split("jbobau").as_deref(), Some("jbo bau"));
+		assert_eq!(split("brivla").as_deref(), Some("bri vla"));
+		assert_eq!(split("klagau").as_deref(), Some("kla gau"));
+		assert_eq!(split("lojbymlatu").as_deref(), Some("lojb mlatu"));
+		assert_eq!(split("zdanygerku").as_deref(), Some("zdan gerku"));
+		assert_eq!(split("sairgau").as_deref(), Some("sai gau"));
+		assert_eq!(split("klama"), None);
+		assert_eq!(split("mi"), None);
+	}
+
+	#[test]
+	fn lookup_rafsi() {
+		let dictionary = Dictionary::sample();
+		let word = |rafsi: &str| dictionary.rafsi(rafsi).map(|entry| entry.word.as_str());
+		assert_eq!(word("kla"), Some("klama"));
+		assert_eq!(word("klam"), Some("klama"));
+		assert_eq!(word("klama"), Some("klama"));
+		assert_eq!(word("pa'i"), Some("prami"));
+		assert_eq!(word("sel"), Some("se"));
+		assert_eq!(word("gau"), None);
+	}
+
+	#[test]
+	fn plain() {
+		let glossed = gloss("coi .djan.", Dictionary::sample()).unwrap();
+		assert_eq!(
+			glossed.to_string(),
+			"coi        djan\nCOI        cmevla\ngreetings  djan"
+		);
+	}
+
+	#[test]
+	fn html_escapes() {
+		let glossed = gloss("zoi gy <b> & gy", Dictionary::sample()).unwrap();
+		assert!(glossed
+			.render(Format::Html)
+			.contains("<td class=\"quote\">&lt;b&gt; &amp;</td>"));
+	}
+
+	make_test!(parse_error, "klama ku", "klama/gismu/come ku/KU/");
+	make_test!(
+		parse_error_quotes,
+		"zo mi ku zoi gy hello gy lo'u mi klama le'u ku",
+		"zo/ZO/ mi/quote/\"mi\" ku/KU/ zoi/ZOI/ gy/(zoi delimiter)/ hello/quote/\"hello\" gy/(zoi delimiter)/ lo'u/LOhU/ mi klama/quote/\"mi klama\" le'u/LEhU/ ku/KU/"
+	);
+
+	#[test]
+	fn lex_error() {
+		assert!(gloss("zoi gy hello", Dictionary::sample()).is_err());
+	}
+}
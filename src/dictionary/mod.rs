@@ -0,0 +1,481 @@
+//! A dictionary of Lojban words, with English glosses and place structures.
+//!
+//! This module needs the `dictionary` feature.
+//! A [`Dictionary`] can be read from an XML export of [jbovlaste](https://jbovlaste.lojban.org/export/xml.html) with [`Dictionary::from_xml`],
+//! and [`Dictionary::sample`] gives a sample of about 80 common words that is built into the crate, for demos and tests.
+//! The full gismu list and the rest of jbovlaste are not bundled with the crate, so checks and glosses that should cover all of Lojban need a dictionary read from an export.
+//!
+//! Knowing how many places a brivla has, [`Dictionary::check_arity`] warns about bridi that fill more places than their selbri has.
+//! The [`interlinear`] module uses the glosses to gloss a text word by word.
+//!
+//! ```rust
+//! use sneturfahi::dictionary::Dictionary;
+//! use sneturfahi::parse::ast;
+//!
+//! let dictionary = Dictionary::sample();
+//! let klama = dictionary.get("klama").unwrap();
+//! assert_eq!(klama.places, 5);
+//! assert!(klama.glosses.iter().any(|gloss| gloss == "come"));
+//!
+//! let input = "mi prami do lo gerku";
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::parse_in(input, &arena).unwrap();
+//! let text = ast::lower(&cst, input);
+//! let warnings = dictionary.check_arity(&text.paragraphs[0].sentences[0]);
+//! assert_eq!(warnings[0].span.slice(input), Some("lo gerku"));
+//! assert_eq!(
+//! 	warnings[0].to_string(),
+//! 	"prami has 2 places, but the sumti at 12..20 fills x3"
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use crate::parse::ast::places::{self, Place};
+use crate::parse::ast::Sentence;
+use crate::span::Span;
+
+pub mod interlinear;
+mod xml;
+
+/// A dictionary of Lojban words.
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+	entries: HashMap<String, Entry>,
+	/// The normalized word of each rafsi.
+	rafsi: HashMap<String, String>,
+}
+
+impl Dictionary {
+	/// A demo sample of about 80 common gismu, cmavo and lujvo that is built into the crate, enough for examples and tests.
+	///
+	/// This is not a full dictionary, so most words are missing from it.
+	/// Load a jbovlaste export with [`from_xml`](Self::from_xml) to look up every word.
+	///
+	/// # Panics
+	/// Never; the built-in data is checked by the tests.
+	#[must_use]
+	pub fn sample() -> &'static Self {
+		static SAMPLE: std::sync::OnceLock<Dictionary> = std::sync::OnceLock::new();
+		SAMPLE.get_or_init(|| {
+			Self::from_xml(include_str!("sample.xml")).expect("the sample dictionary is valid")
+		})
+	}
+
+	/// Read a dictionary from an XML export of jbovlaste.
+	///
+	/// Only the `valsi` elements are read, so exports in any language work, though the glosses will be in that language.
+	/// If a word appears more than once, the first is kept.
+	///
+	/// # Errors
+	/// Returns an error if the XML is malformed or a `valsi` element has no `word` attribute.
+	pub fn from_xml(xml: &str) -> Result<Self, Error> {
+		let mut entries = HashMap::new();
+		let mut rafsi = HashMap::new();
+		let mut entry: Option<Entry> = None;
+		// the element whose text is being read into the entry
+		let mut field: Option<&str> = None;
+		let mut reader = xml::Reader::new(xml);
+		while let Some(event) = reader.next() {
+			match event? {
+				xml::Event::Start {
+					name: "valsi",
+					attributes,
+					empty,
+				} => {
+					let attribute = |key: &str| {
+						attributes
+							.iter()
+							.find(|(name, _)| *name == key)
+							.map(|(_, value)| value.as_str())
+					};
+					let word = attribute("word").ok_or(Error::MissingWord(reader.position()))?;
+					let kind = attribute("type").unwrap_or_default();
+					let new = Entry {
+						word: word.to_owned(),
+						kind: WordKind::from_type(kind),
+						experimental: kind.starts_with("experimental") || kind.starts_with("obsolete"),
+						selmaho: None,
+						definition: String::new(),
+						glosses: Vec::new(),
+						rafsi: Vec::new(),
+						places: 0,
+					};
+					if empty {
+						entries.entry(normalize(word)).or_insert(new);
+					} else {
+						entry = Some(new);
+					}
+				}
+				xml::Event::Start {
+					name: "glossword",
+					attributes,
+					..
+				} => {
+					if let (Some(entry), Some((_, gloss))) = (
+						&mut entry,
+						attributes.into_iter().find(|(name, _)| *name == "word"),
+					) {
+						entry.glosses.push(gloss);
+					}
+				}
+				xml::Event::Start {
+					name: name @ ("definition" | "selmaho" | "rafsi"),
+					empty: false,
+					..
+				} if entry.is_some() => field = Some(name),
+				xml::Event::Start { .. } => {}
+				xml::Event::Text(text) => {
+					if let (Some(entry), Some(field)) = (&mut entry, field) {
+						match field {
+							"definition" => entry.definition.push_str(&text),
+							"selmaho" => entry
+								.selmaho
+								.get_or_insert_with(String::new)
+								.push_str(&text),
+							_ => entry.rafsi.push(text.trim().to_owned()),
+						}
+					}
+				}
+				xml::Event::End("valsi") => {
+					if let Some(mut done) = entry.take() {
+						done.places = count_places(&done.definition);
+						let word = normalize(&done.word);
+						for form in &done.rafsi {
+							rafsi.entry(normalize(form)).or_insert_with(|| word.clone());
+						}
+						entries.entry(word).or_insert(done);
+					}
+				}
+				xml::Event::End(_) => field = None,
+			}
+		}
+		Ok(Self { entries, rafsi })
+	}
+
+	/// The entry for `word`, which is normalized as the parser normalizes words, so `.I` and `koha` find `.i` and `ko'a`.
+	#[must_use]
+	pub fn get(&self, word: &str) -> Option<&Entry> {
+		self.entries.get(&normalize(word))
+	}
+
+	/// The word that `rafsi` stands for in lujvo.
+	///
+	/// Short rafsi are looked up in the rafsi of each entry, and four- and five-letter rafsi are the gismu that they are cut from, so `kla`, `klam` and `klama` all find `klama`.
+	#[must_use]
+	pub fn rafsi(&self, rafsi: &str) -> Option<&Entry> {
+		let rafsi = normalize(rafsi);
+		let gismu = |word: &str| self.get(word).filter(|entry| entry.kind == WordKind::Gismu);
+		match rafsi.len() {
+			5 => gismu(&rafsi),
+			4 if !rafsi.contains('\'') => ['a', 'e', 'i', 'o', 'u']
+				.into_iter()
+				.find_map(|vowel| gismu(&format!("{rafsi}{vowel}"))),
+			_ => self
+				.rafsi
+				.get(&rafsi)
+				.and_then(|word| self.entries.get(word)),
+		}
+	}
+
+	/// The number of words in the dictionary.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether the dictionary has no words.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// The entries in the dictionary, in no particular order.
+	pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+		self.entries.values()
+	}
+
+	/// Find sumti in `sentence` that fill places beyond the last place of their selbri, using [`places::resolve`].
+	///
+	/// Selbri whose head is not a word in the dictionary, or whose definition has no places, are not checked.
+	#[must_use]
+	pub fn check_arity(&self, sentence: &Sentence) -> Vec<ArityWarning> {
+		let mut warnings = Vec::new();
+		for map in places::resolve(sentence) {
+			let Some(word) = &map.word else {
+				continue;
+			};
+			let Some(entry) = self.get(&word.text).filter(|entry| entry.places > 0) else {
+				continue;
+			};
+			for filler in &map.fillers {
+				if let Place::Numbered(place) = filler.place {
+					if place > entry.places {
+						warnings.push(ArityWarning {
+							word: entry.word.clone(),
+							places: entry.places,
+							place,
+							span: filler.span,
+						});
+					}
+				}
+			}
+		}
+		warnings
+	}
+}
+
+/// A word in a [`Dictionary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+	/// The word, as written in the dictionary.
+	pub word: String,
+	/// What kind of word it is.
+	pub kind: WordKind,
+	/// Whether the word is experimental or obsolete rather than official.
+	pub experimental: bool,
+	/// The selmaho of a cmavo.
+	pub selmaho: Option<String>,
+	/// The definition, with places written as `$x_{1}$` and so on.
+	pub definition: String,
+	/// Short translations of the word.
+	pub glosses: Vec<String>,
+	/// The rafsi of the word.
+	pub rafsi: Vec<String>,
+	/// The number of places in the definition, which is the highest `x` it mentions, or 0 if it has none.
+	pub places: usize,
+}
+
+/// The kinds of word in a [`Dictionary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WordKind {
+	/// A root brivla, such as `klama`.
+	Gismu,
+	/// A structure word or compound of them, such as `cu` or `.iseni'ibo`.
+	Cmavo,
+	/// A compound brivla, such as `brivla`, including those made with `zei`.
+	Lujvo,
+	/// A borrowed brivla.
+	Fuhivla,
+	/// A name.
+	Cmevla,
+	/// Other kinds, such as `bu` letterals.
+	Other,
+}
+
+impl WordKind {
+	fn from_type(kind: &str) -> Self {
+		if kind.contains("gismu") {
+			Self::Gismu
+		} else if kind.contains("cmavo") {
+			Self::Cmavo
+		} else if kind.contains("lujvo") {
+			Self::Lujvo
+		} else if kind.contains("fu'ivla") {
+			Self::Fuhivla
+		} else if kind.contains("cmevla") {
+			Self::Cmevla
+		} else {
+			Self::Other
+		}
+	}
+}
+
+/// A sumti that fills a place beyond the last place of its selbri, found by [`Dictionary::check_arity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArityWarning {
+	/// The word at the head of the selbri.
+	pub word: String,
+	/// The number of places the word has.
+	pub places: usize,
+	/// The place the sumti fills.
+	pub place: usize,
+	/// The span of the sumti.
+	pub span: Span,
+}
+
+impl std::fmt::Display for ArityWarning {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			formatter,
+			"{} has {} places, but the sumti at {:?} fills x{}",
+			self.word, self.places, self.span, self.place
+		)
+	}
+}
+
+/// An error in reading a dictionary.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+	/// The XML is malformed at the given byte offset.
+	#[error("malformed XML at byte {0}")]
+	Malformed(usize),
+	/// A `valsi` element ending before the given byte offset has no `word` attribute.
+	#[error("valsi without a word before byte {0}")]
+	MissingWord(usize),
+}
+
+/// Normalize a word for lookup: lowercase, without pauses, and with `h` for the apostrophe.
+fn normalize(word: &str) -> String {
+	word
+		.chars()
+		.filter(|&letter| letter != ',' && letter != '.')
+		.map(|letter| match letter.to_ascii_lowercase() {
+			'h' => '\'',
+			other => other,
+		})
+		.collect()
+}
+
+/// The highest place mentioned in a definition, written as `x1`, `x_1` or `x_{1}`.
+fn count_places(definition: &str) -> usize {
+	let mut places = 0;
+	let mut previous = None;
+	for (index, letter) in definition.char_indices() {
+		if letter == 'x' && !previous.is_some_and(char::is_alphanumeric) {
+			let digits = definition[index + 1..]
+				.trim_start_matches(['_', '{'])
+				.chars()
+				.take_while(char::is_ascii_digit)
+				.collect::<String>();
+			if let Ok(place) = digits.parse::<usize>() {
+				places = places.max(place);
+			}
+		}
+		previous = Some(letter);
+	}
+	places
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<dictionary>
+<direction from="lojban" to="English">
+<valsi word="klama" type="gismu">
+	<rafsi>kla</rafsi>
+	<definition>$x_{1}$ comes/goes to destination $x_{2}$ from origin $x_{3}$ via route $x_{4}$ using means/vehicle $x_{5}$.</definition>
+	<notes>See also &quot;litru&quot;.</notes>
+	<glossword word="come" sense="move"/>
+	<glossword word="go"/>
+</valsi>
+<valsi word=".i" type="cmavo">
+	<selmaho>I</selmaho>
+	<definition>sentence link.</definition>
+</valsi>
+<valsi word="xo'ei" type="experimental cmavo"><definition>x1 is odd, x_2 too</definition></valsi>
+<valsi word="klama" type="gismu"><definition>$x_{1}$ duplicates</definition></valsi>
+</direction>
+</dictionary>
+"#;
+
+	#[test]
+	fn from_xml() {
+		let dictionary = Dictionary::from_xml(XML).unwrap();
+		assert_eq!(dictionary.len(), 3);
+		let klama = dictionary.get("klama").unwrap();
+		assert_eq!(klama.kind, WordKind::Gismu);
+		assert!(!klama.experimental);
+		assert_eq!(klama.places, 5);
+		assert_eq!(klama.rafsi, ["kla"]);
+		assert_eq!(klama.glosses, ["come", "go"]);
+		assert!(klama.definition.starts_with("$x_{1}$ comes/goes"));
+		let i = dictionary.get("I").unwrap();
+		assert_eq!(i.kind, WordKind::Cmavo);
+		assert_eq!(i.selmaho.as_deref(), Some("I"));
+		assert_eq!(i.places, 0);
+		let xohei = dictionary.get("xohei").unwrap();
+		assert!(xohei.experimental);
+		assert_eq!(xohei.places, 2);
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			Dictionary::from_xml("<valsi word=\"klama>").unwrap_err(),
+			Error::Malformed(0)
+		);
+		assert_eq!(
+			Dictionary::from_xml("<a/><valsi type=\"gismu\">").unwrap_err(),
+			Error::MissingWord(24)
+		);
+	}
+
+	#[test]
+	fn places() {
+		assert_eq!(count_places("$x_{1}$ is blue"), 1);
+		assert_eq!(count_places("x1 gives x2 to x3"), 3);
+		assert_eq!(count_places("x_1 loves x_2"), 2);
+		assert_eq!(count_places("a box of x_{12}"), 12);
+		assert_eq!(count_places("vocative: hello"), 0);
+		assert_eq!(count_places("a tax3 on max2"), 0);
+	}
+
+	#[test]
+	fn sample() {
+		let dictionary = Dictionary::sample();
+		assert!(!dictionary.is_empty());
+		for entry in dictionary.entries() {
+			assert!(!entry.glosses.is_empty(), "{} has no glosses", entry.word);
+			if entry.kind == WordKind::Gismu {
+				assert!(entry.places > 0, "{} has no places", entry.word);
+			}
+		}
+		assert_eq!(dictionary.get("dunda").unwrap().places, 3);
+		assert_eq!(
+			dictionary.get("gi'e").unwrap().selmaho.as_deref(),
+			Some("GIhA")
+		);
+	}
+
+	fn check(input: &str) -> Vec<String> {
+		let arena = crate::Arena::new();
+		let cst = crate::parse_in(input, &arena).unwrap();
+		let text = crate::parse::ast::lower(&cst, input);
+		Dictionary::sample()
+			.check_arity(&text.paragraphs[0].sentences[0])
+			.iter()
+			.map(ToString::to_string)
+			.collect()
+	}
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:expr) => {
+			#[test]
+			fn $name() {
+				assert_eq!(check($input), $expected as [&str; _]);
+			}
+		};
+	}
+
+	make_test!(within, "mi klama le zarci", []);
+	make_test!(
+		extra,
+		"mi prami do mi",
+		["prami has 2 places, but the sumti at 12..14 fills x3"]
+	);
+	make_test!(
+		tagged,
+		"fu do mi citka",
+		[
+			"citka has 2 places, but the sumti at 3..5 fills x5",
+			"citka has 2 places, but the sumti at 6..8 fills x6"
+		]
+	);
+	make_test!(
+		bound,
+		"mi prami be do bei mi",
+		["prami has 2 places, but the sumti at 19..21 fills x3"]
+	);
+	make_test!(converted, "mi te dunda do", []);
+	make_test!(unknown, "mi broda do mi", []);
+	make_test!(
+		each_bridi,
+		"mi gleki do gi'e citka do mi",
+		["citka has 2 places, but the sumti at 26..28 fills x3"]
+	);
+}
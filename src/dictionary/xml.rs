@@ -0,0 +1,266 @@
+//! Just enough XML to read jbovlaste exports: elements, attributes, text, CDATA sections and the predefined and numeric entities.
+//! Comments, processing instructions and doctypes are skipped, and nothing is validated beyond what is needed to find the elements.
+
+use super::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Event<'input> {
+	/// A start tag, which is also the end tag if `empty` is true.
+	Start {
+		name: &'input str,
+		attributes: Vec<(&'input str, String)>,
+		empty: bool,
+	},
+	End(&'input str),
+	Text(String),
+}
+
+pub(super) struct Reader<'input> {
+	input: &'input str,
+	position: usize,
+}
+
+impl<'input> Reader<'input> {
+	pub(super) fn new(input: &'input str) -> Self {
+		Self { input, position: 0 }
+	}
+
+	/// The byte offset of the next event.
+	pub(super) fn position(&self) -> usize {
+		self.position
+	}
+
+	fn rest(&self) -> &'input str {
+		&self.input[self.position..]
+	}
+
+	/// Skip past the next `terminator`, or fail at the current position if there is none.
+	fn skip_past(&mut self, terminator: &str) -> Result<(), Error> {
+		let end = self
+			.rest()
+			.find(terminator)
+			.ok_or(Error::Malformed(self.position))?;
+		self.position += end + terminator.len();
+		Ok(())
+	}
+
+	/// The offset in [`rest`](Self::rest) of the `>` that ends the tag there, skipping any inside quoted attribute values.
+	fn tag_end(&self) -> Option<usize> {
+		let mut quote = None;
+		for (offset, letter) in self.rest().char_indices() {
+			match (quote, letter) {
+				(None, '>') => return Some(offset),
+				(None, '"' | '\'') => quote = Some(letter),
+				(Some(open), _) if open == letter => quote = None,
+				_ => {}
+			}
+		}
+		None
+	}
+
+	fn tag(&mut self) -> Result<Event<'input>, Error> {
+		let start = self.position;
+		let end = self.tag_end().ok_or(Error::Malformed(start))?;
+		let tag = &self.rest()[1..end];
+		self.position += end + 1;
+		if let Some(name) = tag.strip_prefix('/') {
+			return Ok(Event::End(name.trim()));
+		}
+		let (tag, empty) = match tag.strip_suffix('/') {
+			Some(tag) => (tag, true),
+			None => (tag, false),
+		};
+		let name_end = tag
+			.find(|letter: char| letter.is_ascii_whitespace())
+			.unwrap_or(tag.len());
+		let name = &tag[..name_end];
+		if name.is_empty() {
+			return Err(Error::Malformed(start));
+		}
+		let mut attributes = Vec::new();
+		let mut rest = tag[name_end..].trim_start();
+		while !rest.is_empty() {
+			let malformed = Error::Malformed(start);
+			let (key, value) = rest.split_once('=').ok_or(malformed.clone())?;
+			let value = value.trim_start();
+			let quote = value
+				.chars()
+				.next()
+				.filter(|&quote| quote == '"' || quote == '\'');
+			let quote = quote.ok_or(malformed.clone())?;
+			let (value, after) = value[1..].split_once(quote).ok_or(malformed)?;
+			attributes.push((key.trim(), unescape(value, start)?));
+			rest = after.trim_start();
+		}
+		Ok(Event::Start {
+			name,
+			attributes,
+			empty,
+		})
+	}
+}
+
+impl<'input> Iterator for Reader<'input> {
+	type Item = Result<Event<'input>, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let rest = self.rest();
+			if rest.is_empty() {
+				return None;
+			}
+			let skipped = if rest.starts_with("<!--") {
+				self.skip_past("-->")
+			} else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+				let start = self.position;
+				let Some(end) = cdata.find("]]>") else {
+					return Some(Err(Error::Malformed(start)));
+				};
+				self.position += "<![CDATA[".len() + end + "]]>".len();
+				return Some(Ok(Event::Text(cdata[..end].to_owned())));
+			} else if rest.starts_with("<?") {
+				self.skip_past("?>")
+			} else if rest.starts_with("<!") {
+				self.skip_past(">")
+			} else if rest.starts_with('<') {
+				return Some(self.tag());
+			} else {
+				let start = self.position;
+				let end = rest.find('<').unwrap_or(rest.len());
+				self.position += end;
+				return Some(unescape(&rest[..end], start).map(Event::Text));
+			};
+			if let Err(error) = skipped {
+				return Some(Err(error));
+			}
+		}
+	}
+}
+
+/// Replace the entities in `text`, which starts at byte `start` of the input.
+fn unescape(text: &str, start: usize) -> Result<String, Error> {
+	let mut unescaped = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(ampersand) = rest.find('&') {
+		unescaped.push_str(&rest[..ampersand]);
+		let malformed = Error::Malformed(start + (text.len() - rest.len()) + ampersand);
+		let (entity, after) = rest[ampersand + 1..]
+			.split_once(';')
+			.ok_or(malformed.clone())?;
+		let letter = match entity {
+			"lt" => '<',
+			"gt" => '>',
+			"amp" => '&',
+			"quot" => '"',
+			"apos" => '\'',
+			_ => {
+				let code = if let Some(hex) = entity.strip_prefix("#x") {
+					u32::from_str_radix(hex, 16).ok()
+				} else if let Some(decimal) = entity.strip_prefix('#') {
+					decimal.parse().ok()
+				} else {
+					None
+				};
+				code.and_then(char::from_u32).ok_or(malformed)?
+			}
+		};
+		unescaped.push(letter);
+		rest = after;
+	}
+	unescaped.push_str(rest);
+	Ok(unescaped)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn events() {
+		let input = r#"<?xml version="1.0"?><!-- comment --><a b="1 &amp; 2" c='x'>text &lt;&#65;&#x42;&gt;<d/></a>"#;
+		let events = Reader::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(
+			events,
+			[
+				Event::Start {
+					name: "a",
+					attributes: vec![("b", "1 & 2".to_owned()), ("c", "x".to_owned())],
+					empty: false,
+				},
+				Event::Text("text <AB>".to_owned()),
+				Event::Start {
+					name: "d",
+					attributes: Vec::new(),
+					empty: true,
+				},
+				Event::End("a"),
+			]
+		);
+	}
+
+	#[test]
+	fn quoted_greater_than() {
+		let input = r#"<a b="x > y" c='">'/><d e="it's"></d>"#;
+		let events = Reader::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(
+			events,
+			[
+				Event::Start {
+					name: "a",
+					attributes: vec![("b", "x > y".to_owned()), ("c", "\">".to_owned())],
+					empty: true,
+				},
+				Event::Start {
+					name: "d",
+					attributes: vec![("e", "it's".to_owned())],
+					empty: false,
+				},
+				Event::End("d"),
+			]
+		);
+	}
+
+	#[test]
+	fn cdata() {
+		let input = "<a>x <![CDATA[<b> &amp; ]]]]> y</a>";
+		let events = Reader::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(
+			events,
+			[
+				Event::Start {
+					name: "a",
+					attributes: Vec::new(),
+					empty: false,
+				},
+				Event::Text("x ".to_owned()),
+				Event::Text("<b> &amp; ]]".to_owned()),
+				Event::Text(" y".to_owned()),
+				Event::End("a"),
+			]
+		);
+	}
+
+	#[test]
+	fn malformed() {
+		let errors = [
+			"<a",
+			"<a b>",
+			"<a b=\"1>",
+			"a &bogus; b",
+			"<!-- a",
+			"<![CDATA[ a",
+		]
+		.map(|input| Reader::new(input).find_map(Result::err));
+		assert_eq!(
+			errors,
+			[
+				Some(Error::Malformed(0)),
+				Some(Error::Malformed(0)),
+				Some(Error::Malformed(0)),
+				Some(Error::Malformed(2)),
+				Some(Error::Malformed(0)),
+				Some(Error::Malformed(0)),
+			]
+		);
+	}
+}
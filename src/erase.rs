@@ -0,0 +1,118 @@
+//! Erasure of words with the cmavo `si`, `sa` and `su`, an optional step between [lexing](crate::lex()) and [parsing](crate::parse()).
+//!
+//! This module centers around the [erase] function.
+
+use crate::lex::{Selmaho, Token};
+
+/// What the tokens after a kept token are quoted by, if anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quoting {
+	None,
+	/// The next token is quoted by `zo`.
+	Word,
+	/// The tokens up to the next `le'u` are quoted by `lo'u`.
+	Lohu,
+}
+
+/// Remove the words erased by `si`, `sa` and `su` from `tokens`, along with those cmavo themselves.
+///
+/// - `si` erases the word before it, so `si si` erases two words.
+///   A `zo` quote is erased as one word, since a `si` after a lone `zo` would be quoted by it.
+/// - `sa` erases back to and including the last word with the same selmaho as the word after it.
+///   If there is no such word, it erases everything before it.
+/// - `su` erases everything before it.
+///
+/// The cmavo do not erase anything when they are quoted by `zo` or inside `lo'u` … `le'u`, or when they are followed by `bu`.
+/// The tokens of `zoi` and similar quotes are words like any other, so erasing a whole `zoi` quote takes one `si` for each of its tokens.
+///
+/// ```rust
+/// let input = "mi klama si cadzu lo zarci sa lo zdani";
+/// let tokens = sneturfahi::lex(input)
+/// 	.collect::<Result<Vec<_>, _>>()
+/// 	.unwrap();
+/// let words = sneturfahi::erase(&tokens)
+/// 	.into_iter()
+/// 	.map(|token| token.span.slice(input).unwrap())
+/// 	.collect::<Vec<_>>();
+/// assert_eq!(words, ["mi", "cadzu", "lo", "zdani"]);
+/// ```
+#[must_use]
+pub fn erase(tokens: &[Token]) -> Vec<Token> {
+	// each kept token along with how the tokens after it are quoted, so that erasing it also restores the quoting before it
+	let mut kept: Vec<(Token, Quoting)> = Vec::with_capacity(tokens.len());
+	let mut tokens = tokens.iter().copied().peekable();
+	while let Some(token) = tokens.next() {
+		let quoting = kept.last().map_or(Quoting::None, |&(_, quoting)| quoting);
+		let next = tokens.peek().map(|next| next.selmaho);
+		if quoting == Quoting::None && next != Some(Selmaho::Bu) {
+			match token.selmaho {
+				Selmaho::Si => {
+					kept.pop();
+					if matches!(kept.last(), Some((_, Quoting::Word))) {
+						kept.pop();
+					}
+					continue;
+				}
+				Selmaho::Sa => {
+					if let Some(selmaho) = next {
+						while let Some((erased, _)) = kept.pop() {
+							if erased.selmaho == selmaho {
+								break;
+							}
+						}
+					}
+					continue;
+				}
+				Selmaho::Su => {
+					kept.clear();
+					continue;
+				}
+				_ => {}
+			}
+		}
+		let quoting = match (quoting, token.selmaho) {
+			(Quoting::None, Selmaho::Zo) => Quoting::Word,
+			(Quoting::None, Selmaho::Lohu) => Quoting::Lohu,
+			(Quoting::Word, _) | (Quoting::Lohu, Selmaho::Lehu) => Quoting::None,
+			(quoting, _) => quoting,
+		};
+		kept.push((token, quoting));
+	}
+	kept.into_iter().map(|(token, _)| token).collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:literal) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let tokens = crate::lex(input).collect::<Result<Vec<_>, _>>().unwrap();
+				let words = erase(&tokens)
+					.into_iter()
+					.map(|token| token.span.slice(input).unwrap())
+					.collect::<Vec<_>>();
+				assert_eq!(words.join(" "), $expected);
+			}
+		};
+	}
+
+	make_test!(nothing, "mi klama lo zarci", "mi klama lo zarci");
+	make_test!(si, "mi klama si cadzu", "mi cadzu");
+	make_test!(si_si, "mi klama si si do cadzu", "do cadzu");
+	make_test!(si_at_start, "si mi klama", "mi klama");
+	make_test!(sa, "mi klama lo zarci sa lo zdani", "mi klama lo zdani");
+	make_test!(sa_not_found, "mi klama sa lo zdani", "lo zdani");
+	make_test!(sa_at_end, "mi klama sa", "mi klama");
+	make_test!(su, "mi klama su do cadzu", "do cadzu");
+	make_test!(zo, "zo si cmavo", "zo si cmavo");
+	make_test!(zo_erased, "zo si si broda", "broda");
+	make_test!(zo_zo, "zo zo si mi", "mi");
+	make_test!(zo_after_erasure, "mi zo klama si si do", "do");
+	make_test!(lohu, "lo'u mi si su le'u si do", "lo'u mi si su do");
+	make_test!(bu, "si bu sa bu", "si bu sa bu");
+	make_test!(zoi, "zoi gy si gy si si si si mi", "mi");
+}
@@ -0,0 +1,74 @@
+//! [`Error`], the errors that can occur anywhere in the pipeline of [`parse`](crate::parse()) and [`parse_in`](crate::parse_in).
+
+use crate::lex::Selmaho;
+use crate::parse::cst::error::WithLocation;
+use crate::span::Span;
+
+/// Errors from lexing or parsing a text, located by spans in the text.
+///
+/// ```rust
+/// let error = sneturfahi::parse("mi klama ku'o").unwrap_err();
+/// assert!(matches!(error, sneturfahi::Error::Parse { .. }));
+/// assert_eq!(error.span().slice("mi klama ku'o"), Some("ku'o"));
+/// assert!(error.to_string().ends_with(" at 9..13, found KUhO"));
+///
+/// let error = sneturfahi::parse("zoi").unwrap_err();
+/// assert!(matches!(error, sneturfahi::Error::Lex(_)));
+/// assert_eq!(error.span().slice("zoi"), Some("zoi"));
+/// ```
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+	/// The text could not be lexed.
+	#[error(transparent)]
+	Lex(#[from] crate::lex::Error),
+	/// The tokens could not be parsed.
+	#[error("{error} at {span:?}, found {}", found(*.selmaho))]
+	Parse {
+		/// The error from the parser.
+		error: crate::parse::cst::Error,
+		/// The span of the token where the error occurred, or an empty span at the end of the text if it occurred there.
+		span: Span,
+		/// The selmaho of the token where the error occurred, or `None` if it occurred at the end of the text.
+		selmaho: Option<Selmaho>,
+	},
+}
+
+/// Describe the token where a parsing error occurred, by its selmaho.
+fn found(selmaho: Option<Selmaho>) -> String {
+	selmaho.map_or_else(
+		|| "the end of the text".to_owned(),
+		|selmaho| selmaho.to_string(),
+	)
+}
+
+impl Error {
+	/// Convert an error from [`Cst::parse`](crate::Cst::parse) on the tokens of `input` to an error located in `input`.
+	#[must_use]
+	pub fn from_parse(error: WithLocation<'_>, input: &str) -> Self {
+		let token = error.location.first();
+		Self::Parse {
+			error: error.error,
+			span: token.map_or_else(
+				|| Span::at(Span::entire_slice(input).end, 0),
+				|token| token.span,
+			),
+			selmaho: token.map(|token| token.selmaho),
+		}
+	}
+
+	/// The span of the text where the error occurred.
+	///
+	/// For lexing errors, this is the word that started the quote that could not be lexed.
+	#[must_use]
+	pub fn span(&self) -> Span {
+		match self {
+			Self::Lex(
+				crate::lex::Error::DelimitedQuoteMissingSeparator { initiator_span }
+				| crate::lex::Error::DelimitedQuoteUnclosed { initiator_span, .. }
+				| crate::lex::Error::PauseDelimitedQuoteEof { initiator_span },
+			) => *initiator_span,
+			Self::Parse { span, .. } => *span,
+		}
+	}
+}
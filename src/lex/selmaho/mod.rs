@@ -10,7 +10,8 @@ mod display;
 /// Most of the variants conform to the strict meaning of selmaho, which is the grammatical type of a cmavo.
 /// A few others represent other word types in Lojban: `Cmevla`, `Gismu`, `Fuhivla`, and `Lujvo`.
 /// Finally, there are some "technical" selmaho: `AnyText`, `UnknownCmavo`, and `ZoiDelimiter`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Selmaho {
 	// regular cmavo
@@ -280,6 +281,46 @@ pub enum Selmaho {
 }
 
 impl Selmaho {
+	/// If this selmaho is an elidable terminator, such as KU or VAU, the cmavo that is used when the terminator is written out.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use sneturfahi::lex::selmaho::Selmaho;
+	/// assert_eq!(Selmaho::Ku.elidable_terminator(), Some("ku"));
+	/// assert_eq!(Selmaho::Beho.elidable_terminator(), Some("be'o"));
+	/// // le'u can never be elided
+	/// assert_eq!(Selmaho::Lehu.elidable_terminator(), None);
+	/// assert_eq!(Selmaho::Gismu.elidable_terminator(), None);
+	/// ```
+	#[must_use]
+	pub fn elidable_terminator(self) -> Option<&'static str> {
+		Some(match self {
+			Self::Beho => "be'o",
+			Self::Boi => "boi",
+			Self::Dohu => "do'u",
+			Self::Fehu => "fe'u",
+			Self::Gehu => "ge'u",
+			Self::Kei => "kei",
+			Self::Kehe => "ke'e",
+			Self::Ku => "ku",
+			Self::Kuhe => "ku'e",
+			Self::Kuho => "ku'o",
+			Self::Lihu => "li'u",
+			Self::Loho => "lo'o",
+			Self::Luhu => "lu'u",
+			Self::Mehu => "me'u",
+			Self::Nuhu => "nu'u",
+			Self::Sehu => "se'u",
+			Self::Tehu => "te'u",
+			Self::Toi => "toi",
+			Self::Tuhu => "tu'u",
+			Self::Vau => "vau",
+			Self::Veho => "ve'o",
+			_ => return None,
+		})
+	}
+
 	/// If the selmaho itself is experimental, meaning that all the cmavo in it are experimental.
 	/// False for all non-cmavo and technical cmavo, except `UnknownCmavo`.
 	///
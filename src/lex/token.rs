@@ -12,6 +12,7 @@ use crate::span::Span;
 ///
 /// [lex]: crate::lex::lex
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
 	/// For cmavo, if that cmavo is experimental.
 	pub experimental: bool,
@@ -27,17 +27,27 @@
 	clippy::redundant_else, // sometimes it's clearer
 )]
 #![forbid(unsafe_code)]
+#![recursion_limit = "256"] // instantiating the parsers of deeply nested rules through arena references
 
 mod rules;
 
 pub mod decompose;
 pub use decompose::decompose;
 
+pub mod erase;
+pub use erase::erase;
+
+#[cfg(feature = "dictionary")]
+pub mod dictionary;
+
+pub mod error;
+pub use error::Error;
+
 pub mod lex;
 pub use lex::lex;
 
 pub mod parse;
-pub use parse::{Arena, Cst};
+pub use parse::{parse, parse_in, parse_in_with, parse_with, Arena, Cst, ParsedText};
 
 pub mod span;
 pub use span::Span;
@@ -1,12 +1,227 @@
+//! [`Arena`], the memory that a [`Cst`](crate::Cst) is allocated in.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// The memory that the nodes of a [`Cst`](crate::Cst) are allocated in, which is freed all at once.
+///
+/// An arena can be reused for many parses with [`reset`](Self::reset), which avoids allocating new memory for each one.
+/// An arena created with [`with_stats`](Self::with_stats) also records how many nodes of each rule were parsed, and how much of its memory goes to each type:
+///
+/// ```rust
+/// let tokens: Vec<_> = sneturfahi::lex("mi klama lo zdani")
+/// 	.collect::<Result<_, _>>()
+/// 	.unwrap();
+/// let mut arena = sneturfahi::Arena::with_stats();
+/// for _ in 0..3 {
+/// 	arena.reset();
+/// 	sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+/// }
+/// let stats = arena.stats().unwrap();
+/// let sentence = stats.iter().find(|stats| stats.name == "Sentence").unwrap();
+/// assert_eq!(sentence.parsed, 3);
+/// assert!(sentence.parsed_bytes > 0);
+/// assert_eq!(sentence.nodes, 3);
+/// assert!(sentence.bytes > 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct Arena {
+	bump: bumpalo::Bump,
+	stats: Option<RefCell<Stats>>,
+}
+
+/// What an [`Arena`] created with [`Arena::with_stats`] has recorded.
 #[derive(Debug, Default)]
-pub struct Arena(pub(in crate::parse) bumpalo::Bump);
+struct Stats {
+	/// The number of nodes and bytes parsed, keyed by the name of the rule.
+	parsed: HashMap<&'static str, (usize, usize)>,
+	/// The number of nodes and bytes allocated, keyed by the full name of the type.
+	allocated: HashMap<&'static str, (usize, usize)>,
+}
+
+/// The nodes parsed and the memory allocated for one rule or type in an [`Arena`], as returned by [`Arena::stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleStats {
+	/// The name of the rule, such as `Sumti`, or of another allocated type, such as `WithFree<Koha>`.
+	pub name: String,
+	/// The number of times this rule was parsed successfully.
+	pub parsed: usize,
+	/// The size of the nodes this rule parsed successfully, whether or not they were allocated in the arena.
+	pub parsed_bytes: usize,
+	/// The number of nodes of this type that were allocated.
+	pub nodes: usize,
+	/// The number of bytes allocated for nodes of this type.
+	pub bytes: usize,
+}
 
 impl Arena {
+	/// Create an empty arena.
+	#[must_use]
 	pub fn new() -> Self {
 		Self::default()
 	}
 
+	/// Create an empty arena that records the nodes parsed for each rule and the memory allocated for each type, to be read with [`stats`](Self::stats).
+	///
+	/// Recording makes parsing slower, so this is meant for investigating memory use rather than for normal parsing.
+	#[must_use]
+	pub fn with_stats() -> Self {
+		Self {
+			bump: bumpalo::Bump::new(),
+			stats: Some(RefCell::default()),
+		}
+	}
+
+	/// The total number of bytes allocated by the arena, including memory that is not yet used by any node.
+	#[must_use]
 	pub fn allocated_bytes(&self) -> usize {
-		self.0.allocated_bytes()
+		self.bump.allocated_bytes()
+	}
+
+	/// Free every node in the arena so that its memory can be reused for another parse.
+	///
+	/// The arena keeps its largest chunk of memory, so parsing texts of similar sizes after resetting does not allocate.
+	/// Statistics are not reset, so they cover every parse since the arena was created.
+	pub fn reset(&mut self) {
+		self.bump.reset();
+	}
+
+	/// The nodes parsed for each rule and the memory allocated for each type since the arena was created, from most bytes parsed to fewest, or `None` if the arena was not created with [`with_stats`](Self::with_stats).
+	///
+	/// Every successful parse of a rule is counted, including those of alternatives that did not end up in the tree, at the size of the rule's type.
+	/// Generic rules are counted together under the name of the rule, such as `WithFree`.
+	///
+	/// Allocations are counted separately, by the type that was allocated.
+	/// Nodes stored directly inside other nodes are counted as part of the node that contains them, not as their own type, so a rule can be parsed many times without being allocated at all.
+	#[must_use]
+	pub fn stats(&self) -> Option<Vec<RuleStats>> {
+		let stats = self.stats.as_ref()?.borrow();
+		let parsed = stats
+			.parsed
+			.iter()
+			.map(|(&rule, &(nodes, bytes))| (rule.to_owned(), [nodes, bytes, 0, 0]));
+		let allocated = stats
+			.allocated
+			.iter()
+			.map(|(&type_name, &(nodes, bytes))| (short_type_name(type_name), [0, 0, nodes, bytes]));
+		let mut merged = HashMap::<String, RuleStats>::new();
+		for (name, [parsed, parsed_bytes, nodes, bytes]) in parsed.chain(allocated) {
+			let merged = merged.entry(name.clone()).or_insert_with(|| RuleStats {
+				name,
+				parsed: 0,
+				parsed_bytes: 0,
+				nodes: 0,
+				bytes: 0,
+			});
+			merged.parsed += parsed;
+			merged.parsed_bytes += parsed_bytes;
+			merged.nodes += nodes;
+			merged.bytes += bytes;
+		}
+		let mut merged: Vec<_> = merged.into_values().collect();
+		merged.sort_by(|a, b| {
+			b.parsed_bytes
+				.cmp(&a.parsed_bytes)
+				.then_with(|| b.bytes.cmp(&a.bytes))
+				.then_with(|| a.name.cmp(&b.name))
+		});
+		Some(merged)
+	}
+
+	/// Record that the rule named `rule` was parsed successfully into a node of `bytes` bytes.
+	pub(in crate::parse) fn record_parsed(&self, rule: &'static str, bytes: usize) {
+		if let Some(stats) = &self.stats {
+			let mut stats = stats.borrow_mut();
+			let stats = stats.parsed.entry(rule).or_default();
+			stats.0 += 1;
+			stats.1 += bytes;
+		}
 	}
+
+	fn record<T>(&self, nodes: usize) {
+		if let Some(stats) = &self.stats {
+			let mut stats = stats.borrow_mut();
+			let stats = stats
+				.allocated
+				.entry(std::any::type_name::<T>())
+				.or_default();
+			stats.0 += nodes;
+			stats.1 += nodes * size_of::<T>();
+		}
+	}
+
+	pub(in crate::parse) fn alloc<T>(&self, value: T) -> &mut T {
+		self.record::<T>(1);
+		self.bump.alloc(value)
+	}
+
+	pub(in crate::parse) fn alloc_slice_fill_iter<T, I>(&self, iter: I) -> &mut [T]
+	where
+		I: IntoIterator<Item = T>,
+		I::IntoIter: ExactSizeIterator,
+	{
+		let slice = self.bump.alloc_slice_fill_iter(iter);
+		self.record::<T>(slice.len());
+		slice
+	}
+
+	pub(in crate::parse) fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &mut [T] {
+		self.record::<T>(slice.len());
+		self.bump.alloc_slice_copy(slice)
+	}
+}
+
+/// Remove the module paths and elided lifetimes from a type name, turning `a::B<'_, c::D>` into `B<D>`.
+fn short_type_name(name: &str) -> String {
+	let mut short = String::with_capacity(name.len());
+	let mut segment_start = 0;
+	let mut rest = name;
+	while let Some(ch) = rest.chars().next() {
+		if let Some(after) = rest.strip_prefix("::") {
+			short.truncate(segment_start);
+			rest = after;
+			continue;
+		}
+		short.push(ch);
+		if !(ch.is_alphanumeric() || ch == '_') {
+			segment_start = short.len();
+		}
+		rest = &rest[ch.len_utf8()..];
+	}
+	short
+		.replace("<'_>", "")
+		.replace("'_, ", "")
+		.replace("&'_ ", "&")
+}
+
+#[cfg(test)]
+mod test {
+	use super::short_type_name;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:literal) => {
+			#[test]
+			fn $name() {
+				assert_eq!(short_type_name($input), $expected);
+			}
+		};
+	}
+
+	make_test!(plain, "Sumti", "Sumti");
+	make_test!(path, "sneturfahi::parse::cst::rules::Sumti", "Sumti");
+	make_test!(
+		generic,
+		"sneturfahi::parse::cst::rules::WithFree<sneturfahi::parse::cst::rules::selmaho::Koha>",
+		"WithFree<Koha>"
+	);
+	make_test!(
+		lifetimes,
+		"a::WithFree<'_, a::Sumti<'_>>",
+		"WithFree<Sumti>"
+	);
+	make_test!(
+		tuple,
+		"(core::option::Option<a::B>, &c::D)",
+		"(Option<B>, &D)"
+	);
 }
@@ -0,0 +1,520 @@
+//! Letters spelled by lerfu strings.
+//!
+//! [`Numeral`]s keep their `BY` words, `bu` letters, `LAU` prefixes and `tei … foi` composites as words; this module reads them as a string of Unicode letters.
+//! Letters are in the Latin alphabet and lower case until a shift word changes that:
+//! `lo'a`, `ge'o`, `je'o`, `jo'o` and `ru'o` shift to the Latin, Greek, Hebrew, Arabic and Cyrillic alphabets, `ga'e` and `to'a` shift to upper and lower case, and `na'a` cancels all shifts.
+//! Letters of other alphabets are chosen by sound, so `ge'o by` is `β` and `ru'o cy` is `ш`.
+//! `tau` changes the case of the one letter after it, `zai` applies an alphabet shift to the one letter after it, and `se'e` followed by digits is the character with that code.
+//!
+//! Lerfu strings used as sumti are pro-sumti, and each [`Letter`] records whether it is a [variable](Letter::variable) or a digit or other symbol.
+//!
+//! ```rust
+//! use sneturfahi::parse::ast::{self, lerfu, BridiTail, Statement, SumtiKind, Term};
+//!
+//! let input = "mi viska by ga'e cy vo";
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::parse_in(input, &arena).unwrap();
+//! let text = ast::lower(&cst, input);
+//!
+//! let Statement::Bridi(bridi) = &text.paragraphs[0].sentences[0].statement else {
+//! 	unreachable!();
+//! };
+//! let BridiTail::Selbri { terms, .. } = &bridi.tail else {
+//! 	unreachable!();
+//! };
+//! let Term::Sumti { sumti, .. } = &terms[0] else {
+//! 	unreachable!();
+//! };
+//! let SumtiKind::Lerfu(string) = &sumti.kind else {
+//! 	unreachable!();
+//! };
+//! let letters = lerfu::interpret(string).unwrap();
+//! assert_eq!(letters.to_string(), "bC4");
+//! assert_eq!(letters.variables().count(), 2);
+//! ```
+
+use super::number::digit;
+use super::{Numeral, NumeralWord, Word};
+use crate::span::{Location, Span};
+
+/// Interpret a [`Numeral`], such as a lerfu string or the operand `xy` in mekso, as letters.
+///
+/// # Errors
+/// Returns an error if the string contains a letter with no equivalent in the alphabet it is in, a `LAU` word other than `tau` and `zai`, or an invalid character code.
+pub fn interpret(numeral: &Numeral) -> Result<Letters, Error> {
+	let mut interpreter = Interpreter::new();
+	interpreter.words(&numeral.words)?;
+	interpreter.finish()
+}
+
+/// The letters spelled by a lerfu string, which display as one string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Letters {
+	/// The letters, in order.
+	pub letters: Vec<Letter>,
+}
+
+impl Letters {
+	/// The letters that are variables, leaving out digits and symbols.
+	pub fn variables(&self) -> impl Iterator<Item = &Letter> {
+		self.letters.iter().filter(|letter| letter.variable)
+	}
+}
+
+impl std::fmt::Display for Letters {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for letter in &self.letters {
+			formatter.write_str(&letter.text)?;
+		}
+		Ok(())
+	}
+}
+
+/// A letter, digit or symbol in a lerfu string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Letter {
+	/// The letter as Unicode text, usually one character.
+	/// Composites with `tei … foi` that have no single character are the characters of their parts.
+	pub text: String,
+	/// Whether the letter is alphabetic, and so can stand for a sumti, rather than a digit or a symbol such as `'`.
+	pub variable: bool,
+	/// The span of the words that spell the letter, including `tau`, `zai` and `tei … foi`.
+	pub span: Span,
+}
+
+/// An error in interpreting a lerfu string.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+	/// A letter that has no equivalent in the alphabet it is in, such as `ge'o cy`, or a word with `bu` that is not a letter.
+	#[error("the lerfu at {0:?} is not a letter of its alphabet")]
+	Unsupported(Span),
+	/// A `LAU` word other than `tau`, or `zai` followed by something other than an alphabet shift.
+	#[error("the LAU cmavo at {0:?} cannot be interpreted")]
+	Lau(Span),
+	/// `se'e` without a valid character code after it.
+	#[error("invalid character code at {0:?}")]
+	CharacterCode(Span),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Alphabet {
+	Latin,
+	Greek,
+	Hebrew,
+	Arabic,
+	Cyrillic,
+}
+
+impl Alphabet {
+	fn from_word(word: &str) -> Option<Self> {
+		Some(match word {
+			"lo'a" => Self::Latin,
+			"ge'o" => Self::Greek,
+			"je'o" => Self::Hebrew,
+			"jo'o" => Self::Arabic,
+			"ru'o" => Self::Cyrillic,
+			_ => return None,
+		})
+	}
+
+	/// The letter for `key`, which is the Lojban letter that spells it followed by a `+` for each `bu` that changes it, as in `t+` for `ty bu`.
+	/// Composites are the keys of their parts joined together.
+	fn letter(self, key: &str) -> Option<&'static str> {
+		Some(match (self, key) {
+			(Self::Latin, "'") => "'",
+			(Self::Latin, "'+") => "h",
+			(Self::Latin, "k+") => "q",
+			(Self::Latin, "v+") => "w",
+			(Self::Latin, _) => {
+				const LETTERS: &str = "abcdefgijklmnoprstuvxyz";
+				let index = LETTERS.find(key).filter(|_| key.len() == 1)?;
+				&LETTERS[index..=index]
+			}
+			(Self::Greek, "a") => "α",
+			(Self::Greek, "b") => "β",
+			(Self::Greek, "g") => "γ",
+			(Self::Greek, "d") => "δ",
+			(Self::Greek, "e") => "ε",
+			(Self::Greek, "z") => "ζ",
+			(Self::Greek, "e+") => "η",
+			(Self::Greek, "t+") => "θ",
+			(Self::Greek, "i") => "ι",
+			(Self::Greek, "k") => "κ",
+			(Self::Greek, "l") => "λ",
+			(Self::Greek, "m") => "μ",
+			(Self::Greek, "n") => "ν",
+			(Self::Greek, "ks") => "ξ",
+			(Self::Greek, "o") => "ο",
+			(Self::Greek, "p") => "π",
+			(Self::Greek, "r") => "ρ",
+			(Self::Greek, "s") => "σ",
+			(Self::Greek, "t") => "τ",
+			(Self::Greek, "u") => "υ",
+			(Self::Greek, "p+") => "φ",
+			(Self::Greek, "k+") => "χ",
+			(Self::Greek, "ps") => "ψ",
+			(Self::Greek, "o+") => "ω",
+			(Self::Hebrew, "a") => "א",
+			(Self::Hebrew, "b") => "ב",
+			(Self::Hebrew, "g") => "ג",
+			(Self::Hebrew, "d") => "ד",
+			(Self::Hebrew, "v") => "ו",
+			(Self::Hebrew, "z") => "ז",
+			(Self::Hebrew, "x") => "ח",
+			(Self::Hebrew, "t") => "ט",
+			(Self::Hebrew, "i") => "י",
+			(Self::Hebrew, "k") => "כ",
+			(Self::Hebrew, "l") => "ל",
+			(Self::Hebrew, "m") => "מ",
+			(Self::Hebrew, "n") => "נ",
+			(Self::Hebrew, "s") => "ס",
+			(Self::Hebrew, "p") => "פ",
+			(Self::Hebrew, "ts") => "צ",
+			(Self::Hebrew, "r") => "ר",
+			(Self::Hebrew, "c") => "ש",
+			(Self::Arabic, "'") => "ء",
+			(Self::Arabic, "a") => "ا",
+			(Self::Arabic, "b") => "ب",
+			(Self::Arabic, "t") => "ت",
+			(Self::Arabic, "j") => "ج",
+			(Self::Arabic, "x") => "خ",
+			(Self::Arabic, "d") => "د",
+			(Self::Arabic, "r") => "ر",
+			(Self::Arabic, "z") => "ز",
+			(Self::Arabic, "s") => "س",
+			(Self::Arabic, "c") => "ش",
+			(Self::Arabic, "f") => "ف",
+			(Self::Arabic, "k") => "ك",
+			(Self::Arabic, "l") => "ل",
+			(Self::Arabic, "m") => "م",
+			(Self::Arabic, "n") => "ن",
+			(Self::Arabic, "u") => "و",
+			(Self::Arabic, "i") => "ي",
+			(Self::Cyrillic, "a") => "а",
+			(Self::Cyrillic, "b") => "б",
+			(Self::Cyrillic, "v") => "в",
+			(Self::Cyrillic, "g") => "г",
+			(Self::Cyrillic, "d") => "д",
+			(Self::Cyrillic, "e") => "е",
+			(Self::Cyrillic, "j") => "ж",
+			(Self::Cyrillic, "z") => "з",
+			(Self::Cyrillic, "i") => "и",
+			(Self::Cyrillic, "k") => "к",
+			(Self::Cyrillic, "l") => "л",
+			(Self::Cyrillic, "m") => "м",
+			(Self::Cyrillic, "n") => "н",
+			(Self::Cyrillic, "o") => "о",
+			(Self::Cyrillic, "p") => "п",
+			(Self::Cyrillic, "r") => "р",
+			(Self::Cyrillic, "s") => "с",
+			(Self::Cyrillic, "t") => "т",
+			(Self::Cyrillic, "u") => "у",
+			(Self::Cyrillic, "f") => "ф",
+			(Self::Cyrillic, "x") => "х",
+			(Self::Cyrillic, "ts") => "ц",
+			(Self::Cyrillic, "tc") => "ч",
+			(Self::Cyrillic, "c") => "ш",
+			(Self::Cyrillic, "y") => "ы",
+			_ => return None,
+		})
+	}
+}
+
+/// The key of a letter spelled by `word` followed by `bu` `bus` times, as for [`Alphabet::letter`].
+fn key(word: &str, bus: usize) -> Option<String> {
+	let (base, bus) = match word {
+		"a" | "e" | "i" | "o" | "u" | "y" if bus > 0 => (word.to_owned(), bus - 1),
+		"y'y" => ("'".to_owned(), bus),
+		_ => match word.as_bytes() {
+			[consonant, b'y'] if consonant.is_ascii_lowercase() && !b"aeiouy".contains(consonant) => {
+				(char::from(*consonant).to_string(), bus)
+			}
+			_ => return None,
+		},
+	};
+	Some(base + &"+".repeat(bus))
+}
+
+struct Interpreter {
+	alphabet: Alphabet,
+	upper: bool,
+	/// An alphabet shifted to with `zai` for the next letter only, and where the `zai` starts.
+	once: Option<(Alphabet, Location)>,
+	letters: Vec<Letter>,
+	/// The keys of the letters, for looking up composites, or `None` for digits, composites and character codes.
+	keys: Vec<Option<String>>,
+	/// The span of a `se'e` and the digits after it so far.
+	code: Option<(Span, Vec<u8>)>,
+}
+
+impl Interpreter {
+	fn new() -> Self {
+		Self {
+			alphabet: Alphabet::Latin,
+			upper: false,
+			once: None,
+			letters: Vec::new(),
+			keys: Vec::new(),
+			code: None,
+		}
+	}
+
+	fn finish(mut self) -> Result<Letters, Error> {
+		self.end_code()?;
+		Ok(Letters {
+			letters: self.letters,
+		})
+	}
+
+	fn push(&mut self, text: String, key: Option<String>, span: Span) {
+		let variable = !text.is_empty() && text.chars().all(char::is_alphabetic);
+		self.letters.push(Letter {
+			text,
+			variable,
+			span,
+		});
+		self.keys.push(key);
+	}
+
+	fn cased(&self, letter: &str, toggle: bool) -> String {
+		if self.upper == toggle {
+			letter.to_owned()
+		} else {
+			letter.to_uppercase()
+		}
+	}
+
+	fn end_code(&mut self) -> Result<(), Error> {
+		let Some((span, digits)) = self.code.take() else {
+			return Ok(());
+		};
+		let code = digits.iter().try_fold(None, |code: Option<u32>, &digit| {
+			(digit < 10)
+				.then(|| code.unwrap_or(0).checked_mul(10)?.checked_add(digit.into()))
+				.flatten()
+				.map(Some)
+		});
+		let letter = code
+			.flatten()
+			.and_then(char::from_u32)
+			.ok_or(Error::CharacterCode(span))?;
+		self.push(letter.to_string(), None, span);
+		Ok(())
+	}
+
+	fn words(&mut self, words: &[NumeralWord]) -> Result<(), Error> {
+		for word in words {
+			match word {
+				NumeralWord::Pa(pa) => self.digit(pa)?,
+				NumeralWord::Lerfu { lau, word, bu } => {
+					self.end_code()?;
+					self.lau(lau.as_ref(), word, bu)?;
+				}
+				NumeralWord::Composite { inner, .. } => {
+					self.end_code()?;
+					self.composite(inner, word.span())?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn digit(&mut self, word: &Word) -> Result<(), Error> {
+		let span = word.span;
+		let value = digit(&word.text).ok_or(Error::Unsupported(span))?;
+		if let Some((code_span, digits)) = &mut self.code {
+			code_span.end = span.end;
+			digits.push(value);
+			return Ok(());
+		}
+		let text = char::from_digit(value.into(), 16)
+			.expect("PA digits are hexadecimal")
+			.to_string();
+		self.push(text, None, span);
+		Ok(())
+	}
+
+	/// Interpret a lerfu with the `LAU` word before it, if any.
+	fn lau(&mut self, lau: Option<&Word>, word: &Word, bu: &[Word]) -> Result<(), Error> {
+		let Some(lau) = lau else {
+			return self.lerfu(word, bu, false, word.span.start);
+		};
+		match lau.text.as_str() {
+			"tau" => self.lerfu(word, bu, true, lau.span.start),
+			"zai" => {
+				let alphabet = if bu.is_empty() {
+					Alphabet::from_word(&word.text)
+				} else {
+					None
+				};
+				let alphabet = alphabet.ok_or(Error::Lau(lau.span))?;
+				self.once = Some((alphabet, lau.span.start));
+				Ok(())
+			}
+			_ => Err(Error::Lau(lau.span)),
+		}
+	}
+
+	/// Interpret a composite in `tei` and `foi` that spans `whole`.
+	fn composite(&mut self, inner: &Numeral, whole: Span) -> Result<(), Error> {
+		let (alphabet, start) = self.take_alphabet(whole.start);
+		let mut composite = Interpreter::new();
+		composite.alphabet = alphabet;
+		composite.upper = self.upper;
+		composite.words(&inner.words)?;
+		composite.end_code()?;
+		let whole = Span::new(start, whole.end);
+		let key = composite.keys.into_iter().collect::<Option<String>>();
+		let text = match key.and_then(|key| alphabet.letter(&key)) {
+			Some(letter) => self.cased(letter, false),
+			None => composite
+				.letters
+				.into_iter()
+				.map(|letter| letter.text)
+				.collect(),
+		};
+		self.push(text, None, whole);
+		Ok(())
+	}
+
+	/// The alphabet for the next letter, and where the letter starts if it was shifted to with `zai`.
+	fn take_alphabet(&mut self, start: Location) -> (Alphabet, Location) {
+		match self.once.take() {
+			Some((alphabet, zai)) => (alphabet, zai.min(start)),
+			None => (self.alphabet, start),
+		}
+	}
+
+	/// Interpret a lerfu that starts at `start`, including any `LAU` word, changing its case if `toggle` is true.
+	fn lerfu(
+		&mut self,
+		word: &Word,
+		bu: &[Word],
+		toggle: bool,
+		start: Location,
+	) -> Result<(), Error> {
+		let lerfu_span = Span::new(word.span.start, bu.last().unwrap_or(word).span.end);
+		if !bu.is_empty() {
+			return self.letter(key(&word.text, bu.len()), toggle, start, lerfu_span);
+		}
+		if let Some(alphabet) = Alphabet::from_word(&word.text) {
+			self.alphabet = alphabet;
+			return Ok(());
+		}
+		match word.text.as_str() {
+			"ga'e" => self.upper = true,
+			"to'a" => self.upper = false,
+			"na'a" => {
+				self.alphabet = Alphabet::Latin;
+				self.upper = false;
+				self.once = None;
+			}
+			"se'e" => self.code = Some((lerfu_span, Vec::new())),
+			_ => return self.letter(key(&word.text, 0), toggle, start, lerfu_span),
+		}
+		Ok(())
+	}
+
+	fn letter(
+		&mut self,
+		key: Option<String>,
+		toggle: bool,
+		start: Location,
+		lerfu_span: Span,
+	) -> Result<(), Error> {
+		let (alphabet, start) = self.take_alphabet(start);
+		let key = key.ok_or(Error::Unsupported(lerfu_span))?;
+		let letter = alphabet
+			.letter(&key)
+			.ok_or(Error::Unsupported(lerfu_span))?;
+		let text = self.cased(letter, toggle);
+		self.push(text, Some(key), Span::new(start, lerfu_span.end));
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::parse::ast::lower::Lowerer;
+	use crate::parse::cst::node::NodeRef;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:expr) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let arena = crate::Arena::new();
+				let cst = crate::parse_in(input, &arena).unwrap();
+				let found =
+					crate::parse::select::select(cst.root(), cst.tokens(), input, "LerfuString").unwrap();
+				let NodeRef::LerfuString(string) = found[0].node.downcast() else {
+					unreachable!();
+				};
+				let string = Lowerer::new(input, cst.tokens()).lerfu_string(string);
+				let expected: Result<&str, Error> = $expected;
+				assert_eq!(
+					interpret(&string).map(|letters| letters.to_string()),
+					expected.map(str::to_owned)
+				);
+			}
+		};
+	}
+
+	make_test!(consonants, "by cy dy", Ok("bcd"));
+	make_test!(vowels, ".abu .ybu", Ok("ay"));
+	make_test!(apostrophe, "y'y", Ok("'"));
+	make_test!(extra_latin, ".y'ybu kybu vybu", Ok("hqw"));
+	make_test!(digits, "xy re", Ok("x2"));
+	make_test!(upper_case, "by ga'e cy dy to'a fy", Ok("bCDf"));
+	make_test!(tau, "by tau cy dy", Ok("bCd"));
+	make_test!(tau_in_upper_case, "ga'e by tau cy", Ok("Bc"));
+	make_test!(greek, "ge'o .abu by gy", Ok("αβγ"));
+	make_test!(greek_bu, "ge'o tybu .obubu", Ok("θω"));
+	make_test!(greek_composite, "ge'o tei py sy foi", Ok("ψ"));
+	make_test!(greek_upper, "ge'o ga'e dy", Ok("Δ"));
+	make_test!(cyrillic, "ru'o cy tei ty cy foi", Ok("шч"));
+	make_test!(hebrew, "je'o .abu by", Ok("אב"));
+	make_test!(arabic, "jo'o by y'y", Ok("بء"));
+	make_test!(shift_back, "ge'o by lo'a by", Ok("βb"));
+	make_test!(zai, "by zai ge'o by by", Ok("bβb"));
+	make_test!(cancel, "ge'o ga'e by na'a by", Ok("Βb"));
+	make_test!(latin_composite, "tei ky sy foi", Ok("ks"));
+	make_test!(character_code, "se'e xa mu by", Ok("Ab"));
+	make_test!(
+		missing_character_code,
+		"se'e by",
+		Err(Error::CharacterCode(Span::new(0, 4)))
+	);
+	make_test!(
+		not_in_alphabet,
+		"ge'o cy",
+		Err(Error::Unsupported(Span::new(5, 7)))
+	);
+	make_test!(
+		unknown_bu,
+		"by denpa bu",
+		Err(Error::Unsupported(Span::new(3, 11)))
+	);
+	make_test!(lau, "lau by", Err(Error::Lau(Span::new(0, 3))));
+
+	#[test]
+	fn variables() {
+		let input = "by ga'e dy re y'y";
+		let arena = crate::Arena::new();
+		let cst = crate::parse_in(input, &arena).unwrap();
+		let found =
+			crate::parse::select::select(cst.root(), cst.tokens(), input, "LerfuString").unwrap();
+		let NodeRef::LerfuString(string) = found[0].node.downcast() else {
+			unreachable!();
+		};
+		let letters = interpret(&Lowerer::new(input, cst.tokens()).lerfu_string(string)).unwrap();
+		let variables = letters
+			.variables()
+			.map(|letter| (letter.text.as_str(), letter.span))
+			.collect::<Vec<_>>();
+		assert_eq!(variables, [("b", Span::new(0, 2)), ("D", Span::new(8, 10))]);
+		assert_eq!(letters.letters.len(), 4);
+	}
+}
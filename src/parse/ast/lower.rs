@@ -0,0 +1,1769 @@
+//! Lowering of the CST into the AST.
+
+use super::{
+	word_text, Abstractor, Addressee, Bridi, BridiTail, Connective, ConnectiveKind, Conversion,
+	Description, DescriptionInner, Fragment, Free, Indicator, Numeral, NumeralWord, Paragraph,
+	Quantifier, Quote, RelativeClause, Selbri, SelbriModifier, Sentence, Statement, Sumti, SumtiKind,
+	Tag, TagUnit, Tanru, TanruUnit, TanruUnitKind, Term, Text, TruthFunction, Word,
+};
+use crate::lex::{Selmaho, Token};
+use crate::parse::cst::node::NodeRef;
+use crate::parse::cst::rules::selmaho::{self, with_token_types};
+use crate::parse::cst::rules::{self as cst, connectives};
+use crate::parse::cst::visit::{Visit as _, Visitor};
+use crate::parse::tree_node::{TreeNode, TreeNodeChild};
+use crate::span::Span;
+
+/// Conversion of a token into a [`Word`] with its indicators.
+pub(super) trait ToWord {
+	fn word(&self, lowerer: &Lowerer<'_>) -> Word;
+}
+
+macro_rules! impl_to_word {
+	(#[raw] $name:ident) => {
+		impl ToWord for selmaho::$name {
+			fn word(&self, lowerer: &Lowerer<'_>) -> Word {
+				lowerer.word_at(Selmaho::$name, lowerer.token_span(self.token), Vec::new())
+			}
+		}
+	};
+	(#[no_indicators] $name:ident) => {
+		impl ToWord for selmaho::$name<'_> {
+			fn word(&self, lowerer: &Lowerer<'_>) -> Word {
+				lowerer.word_at(
+					Selmaho::$name,
+					lowerer.token_span(self.inner.token),
+					Vec::new(),
+				)
+			}
+		}
+	};
+	($name:ident) => {
+		impl ToWord for selmaho::$name<'_> {
+			fn word(&self, lowerer: &Lowerer<'_>) -> Word {
+				let indicators = self
+					.indicators
+					.map_or_else(Vec::new, |indicators| lowerer.indicators(indicators));
+				lowerer.word_at(
+					Selmaho::$name,
+					lowerer.token_span(self.inner.token),
+					indicators,
+				)
+			}
+		}
+	};
+}
+
+macro_rules! token_words {
+	($($(#[$attr:ident])? $name:ident),* $(,)?) => {
+		$(impl_to_word!($(#[$attr])? $name);)*
+
+		/// Get the [`Word`] of a node if it is a token.
+		fn token_word(node: NodeRef<'_>, lowerer: &Lowerer<'_>) -> Option<Word> {
+			match node {
+				$(NodeRef::$name(token) => Some(token.word(lowerer)),)*
+				_ => None,
+			}
+		}
+	};
+}
+
+with_token_types!(token_words);
+
+/// Collects the words of every token that it visits, such as those of a tense.
+struct Words<'lowerer, 'input> {
+	lowerer: &'lowerer Lowerer<'input>,
+	words: Vec<Word>,
+}
+
+impl<'ast> Visitor<'ast> for Words<'_, '_> {
+	fn visit_token<T: crate::parse::cst::visit::Visit<'ast> + TreeNode>(
+		&mut self,
+		_: Selmaho,
+		node: &'ast T,
+	) {
+		self.words.extend(token_word(node.downcast(), self.lowerer));
+	}
+}
+
+/// Take the `nai` that directly follows a word, which the CST parses as an indicator of the word.
+fn take_nai(word: &mut Word) -> Option<Word> {
+	if word
+		.indicators
+		.first()
+		.is_some_and(|indicator| indicator.word.selmaho == Selmaho::Nai)
+	{
+		Some(word.indicators.remove(0).word)
+	} else {
+		None
+	}
+}
+
+/// Get the truth function of a logical connective from the vowel at the end of its word.
+fn truth_function(word: &Word) -> TruthFunction {
+	let vowel = word.text.chars().rev().find(char::is_ascii_alphabetic);
+	match vowel {
+		Some('a') => TruthFunction::Or,
+		Some('e') => TruthFunction::And,
+		Some('o') => TruthFunction::Iff,
+		Some('u') => TruthFunction::Whether,
+		_ => TruthFunction::Question,
+	}
+}
+
+/// Join items with the connectives between them, grouping from right to left.
+pub(super) fn join_right<C, T>(
+	mut items: Vec<T>,
+	mut connectives: Vec<C>,
+	mut join: impl FnMut(C, T, T) -> T,
+) -> T {
+	let mut result = items.pop().expect("there is at least one item");
+	while let Some(connective) = connectives.pop() {
+		let left = items
+			.pop()
+			.expect("there is an item before each connective");
+		result = join(connective, left, result);
+	}
+	result
+}
+
+/// Lower the items of `separated` and join them with its separators, grouping from left to right.
+fn fold_left<'arena, Item, Separator, T, State>(
+	separated: &'arena cst::Separated<'arena, Item, Separator>,
+	state: &mut State,
+	mut item: impl FnMut(&mut State, &'arena Item) -> T,
+	mut join: impl FnMut(&mut State, &'arena Separator, T, T) -> T,
+) -> T {
+	let mut result = item(state, separated.first);
+	for (separator, next) in separated.rest.as_slice() {
+		let next = item(state, next);
+		result = join(state, separator, result, next);
+	}
+	result
+}
+
+/// Lower the items of `separated` and join them with its separators, grouping from right to left.
+fn fold_right<'arena, Item, Separator, T, State>(
+	separated: &'arena cst::Separated<'arena, Item, Separator>,
+	state: &mut State,
+	mut item: impl FnMut(&mut State, &'arena Item) -> T,
+	mut join: impl FnMut(&mut State, &'arena Separator, T, T) -> T,
+) -> T {
+	let mut items = vec![item(state, separated.first)];
+	let mut separators = Vec::new();
+	for (separator, next) in separated.rest.as_slice() {
+		separators.push(separator);
+		items.push(item(state, next));
+	}
+	join_right(items, separators, |separator, left, right| {
+		join(state, separator, left, right)
+	})
+}
+
+fn connected_sumti(connective: Connective, left: Sumti, right: Sumti, span: Span) -> Sumti {
+	Sumti {
+		quantifier: None,
+		kind: SumtiKind::Connected {
+			connective,
+			left: Box::new(left),
+			right: Box::new(right),
+		},
+		relative_clauses: Vec::new(),
+		frees: Vec::new(),
+		span,
+	}
+}
+
+/// Lowers CST nodes into AST nodes.
+///
+/// Methods that take a `frees` argument push free modifiers that have no closer node with a `frees` field to it.
+pub(super) struct Lowerer<'input> {
+	input: &'input str,
+	tokens: &'input [Token],
+}
+
+impl<'input> Lowerer<'input> {
+	pub(super) fn new(input: &'input str, tokens: &'input [Token]) -> Self {
+		Self { input, tokens }
+	}
+
+	/// Get the span of a node that is not empty.
+	pub(super) fn span(&self, node: &dyn TreeNodeChild) -> Span {
+		let start = TreeNodeChild::start_token(node).expect("lowered nodes are not empty");
+		let end = TreeNodeChild::end_token(node).expect("lowered nodes are not empty");
+		Span::new(
+			self.tokens[start as usize].span.start,
+			self.tokens[end as usize - 1].span.end,
+		)
+	}
+
+	/// Get the span of a token.
+	fn token_span(&self, token: cst::TokenIndex) -> Span {
+		token.get(self.tokens).span
+	}
+
+	/// Make a [`Word`] of the token at `span`.
+	fn word_at(&self, selmaho: Selmaho, span: Span, indicators: Vec<Indicator>) -> Word {
+		Word {
+			selmaho,
+			text: word_text(span, self.input),
+			span,
+			indicators,
+		}
+	}
+
+	/// The text at `span` as it is written.
+	pub(super) fn text_at(&self, span: Span) -> String {
+		span.slice(self.input).unwrap_or_default().to_owned()
+	}
+
+	pub(super) fn word(&self, token: &impl ToWord) -> Word {
+		token.word(self)
+	}
+
+	/// Lower indicators, grouping each `CAI` and `NAI` with the indicator before it.
+	fn indicators(&self, indicators: &cst::Indicators<'_>) -> Vec<Indicator> {
+		let mut words = Vec::new();
+		self.flatten_indicators(indicators, &mut words);
+		let mut grouped: Vec<Indicator> = Vec::new();
+		for word in words {
+			match (word.selmaho, grouped.last_mut()) {
+				(Selmaho::Cai | Selmaho::Nai, Some(last)) => last.modifiers.push(word),
+				_ => grouped.push(Indicator {
+					word,
+					modifiers: Vec::new(),
+				}),
+			}
+		}
+		grouped
+	}
+
+	/// Collect the words of indicators in order, including those nested in the indicators of other indicators, without their own indicators.
+	fn flatten_indicators(&self, indicators: &cst::Indicators<'_>, words: &mut Vec<Word>) {
+		if let Some(fuhe) = &indicators.0 {
+			words.push(self.word(fuhe));
+		}
+		for indicator in indicators.1.as_slice() {
+			let (selmaho, token, nested) = match indicator {
+				cst::Indicator::Ui(ui) => (Selmaho::Ui, ui.inner.token, ui.indicators),
+				cst::Indicator::Cai(cai) => (Selmaho::Cai, cai.inner.token, cai.indicators),
+				cst::Indicator::Nai(nai) => (Selmaho::Nai, nai.inner.token, nai.indicators),
+				cst::Indicator::Daho(daho) => (Selmaho::Daho, daho.inner.token, daho.indicators),
+				cst::Indicator::Fuho(fuho) => (Selmaho::Fuho, fuho.inner.token, fuho.indicators),
+				cst::Indicator::Y(y) => (Selmaho::Y, y.inner.token, y.indicators),
+			};
+			words.push(self.word_at(selmaho, self.token_span(token), Vec::new()));
+			if let Some(nested) = nested {
+				self.flatten_indicators(nested, words);
+			}
+		}
+	}
+
+	/// Determine whether the second construct of a forethought connection is negated with `nai` after `gi`.
+	fn gik_nai(&self, gik: &connectives::Gik<'_>) -> bool {
+		gik.1.is_some() || take_nai(&mut self.word(&gik.0)).is_some()
+	}
+
+	/// Lower a `JOI` or `BIhI` connective, which does not depend on the words themselves.
+	fn joik<'arena>(&self, joik: &'arena connectives::Joik<'arena>) -> Connective {
+		let interval = |interval: &connectives::Interval<'_>, brackets| {
+			let mut word = self.word(&interval.1);
+			let negated = interval.2.is_some() | take_nai(&mut word).is_some();
+			(
+				word,
+				ConnectiveKind::Interval {
+					swapped: interval.0.is_some(),
+					negated,
+					brackets,
+				},
+			)
+		};
+		let (word, kind) = match joik {
+			connectives::Joik::SeJoiNai(se, joi, nai) => {
+				let mut word = self.word(joi);
+				let negated = nai.is_some() | take_nai(&mut word).is_some();
+				(
+					word,
+					ConnectiveKind::NonLogical {
+						swapped: se.is_some(),
+						negated,
+					},
+				)
+			}
+			connectives::Joik::Interval(inner) => interval(inner, None),
+			connectives::Joik::Gaho(open, inner, close) => {
+				interval(inner, Some((self.word(open), self.word(close))))
+			}
+		};
+		Connective {
+			word: Some(word),
+			kind,
+			tag: None,
+			frees: Vec::new(),
+		}
+	}
+
+	pub(super) fn text<'arena>(&self, text: &'arena cst::Text<'arena>) -> Text {
+		let mut frees = Vec::new();
+		self.frees(&text.initial_frees, &mut frees);
+		let separator = text.initial_paragraph_separator.as_ref();
+		let paragraphs = if let Some(paragraphs) = &text.paragraphs {
+			self.paragraphs(separator, paragraphs)
+		} else {
+			if let Some(separator) = separator {
+				self.frees(&separator.1, &mut frees);
+			}
+			Vec::new()
+		};
+		Text {
+			paragraphs,
+			indicators: text
+				.initial_indicators
+				.as_ref()
+				.map_or_else(Vec::new, |indicators| self.indicators(indicators)),
+			frees,
+		}
+	}
+
+	fn paragraphs<'arena>(
+		&self,
+		initial_separator: Option<&'arena cst::ParagraphSeparator<'arena>>,
+		paragraphs: &'arena cst::Paragraphs<'arena>,
+	) -> Vec<Paragraph> {
+		let mut lowered = vec![self.paragraph(initial_separator, paragraphs.0.first)];
+		for (separator, paragraph) in paragraphs.0.rest.as_slice() {
+			lowered.push(self.paragraph(Some(separator), paragraph));
+		}
+		lowered
+	}
+
+	fn paragraph<'arena>(
+		&self,
+		separator: Option<&'arena cst::ParagraphSeparator<'arena>>,
+		paragraph: &'arena cst::Paragraph<'arena>,
+	) -> Paragraph {
+		let mut frees = Vec::new();
+		let niho = separator.map_or_else(Vec::new, |separator| {
+			self.frees(&separator.1, &mut frees);
+			separator.0.iter().map(|token| self.word(token)).collect()
+		});
+		let mut sentences = Vec::new();
+		self.paragraph_item(
+			paragraph.initial_sentence_separator.as_ref(),
+			paragraph.sentences.first,
+			&mut sentences,
+		);
+		for (separator, item) in paragraph.sentences.rest.as_slice() {
+			self.paragraph_item(Some(separator), item, &mut sentences);
+		}
+		Paragraph {
+			niho,
+			sentences,
+			frees,
+		}
+	}
+
+	fn paragraph_item<'arena>(
+		&self,
+		separator: Option<&'arena cst::SentenceSeparator<'arena>>,
+		item: &'arena cst::ParagraphItem<'arena>,
+		sentences: &mut Vec<Sentence>,
+	) {
+		let mut frees = Vec::new();
+		let i = separator.map(|separator| {
+			self.frees(&separator.1, &mut frees);
+			self.word(&separator.0)
+		});
+		let statement = match item {
+			cst::ParagraphItem::Sentences(sentences) => self.sentences1(sentences, &mut frees),
+			cst::ParagraphItem::Fragment(fragment) => {
+				Statement::Fragment(self.fragment(fragment, &mut frees))
+			}
+			cst::ParagraphItem::Empty() => return,
+		};
+		sentences.push(Sentence {
+			i,
+			statement,
+			frees,
+		});
+	}
+
+	fn fragment<'arena>(
+		&self,
+		fragment: &'arena cst::Fragment<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Fragment {
+		let (mut connective, connective_frees) = match fragment {
+			cst::Fragment::Ek(ek) => (self.logical(&ek.inner.0), &ek.frees),
+			cst::Fragment::Jek(jek) => (self.logical(&jek.inner.0), &jek.frees),
+			cst::Fragment::Joik(joik) => (self.joik(&joik.inner), &joik.frees),
+			cst::Fragment::Gihek(gihek) => (self.logical(&gihek.inner.0), &gihek.frees),
+			cst::Fragment::Number(quantifier) => {
+				return Fragment::Number(self.quantifier(quantifier, frees));
+			}
+			cst::Fragment::Na(na, na_frees) => {
+				self.frees(na_frees, frees);
+				return Fragment::Negation(self.word(na));
+			}
+		};
+		self.frees(connective_frees, &mut connective.frees);
+		Fragment::Connective(connective)
+	}
+
+	fn sentences1<'arena>(
+		&self,
+		sentences: &'arena cst::Sentences1<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Statement {
+		let body = fold_left(
+			&sentences.1,
+			frees,
+			|frees, sentences| self.sentences2(sentences, frees),
+			|_, separator, left, right| {
+				let mut connective = self.joik_jek(&separator.1);
+				self.frees(&separator.2, &mut connective.frees);
+				Statement::Connected {
+					i: self.word(&separator.0),
+					connective,
+					left: Box::new(left),
+					right: Box::new(right),
+				}
+			},
+		);
+		self.prenexes(sentences.0.as_slice(), body)
+	}
+
+	fn sentences2<'arena>(
+		&self,
+		sentences: &'arena cst::Sentences2<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Statement {
+		fold_right(
+			sentences,
+			frees,
+			|frees, sentences| self.sentences3(sentences, frees),
+			|_, separator, left, right| Statement::Connected {
+				i: self.word(&separator.0),
+				connective: self.tagged(
+					separator
+						.1
+						.as_ref()
+						.map(|connective| self.joik_jek(connective)),
+					separator.2.as_ref(),
+					&separator.4,
+				),
+				left: Box::new(left),
+				right: Box::new(right),
+			},
+		)
+	}
+
+	fn sentences3<'arena>(
+		&self,
+		sentences: &'arena cst::Sentences3<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Statement {
+		match sentences {
+			cst::Sentences3::Grouped(tag, tuhe, paragraphs, _, trailing_frees) => {
+				let mut group_frees = Vec::new();
+				let tag = tag.as_ref().map(|tag| self.tag(tag, &mut group_frees));
+				self.frees(&tuhe.frees, &mut group_frees);
+				let paragraphs = self.paragraphs(None, paragraphs);
+				self.frees(trailing_frees, &mut group_frees);
+				Statement::Group {
+					tag,
+					paragraphs,
+					frees: group_frees,
+				}
+			}
+			cst::Sentences3::Single(sentence) => self.sentence(sentence, frees),
+		}
+	}
+
+	fn prenexes<'arena>(
+		&self,
+		prenexes: &'arena [cst::Prenex<'arena>],
+		body: Statement,
+	) -> Statement {
+		prenexes.iter().rev().fold(body, |body, prenex| {
+			let mut frees = Vec::new();
+			let terms = self.args(&prenex.terms, &mut frees);
+			self.frees(&prenex.frees, &mut frees);
+			Statement::Prenex {
+				terms,
+				body: Box::new(body),
+				frees,
+			}
+		})
+	}
+
+	fn sentence<'arena>(
+		&self,
+		sentence: &'arena cst::Sentence<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Statement {
+		let mut bridi_frees = Vec::new();
+		let head = self.args(&sentence.before_args, &mut bridi_frees);
+		if let Some(tail) = &sentence.tail {
+			let tail = self.sentence_tail(tail, &mut bridi_frees);
+			Statement::Bridi(Bridi {
+				head,
+				tail,
+				frees: bridi_frees,
+				span: self.span(sentence),
+			})
+		} else {
+			frees.append(&mut bridi_frees);
+			Statement::Fragment(Fragment::Terms(head))
+		}
+	}
+
+	fn subsentence<'arena>(&self, subsentence: &'arena cst::Subsentence<'arena>) -> Statement {
+		let mut frees = Vec::new();
+		let head = self.args(&subsentence.1, &mut frees);
+		let tail = self.sentence_tail(&subsentence.2, &mut frees);
+		let mut span = self.span(&subsentence.2);
+		if TreeNodeChild::start_token(&subsentence.1).is_some() {
+			span.start = self.span(&subsentence.1).start;
+		}
+		let bridi = Statement::Bridi(Bridi {
+			head,
+			tail,
+			frees,
+			span,
+		});
+		self.prenexes(subsentence.0.as_slice(), bridi)
+	}
+
+	fn sentence_tail<'arena>(
+		&self,
+		tail: &'arena cst::SentenceTail<'arena>,
+		frees: &mut Vec<Free>,
+	) -> BridiTail {
+		self.frees(&tail.1, frees);
+		self.sentence_tail1(&tail.2, frees)
+	}
+
+	fn sentence_tail1<'arena>(
+		&self,
+		tail: &'arena cst::SentenceTail1<'arena>,
+		frees: &mut Vec<Free>,
+	) -> BridiTail {
+		let left = self.sentence_tail2(&tail.0, frees);
+		let Some(after) = tail.1 else {
+			return left;
+		};
+		let connective = self.tagged(Some(self.logical(&after.0 .0)), after.1.as_ref(), &after.3);
+		let right = self.sentence_tail1(after.4, frees);
+		self.frees(&after.6, frees);
+		BridiTail::Connected {
+			connective,
+			left: Box::new(left),
+			right: Box::new(right),
+			terms: self.tail_args(&after.7, frees),
+		}
+	}
+
+	fn sentence_tail2<'arena>(
+		&self,
+		tail: &'arena cst::SentenceTail2<'arena>,
+		frees: &mut Vec<Free>,
+	) -> BridiTail {
+		let mut result = self.sentence_tail3(tail.0, frees);
+		let Some(after) = &tail.1 else {
+			return result;
+		};
+		for (connective, next) in after.0.as_slice() {
+			let mut lowered = self.logical(&connective.0 .0);
+			self.frees(&connective.1, &mut lowered.frees);
+			let right = self.sentence_tail3(next, frees);
+			result = BridiTail::Connected {
+				connective: lowered,
+				left: Box::new(result),
+				right: Box::new(right),
+				terms: Vec::new(),
+			};
+		}
+		self.shared_terms(&mut result, &after.1, frees);
+		result
+	}
+
+	fn sentence_tail3<'arena>(
+		&self,
+		tail: &'arena cst::SentenceTail3<'arena>,
+		frees: &mut Vec<Free>,
+	) -> BridiTail {
+		let first = self.sentence_tail4(tail.0, frees);
+		let Some(after) = &tail.1 else {
+			return first;
+		};
+		let mut tails = vec![first];
+		let mut connectives = Vec::new();
+		for ((gihek, tag, _, connective_frees), next) in after.0.as_slice() {
+			connectives.push(self.tagged(Some(self.logical(&gihek.0)), tag.as_ref(), connective_frees));
+			tails.push(self.sentence_tail4(next, frees));
+		}
+		let mut result = join_right(tails, connectives, |connective, left, right| {
+			BridiTail::Connected {
+				connective,
+				left: Box::new(left),
+				right: Box::new(right),
+				terms: Vec::new(),
+			}
+		});
+		self.shared_terms(&mut result, &after.1, frees);
+		result
+	}
+
+	fn sentence_tail4<'arena>(
+		&self,
+		tail: &'arena cst::SentenceTail4<'arena>,
+		frees: &mut Vec<Free>,
+	) -> BridiTail {
+		match tail {
+			cst::SentenceTail4::Single(selbri, args) => BridiTail::Selbri {
+				selbri: self.selbri(selbri, frees),
+				terms: self.tail_args(args, frees),
+			},
+			cst::SentenceTail4::Parenthesized(negations, tag, ke, sentence, _, trailing_frees) => {
+				let negations = negations
+					.iter()
+					.map(|na| self.word_with_frees(na, frees))
+					.collect();
+				let tag = tag.as_ref().map(|tag| self.tag(tag, frees));
+				self.frees(&ke.frees, frees);
+				let tail = self.gek_sentence(sentence, negations, tag, frees);
+				self.frees(trailing_frees, frees);
+				tail
+			}
+			cst::SentenceTail4::Connected(sentence) => {
+				self.gek_sentence(sentence, Vec::new(), None, frees)
+			}
+		}
+	}
+
+	fn gek_sentence<'arena>(
+		&self,
+		sentence: &'arena cst::GekSentence<'arena>,
+		mut negations: Vec<Word>,
+		tag: Option<Tag>,
+		frees: &mut Vec<Free>,
+	) -> BridiTail {
+		negations.extend(sentence.0.iter().map(|na| self.word_with_frees(na, frees)));
+		BridiTail::Forethought {
+			negations,
+			tag,
+			connective: self.gek(&sentence.1, &sentence.3),
+			first: Box::new(self.subsentence(&sentence.2)),
+			second: Box::new(self.subsentence(&sentence.4)),
+			terms: self.tail_args(&sentence.5, frees),
+		}
+	}
+
+	/// Set the terms shared by all of a connected tail.
+	fn shared_terms<'arena>(
+		&self,
+		tail: &mut BridiTail,
+		args: &'arena cst::TailArgs<'arena>,
+		frees: &mut Vec<Free>,
+	) {
+		let shared = self.tail_args(args, frees);
+		if let BridiTail::Connected { terms, .. } = tail {
+			*terms = shared;
+		}
+	}
+
+	fn args<'arena>(&self, args: &'arena cst::Args<'arena>, frees: &mut Vec<Free>) -> Vec<Term> {
+		args.0.iter().map(|arg| self.arg(arg, frees)).collect()
+	}
+
+	fn tail_args<'arena>(
+		&self,
+		args: &'arena cst::TailArgs<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Vec<Term> {
+		let terms = self.args(&args.0, frees);
+		self.frees(&args.2, frees);
+		terms
+	}
+
+	fn arg<'arena>(&self, arg: &'arena cst::Arg<'arena>, frees: &mut Vec<Free>) -> Term {
+		let mut result = self.arg1(&arg.0, frees);
+		for post in arg.1.as_slice() {
+			let (connective, right) = match post {
+				cst::SumtiLikeConnectedPost::Normal(connective, next) => {
+					(self.joik_ek(connective), self.arg1(next, frees))
+				}
+				cst::SumtiLikeConnectedPost::Grouped(connective, tag, ke, inner, _, trailing_frees) => {
+					let connective = self.tagged(Some(self.joik_ek(connective)), tag.as_ref(), &ke.frees);
+					let right = self.arg(inner, frees);
+					self.frees(trailing_frees, frees);
+					(connective, right)
+				}
+			};
+			result = Term::Connected {
+				connective,
+				left: Box::new(result),
+				right: Box::new(right),
+			};
+		}
+		result
+	}
+
+	fn arg1<'arena>(&self, arg: &'arena cst::Arg1<'arena>, frees: &mut Vec<Free>) -> Term {
+		fold_left(
+			arg,
+			frees,
+			|frees, arg| self.arg2(arg, frees),
+			|_, separator, left, right| {
+				let mut connective = self.joik_jek(&separator.1);
+				self.frees(&separator.0.frees, &mut connective.frees);
+				Term::Connected {
+					connective,
+					left: Box::new(left),
+					right: Box::new(right),
+				}
+			},
+		)
+	}
+
+	fn arg2<'arena>(&self, arg: &'arena cst::Arg2<'arena>, frees: &mut Vec<Free>) -> Term {
+		if arg.rest.is_empty() {
+			return self.arg3(arg.first, frees);
+		}
+		let mut terms = vec![self.arg3(arg.first, frees)];
+		for (cehe, next) in arg.rest.as_slice() {
+			self.frees(&cehe.frees, frees);
+			terms.push(self.arg3(next, frees));
+		}
+		Term::Termset(terms)
+	}
+
+	fn arg3<'arena>(&self, arg: &'arena cst::Arg3<'arena>, frees: &mut Vec<Free>) -> Term {
+		match arg {
+			cst::Arg3::Tag(tag) => {
+				let lowered = self.tag(&tag.words, frees);
+				let sumti = match &tag.value {
+					Some(cst::TagValue::Sumti(sumti)) => Some(self.sumti(sumti)),
+					Some(cst::TagValue::Ku(ku)) => {
+						self.frees(&ku.frees, frees);
+						None
+					}
+					None => None,
+				};
+				Term::Tagged {
+					tag: lowered,
+					sumti,
+				}
+			}
+			cst::Arg3::Sumti { fa, sumti } => Term::Sumti {
+				place: fa.as_ref().map(|fa| self.word_with_frees(fa, frees)),
+				sumti: self.sumti(sumti),
+			},
+			cst::Arg3::Naku(na, _, naku_frees) => {
+				self.frees(naku_frees, frees);
+				Term::Negation(self.word(na))
+			}
+			cst::Arg3::Termset(termset) => self.termset(termset, frees),
+		}
+	}
+
+	fn termset<'arena>(&self, termset: &'arena cst::Termset<'arena>, frees: &mut Vec<Free>) -> Term {
+		let (gek, first, gik, second) = match termset {
+			cst::Termset::Gek(gek, first, gik, second) => (gek, first, gik, second),
+			cst::Termset::NuhiGi(nuhi, gek, first, _, first_frees, gik, second, _, second_frees) => {
+				self.frees(&nuhi.frees, frees);
+				self.frees(first_frees, frees);
+				self.frees(second_frees, frees);
+				(gek, first, gik, second)
+			}
+			cst::Termset::Nuhi(nuhi, args, _, trailing_frees) => {
+				self.frees(&nuhi.frees, frees);
+				let terms = self.args(args, frees);
+				self.frees(trailing_frees, frees);
+				return Term::Termset(terms);
+			}
+		};
+		Term::Connected {
+			connective: self.gek(gek, gik),
+			left: Box::new(Term::Termset(self.args(first, frees))),
+			right: Box::new(Term::Termset(self.args(second, frees))),
+		}
+	}
+
+	pub(super) fn sumti<'arena>(&self, sumti: &'arena cst::Sumti<'arena>) -> Sumti {
+		let mut frees = Vec::new();
+		let mut lowered = self.sumti1(&sumti.inner, &mut frees);
+		if let Some(vuho) = &sumti.vuho_relative {
+			self.frees(&vuho.vuho.frees, &mut frees);
+			lowered
+				.relative_clauses
+				.extend(self.relative_clauses(&vuho.relative_clauses));
+		}
+		lowered.frees.append(&mut frees);
+		lowered.span = self.span(sumti);
+		lowered
+	}
+
+	fn sumti1<'arena>(&self, sumti: &'arena cst::Sumti1<'arena>, frees: &mut Vec<Free>) -> Sumti {
+		let mut result = self.sumti2(&sumti.0, frees);
+		for post in sumti.1.as_slice() {
+			let (connective, right) = match post {
+				cst::SumtiLikeConnectedPost::Normal(connective, next) => {
+					(self.joik_ek(connective), self.sumti2(next, frees))
+				}
+				cst::SumtiLikeConnectedPost::Grouped(connective, tag, ke, inner, _, trailing_frees) => {
+					let connective = self.tagged(Some(self.joik_ek(connective)), tag.as_ref(), &ke.frees);
+					let right = self.sumti(inner);
+					self.frees(trailing_frees, frees);
+					(connective, right)
+				}
+			};
+			let span = Span::new(result.span.start, right.span.end);
+			result = connected_sumti(connective, result, right, span);
+		}
+		result
+	}
+
+	fn sumti2<'arena>(&self, sumti: &'arena cst::Sumti2<'arena>, frees: &mut Vec<Free>) -> Sumti {
+		fold_right(
+			sumti,
+			frees,
+			|_, sumti| self.sumti3(sumti),
+			|_, (connective, tag, _, connective_frees), left, right| {
+				let span = Span::new(left.span.start, right.span.end);
+				let connective = self.tagged(
+					Some(self.joik_ek(connective)),
+					tag.as_ref(),
+					connective_frees,
+				);
+				connected_sumti(connective, left, right, span)
+			},
+		)
+	}
+
+	fn sumti3<'arena>(&self, sumti: &'arena cst::Sumti3<'arena>) -> Sumti {
+		let mut result = self.sumti4(&sumti.1);
+		for pre in sumti.0.iter().rev() {
+			let span = Span::new(self.span(pre).start, result.span.end);
+			result = connected_sumti(self.gek(&pre.0, &pre.2), self.sumti(&pre.1), result, span);
+		}
+		result
+	}
+
+	fn sumti4<'arena>(&self, sumti: &'arena cst::Sumti4<'arena>) -> Sumti {
+		match sumti {
+			cst::Sumti4::Normal {
+				quantifier,
+				inner,
+				relative_clauses,
+			} => {
+				let mut frees = Vec::new();
+				let quantifier = quantifier.map(|quantifier| self.quantifier(quantifier, &mut frees));
+				let mut lowered = self.component_sumti(inner);
+				frees.append(&mut lowered.frees);
+				if let Some(relative_clauses) = relative_clauses {
+					lowered
+						.relative_clauses
+						.extend(self.relative_clauses(relative_clauses));
+				}
+				Sumti {
+					quantifier,
+					frees,
+					span: self.span(sumti),
+					..lowered
+				}
+			}
+			cst::Sumti4::SelbriShorthand {
+				quantifier,
+				inner,
+				ku: _,
+				frees: trailing_frees,
+				relative_clauses,
+			} => {
+				let mut frees = Vec::new();
+				let quantifier = self.quantifier(quantifier, &mut frees);
+				let selbri = self.selbri(inner, &mut frees);
+				self.frees(trailing_frees, &mut frees);
+				Sumti {
+					quantifier: Some(quantifier),
+					kind: SumtiKind::Description(Box::new(Description {
+						descriptor: None,
+						possessor: None,
+						quantifier: None,
+						inner: DescriptionInner::Selbri(selbri),
+						relative_clauses: Vec::new(),
+					})),
+					relative_clauses: relative_clauses
+						.as_ref()
+						.map_or_else(Vec::new, |relative_clauses| {
+							self.relative_clauses(relative_clauses)
+						}),
+					frees,
+					span: self.span(sumti),
+				}
+			}
+		}
+	}
+
+	/// Lower a sumti without a quantifier or relative clauses after it, such as the possessor in `le mi gerku`.
+	fn component_sumti<'arena>(&self, component: &'arena cst::SumtiComponent<'arena>) -> Sumti {
+		let mut frees = Vec::new();
+		let mut relative_clauses = Vec::new();
+		let kind = self.sumti_component(&component.inner, &mut relative_clauses, &mut frees);
+		self.frees(&component.frees, &mut frees);
+		Sumti {
+			quantifier: None,
+			kind,
+			relative_clauses,
+			frees,
+			span: self.span(component),
+		}
+	}
+
+	fn sumti_component<'arena>(
+		&self,
+		component: &'arena cst::SumtiComponent1<'arena>,
+		relative_clauses: &mut Vec<RelativeClause>,
+		frees: &mut Vec<Free>,
+	) -> SumtiKind {
+		match component {
+			cst::SumtiComponent1::Koha(koha) => SumtiKind::ProSumti(self.word(koha)),
+			cst::SumtiComponent1::Gadri(gadri) => {
+				SumtiKind::Description(Box::new(self.description(gadri, frees)))
+			}
+			cst::SumtiComponent1::La(la) => SumtiKind::Name {
+				la: self.word(&la.la),
+				names: la.inner.iter().map(|token| self.word(token)).collect(),
+			},
+			cst::SumtiComponent1::Lohu(lohu) => SumtiKind::Quote(Quote::Words(
+				match (lohu.inner.first(), lohu.inner.last()) {
+					(Some(first), Some(last)) => Some(Span::new(
+						self.token_span(first.token).start,
+						self.token_span(last.token).end,
+					)),
+					_ => None,
+				},
+			)),
+			cst::SumtiComponent1::Lu(lu) => SumtiKind::Quote(Quote::Text(Box::new(self.text(&lu.text)))),
+			cst::SumtiComponent1::Modified(modified) => {
+				self.frees(&modified.modifier.frees, frees);
+				let word = match &modified.modifier.inner {
+					cst::SumtiModifier::Lahe(lahe) => self.word(lahe),
+					cst::SumtiModifier::NaheBo(nahe, _) => self.word(nahe),
+				};
+				if let Some(modified_relative_clauses) = &modified.relative_clauses {
+					relative_clauses.extend(self.relative_clauses(modified_relative_clauses));
+				}
+				SumtiKind::Modified {
+					modifier: word,
+					sumti: Box::new(self.sumti(&modified.sumti)),
+				}
+			}
+			cst::SumtiComponent1::LerfuString(lerfu_string, _) => {
+				SumtiKind::Lerfu(self.lerfu_string(lerfu_string))
+			}
+			cst::SumtiComponent1::Zo(zo) => {
+				SumtiKind::Quote(Quote::Word(self.token_span(zo.quoted.token)))
+			}
+			cst::SumtiComponent1::Zoi(zoi) => SumtiKind::Quote(Quote::NonLojban(self.span(&zoi.text))),
+			cst::SumtiComponent1::Li(li, mekso, _) => {
+				self.frees(&li.frees, frees);
+				SumtiKind::Mekso(self.mekso(mekso))
+			}
+		}
+	}
+
+	fn description<'arena>(
+		&self,
+		sumti: &'arena cst::GadriSumti<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Description {
+		let descriptor = match &sumti.gadri.inner {
+			cst::Gadri::Le(le) => self.word(le),
+			cst::Gadri::La(la) => self.word(la),
+		};
+		self.frees(&sumti.gadri.frees, frees);
+		let mut relative_clauses = Vec::new();
+		let possessor = match &sumti.pre {
+			cst::GadriSumtiPre::Simple {
+				pe_shorthand,
+				relative_clauses: pre_relative_clauses,
+			} => {
+				let possessor = pe_shorthand.map(|possessor| self.component_sumti(possessor));
+				if let Some(pre_relative_clauses) = pre_relative_clauses {
+					relative_clauses.extend(self.relative_clauses(pre_relative_clauses));
+				}
+				possessor
+			}
+			cst::GadriSumtiPre::Relative {
+				relative_clauses: pre_relative_clauses,
+			} => {
+				relative_clauses.extend(self.relative_clauses(pre_relative_clauses));
+				None
+			}
+			cst::GadriSumtiPre::FullPre { pe_shorthand } => Some(self.sumti(pe_shorthand)),
+		};
+		let (quantifier, inner) = match &sumti.contents {
+			cst::GadriSumtiContents::Selbri(quantifier, selbri, post_relative_clauses) => {
+				let quantifier = quantifier.map(|quantifier| self.quantifier(quantifier, frees));
+				let selbri = self.selbri(selbri, frees);
+				if let Some(post_relative_clauses) = post_relative_clauses {
+					relative_clauses.extend(self.relative_clauses(post_relative_clauses));
+				}
+				(quantifier, DescriptionInner::Selbri(selbri))
+			}
+			cst::GadriSumtiContents::Sumti(quantifier, inner) => (
+				Some(self.quantifier(quantifier, frees)),
+				DescriptionInner::Sumti(self.sumti(inner)),
+			),
+		};
+		Description {
+			descriptor: Some(descriptor),
+			possessor,
+			quantifier,
+			inner,
+			relative_clauses,
+		}
+	}
+
+	fn quantifier<'arena>(
+		&self,
+		quantifier: &'arena cst::Quantifier<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Quantifier {
+		match quantifier {
+			cst::Quantifier::Mekso(vei, mekso, _, trailing_frees) => {
+				self.frees(&vei.frees, frees);
+				self.frees(trailing_frees, frees);
+				Quantifier::Mekso(self.mekso(mekso))
+			}
+			cst::Quantifier::Number(number, _, trailing_frees) => {
+				self.frees(trailing_frees, frees);
+				Quantifier::Number(self.number(number))
+			}
+		}
+	}
+
+	fn relative_clauses<'arena>(
+		&self,
+		relative_clauses: &'arena cst::RelativeClauses<'arena>,
+	) -> Vec<RelativeClause> {
+		let mut lowered = vec![self.relative_clause(relative_clauses.0.first, Vec::new())];
+		for (zihe, relative_clause) in relative_clauses.0.rest.as_slice() {
+			let mut frees = Vec::new();
+			self.frees(&zihe.frees, &mut frees);
+			lowered.push(self.relative_clause(relative_clause, frees));
+		}
+		lowered
+	}
+
+	fn relative_clause<'arena>(
+		&self,
+		relative_clause: &'arena cst::RelativeClause<'arena>,
+		mut frees: Vec<Free>,
+	) -> RelativeClause {
+		match relative_clause {
+			cst::RelativeClause::Goi(goi) => {
+				let word = self.word_with_frees(&goi.goi, &mut frees);
+				let term = self.arg(&goi.inner, &mut frees);
+				self.frees(&goi.frees, &mut frees);
+				RelativeClause::Association {
+					word,
+					term: Box::new(term),
+					frees,
+				}
+			}
+			cst::RelativeClause::Noi(noi) => {
+				let word = self.word_with_frees(&noi.noi, &mut frees);
+				let body = self.subsentence(noi.inner);
+				self.frees(&noi.frees, &mut frees);
+				RelativeClause::Bridi {
+					word,
+					body: Box::new(body),
+					frees,
+				}
+			}
+		}
+	}
+
+	pub(super) fn selbri<'arena>(
+		&self,
+		selbri: &'arena cst::Selbri<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Selbri {
+		let modifiers = selbri
+			.before
+			.iter()
+			.map(|before| match before {
+				cst::SelbriBefore::Na(na) => SelbriModifier::Negation(self.word_with_frees(na, frees)),
+				cst::SelbriBefore::Tag(tag) => SelbriModifier::Tag(self.tag(tag, frees)),
+			})
+			.collect();
+		Selbri {
+			modifiers,
+			tanru: self.selbri1(&selbri.components, frees),
+			span: self.span(selbri),
+		}
+	}
+
+	fn selbri1<'arena>(&self, selbri: &'arena cst::Selbri1<'arena>, frees: &mut Vec<Free>) -> Tanru {
+		// `co` swaps the modifier and the head
+		fold_right(
+			&selbri.0,
+			frees,
+			|frees, selbri| self.selbri2(selbri, frees),
+			|frees, co, head, modifier| {
+				self.frees(&co.frees, frees);
+				Tanru::Modified {
+					modifier: Box::new(modifier),
+					head: Box::new(head),
+				}
+			},
+		)
+	}
+
+	fn selbri2<'arena>(&self, selbri: &'arena cst::Selbri2<'arena>, frees: &mut Vec<Free>) -> Tanru {
+		let mut parts = selbri.0.iter();
+		let first = parts.next().expect("tanru have at least one part");
+		let mut result = self.selbri3(first, frees);
+		for next in parts {
+			let head = self.selbri3(next, frees);
+			result = Tanru::Modified {
+				modifier: Box::new(result),
+				head: Box::new(head),
+			};
+		}
+		result
+	}
+
+	fn selbri3<'arena>(&self, selbri: &'arena cst::Selbri3<'arena>, frees: &mut Vec<Free>) -> Tanru {
+		let mut result = self.selbri4(&selbri.0, frees);
+		for post in selbri.1.as_slice() {
+			let (connective, right) = match post {
+				cst::SelbriLikeConnectedPost::Normal(connective, next) => {
+					(self.joik_jek(connective), self.selbri4(next, frees))
+				}
+				cst::SelbriLikeConnectedPost::Parenthesized(
+					connective,
+					tag,
+					ke,
+					inner,
+					_,
+					trailing_frees,
+				) => {
+					let connective = self.tagged(Some(self.joik_jek(connective)), tag.as_ref(), &ke.frees);
+					let right = self.selbri2(inner, frees);
+					self.frees(trailing_frees, frees);
+					(connective, right)
+				}
+			};
+			result = Tanru::Connected {
+				connective,
+				left: Box::new(result),
+				right: Box::new(right),
+			};
+		}
+		result
+	}
+
+	fn selbri4<'arena>(&self, selbri: &'arena cst::Selbri4<'arena>, frees: &mut Vec<Free>) -> Tanru {
+		fold_right(
+			&selbri.0,
+			frees,
+			|frees, selbri| self.selbri5(selbri, frees),
+			|_, (connective, tag, _, connective_frees), left, right| Tanru::Connected {
+				connective: self.tagged(
+					Some(self.joik_jek(connective)),
+					tag.as_ref(),
+					connective_frees,
+				),
+				left: Box::new(left),
+				right: Box::new(right),
+			},
+		)
+	}
+
+	fn selbri5<'arena>(&self, selbri: &'arena cst::Selbri5<'arena>, frees: &mut Vec<Free>) -> Tanru {
+		let mut result = self.selbri6(&selbri.1, frees);
+		for pre in selbri.0.iter().rev() {
+			let cst::NaheGuhekTGik(nahe, nahe_frees, guhek, first, gik) = pre;
+			self.frees(nahe_frees, frees);
+			let first = self.selbri(first, frees);
+			let left = if first.modifiers.is_empty() {
+				first.tanru
+			} else {
+				Tanru::Selbri(Box::new(first))
+			};
+			result = Tanru::Connected {
+				connective: self.guhek(guhek, gik),
+				left: Box::new(left),
+				right: Box::new(result),
+			};
+			if let Some(nahe) = nahe {
+				result = Tanru::Unit(Box::new(TanruUnit {
+					conversions: vec![Conversion::Nahe(self.word(nahe))],
+					kind: TanruUnitKind::Group(Box::new(result)),
+					bound_arguments: Vec::new(),
+					frees: Vec::new(),
+					span: Span::new(self.span(pre).start, self.span(selbri).end),
+				}));
+			}
+		}
+		result
+	}
+
+	fn selbri6<'arena>(&self, selbri: &'arena cst::Selbri6<'arena>, frees: &mut Vec<Free>) -> Tanru {
+		fold_right(
+			&selbri.0,
+			frees,
+			|frees, unit| self.tanru_unit(unit, frees),
+			|frees, bo, modifier, head| {
+				self.frees(&bo.frees, frees);
+				Tanru::Modified {
+					modifier: Box::new(modifier),
+					head: Box::new(head),
+				}
+			},
+		)
+	}
+
+	fn tanru_unit<'arena>(
+		&self,
+		unit: &'arena cst::TanruUnit<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Tanru {
+		let value = self.tanru_unit1(unit.0.first);
+		if unit.0.rest.is_empty() {
+			return Tanru::Unit(Box::new(value));
+		}
+		let assigned = unit
+			.0
+			.rest
+			.iter()
+			.map(|(cei, unit)| {
+				self.frees(&cei.frees, frees);
+				self.tanru_unit1(unit)
+			})
+			.collect();
+		Tanru::Assignment {
+			value: Box::new(value),
+			assigned,
+		}
+	}
+
+	fn tanru_unit1<'arena>(&self, unit: &'arena cst::TanruUnit1<'arena>) -> TanruUnit {
+		let mut frees = Vec::new();
+		let conversions = unit
+			.before
+			.iter()
+			.map(|before| match before {
+				cst::BeforeTanruUnit::Jai { jai, tag } => Conversion::Jai {
+					jai: self.word_with_frees(jai, &mut frees),
+					tag: tag.as_ref().map(|tag| self.tag(tag, &mut frees)),
+				},
+				cst::BeforeTanruUnit::Nahe(nahe) => {
+					Conversion::Nahe(self.word_with_frees(nahe, &mut frees))
+				}
+				cst::BeforeTanruUnit::Se(se) => Conversion::Se(self.word_with_frees(se, &mut frees)),
+			})
+			.collect();
+		let kind = self.tanru_unit2(&unit.inner, &mut frees);
+		let mut bound_arguments = Vec::new();
+		if let Some(bound) = unit.bound_arguments {
+			self.frees(&bound.be.frees, &mut frees);
+			bound_arguments.push(self.arg(bound.args.first, &mut frees));
+			for (bei, arg) in bound.args.rest.as_slice() {
+				self.frees(&bei.frees, &mut frees);
+				bound_arguments.push(self.arg(arg, &mut frees));
+			}
+			self.frees(&bound.frees, &mut frees);
+		}
+		TanruUnit {
+			conversions,
+			kind,
+			bound_arguments,
+			frees,
+			span: self.span(unit),
+		}
+	}
+
+	fn tanru_unit2<'arena>(
+		&self,
+		unit: &'arena cst::TanruUnit2<'arena>,
+		frees: &mut Vec<Free>,
+	) -> TanruUnitKind {
+		match unit {
+			cst::TanruUnit2::GroupedTanru {
+				ke,
+				group,
+				kehe: _,
+				frees: trailing_frees,
+			} => {
+				self.frees(&ke.frees, frees);
+				let group = self.selbri2(group, frees);
+				self.frees(trailing_frees, frees);
+				TanruUnitKind::Group(Box::new(group))
+			}
+			cst::TanruUnit2::Gismu(brivla) => TanruUnitKind::Brivla(self.word_with_frees(brivla, frees)),
+			cst::TanruUnit2::Lujvo(brivla) => TanruUnitKind::Brivla(self.word_with_frees(brivla, frees)),
+			cst::TanruUnit2::Fuhivla(brivla) => {
+				TanruUnitKind::Brivla(self.word_with_frees(brivla, frees))
+			}
+			cst::TanruUnit2::Zei(zei) => {
+				self.frees(&zei.frees, frees);
+				let indicators = zei
+					.inner
+					.4
+					.map_or_else(Vec::new, |indicators| self.indicators(indicators));
+				// the parts are one lujvo, whose text is that of all of them
+				let parts = Span::new(self.span(&zei.inner).start, self.span(&zei.inner.3).end);
+				TanruUnitKind::Zei(self.word_at(Selmaho::Lujvo, parts, indicators))
+			}
+			cst::TanruUnit2::Goha {
+				goha,
+				raho,
+				frees: trailing_frees,
+			} => {
+				self.frees(trailing_frees, frees);
+				TanruUnitKind::ProBridi {
+					word: self.word(goha),
+					raho: raho.as_ref().map(|token| self.word(token)),
+				}
+			}
+			cst::TanruUnit2::Moi(number, moi, trailing_frees) => {
+				self.frees(trailing_frees, frees);
+				TanruUnitKind::Number {
+					number: self.misc_numbers(number),
+					moi: self.word(moi),
+				}
+			}
+			cst::TanruUnit2::Me {
+				me,
+				inner,
+				mehu: _,
+				frees: trailing_frees,
+				moi,
+			} => {
+				self.frees(&me.frees, frees);
+				let sumti = self.sumti(inner);
+				self.frees(trailing_frees, frees);
+				TanruUnitKind::Me {
+					sumti: Box::new(sumti),
+					moi: moi.map(|moi| self.word_with_frees(moi, frees)),
+				}
+			}
+			cst::TanruUnit2::Nu {
+				nus,
+				inner,
+				kei: _,
+				frees: trailing_frees,
+			} => {
+				let abstractor = fold_left(
+					nus,
+					frees,
+					|frees, (nu, nai, nu_frees)| {
+						self.frees(nu_frees, frees);
+						let mut word = self.word(nu);
+						let nai = nai
+							.as_ref()
+							.map(|token| self.word(token))
+							.or_else(|| take_nai(&mut word));
+						Abstractor::Single { word, nai }
+					},
+					|_, connective, left, right| Abstractor::Connected {
+						connective: self.joik_jek(connective),
+						left: Box::new(left),
+						right: Box::new(right),
+					},
+				);
+				let body = self.subsentence(inner);
+				self.frees(trailing_frees, frees);
+				TanruUnitKind::Abstraction {
+					abstractor,
+					body: Box::new(body),
+				}
+			}
+			cst::TanruUnit2::Nuha { nuha, operator } => {
+				self.frees(&nuha.frees, frees);
+				TanruUnitKind::Operator(self.operator(operator))
+			}
+		}
+	}
+
+	fn tag<'arena>(&self, tag: &'arena cst::TagWords<'arena>, frees: &mut Vec<Free>) -> Tag {
+		fold_left(
+			tag,
+			frees,
+			|frees, word| Tag::Single(self.tag_unit(word, frees)),
+			|_, connective, left, right| Tag::Connected {
+				connective: Box::new(self.joik_jek(connective)),
+				left: Box::new(left),
+				right: Box::new(right),
+			},
+		)
+	}
+
+	fn tag_unit<'arena>(&self, word: &'arena cst::TagWord<'arena>, frees: &mut Vec<Free>) -> TagUnit {
+		match word {
+			cst::TagWord::Bai {
+				nahe,
+				se,
+				bai,
+				nai,
+				ki,
+				frees: trailing_frees,
+			} => {
+				self.frees(trailing_frees, frees);
+				let mut word = self.word(bai);
+				TagUnit::Modal {
+					nahe: nahe.as_ref().map(|token| self.word(token)),
+					se: se.as_ref().map(|token| self.word(token)),
+					nai: nai
+						.as_ref()
+						.map(|token| self.word(token))
+						.or_else(|| take_nai(&mut word)),
+					word,
+					ki: ki.as_ref().map(|token| self.word(token)),
+				}
+			}
+			cst::TagWord::TimeSpaceCaha {
+				nahe,
+				inner,
+				ki,
+				frees: trailing_frees,
+			} => {
+				let mut words = Words {
+					lowerer: self,
+					words: Vec::new(),
+				};
+				inner.visit(&mut words);
+				self.frees(trailing_frees, frees);
+				TagUnit::Tense {
+					nahe: nahe.as_ref().map(|token| self.word(token)),
+					words: words.words,
+					ki: ki.as_ref().map(|token| self.word(token)),
+				}
+			}
+			cst::TagWord::Ki(ki, ki_frees) => {
+				self.frees(ki_frees, frees);
+				TagUnit::Ki(self.word(ki))
+			}
+			cst::TagWord::Cuhe(cuhe, cuhe_frees) => {
+				self.frees(cuhe_frees, frees);
+				TagUnit::Cuhe(self.word(cuhe))
+			}
+			cst::TagWord::Converted(fiho, selbri, _, trailing_frees) => {
+				let fiho = self.word_with_frees(fiho, frees);
+				let selbri = self.selbri(selbri, frees);
+				self.frees(trailing_frees, frees);
+				TagUnit::Selbri {
+					fiho,
+					selbri: Box::new(selbri),
+				}
+			}
+		}
+	}
+
+	/// Lower an `A`, `JA` or `GIhA` connective.
+	fn logical<'arena, T: ToWord>(
+		&self,
+		connective: &'arena connectives::NaSeTNai<'arena, T>,
+	) -> Connective {
+		let mut word = self.word(&connective.2);
+		let negate_second = connective.3.is_some() | take_nai(&mut word).is_some();
+		Connective {
+			kind: ConnectiveKind::Logical {
+				function: truth_function(&word),
+				negate_first: connective.0.is_some(),
+				negate_second,
+				swapped: connective.1.is_some(),
+			},
+			word: Some(word),
+			tag: None,
+			frees: Vec::new(),
+		}
+	}
+
+	pub(super) fn joik_jek<'arena>(
+		&self,
+		connective: &'arena connectives::JoikJek<'arena>,
+	) -> Connective {
+		match connective {
+			connectives::JoikJek::Joik(joik) => self.joik(joik),
+			connectives::JoikJek::Jek(jek) => self.logical(&jek.0),
+		}
+	}
+
+	pub(super) fn joik_ek<'arena>(
+		&self,
+		connective: &'arena connectives::JoikEk<'arena>,
+	) -> Connective {
+		let (mut lowered, frees) = match connective {
+			connectives::JoikEk::Joik(joik, frees) => (self.joik(joik), frees),
+			connectives::JoikEk::Ek(ek, frees) => (self.logical(&ek.0), frees),
+		};
+		self.frees(frees, &mut lowered.frees);
+		lowered
+	}
+
+	/// Lower a forethought connective with the `gi` that separates the constructs it joins.
+	pub(super) fn gek<'arena>(
+		&self,
+		gek: &'arena connectives::Gek<'arena>,
+		gik: &'arena connectives::Gik<'arena>,
+	) -> Connective {
+		let mut connective = match gek {
+			connectives::Gek::SeGaNai(se, ga, nai, gek_frees) => {
+				let mut word = self.word(ga);
+				let negate_first = nai.is_some() | take_nai(&mut word).is_some();
+				let mut connective = Connective {
+					kind: ConnectiveKind::Logical {
+						function: truth_function(&word),
+						negate_first,
+						negate_second: self.gik_nai(gik),
+						swapped: se.is_some(),
+					},
+					word: Some(word),
+					tag: None,
+					frees: Vec::new(),
+				};
+				self.frees(gek_frees, &mut connective.frees);
+				connective
+			}
+			connectives::Gek::JoikGi(joik, _, gek_frees) => {
+				let mut connective = self.joik(joik);
+				self.frees(gek_frees, &mut connective.frees);
+				connective
+			}
+			connectives::Gek::TagGik(tag, tag_gik) => {
+				let mut frees = Vec::new();
+				let tag = self.tag(tag, &mut frees);
+				self.frees(&tag_gik.2, &mut frees);
+				Connective {
+					word: None,
+					kind: ConnectiveKind::Tagged,
+					tag: Some(tag),
+					frees,
+				}
+			}
+		};
+		self.frees(&gik.2, &mut connective.frees);
+		connective
+	}
+
+	pub(super) fn guhek<'arena>(
+		&self,
+		guhek: &'arena connectives::Guhek<'arena>,
+		gik: &'arena connectives::Gik<'arena>,
+	) -> Connective {
+		let mut word = self.word(&guhek.1);
+		let negate_first = guhek.2.is_some() | take_nai(&mut word).is_some();
+		let mut connective = Connective {
+			kind: ConnectiveKind::Logical {
+				function: truth_function(&word),
+				negate_first,
+				negate_second: self.gik_nai(gik),
+				swapped: guhek.0.is_some(),
+			},
+			word: Some(word),
+			tag: None,
+			frees: Vec::new(),
+		};
+		self.frees(&guhek.3, &mut connective.frees);
+		self.frees(&gik.2, &mut connective.frees);
+		connective
+	}
+
+	/// Add a tag and free modifiers to a connective, or make a connective of only the tag, as in `.i ba bo`.
+	pub(super) fn tagged<'arena>(
+		&self,
+		connective: Option<Connective>,
+		tag: Option<&'arena cst::TagWords<'arena>>,
+		frees: &'arena cst::Frees<'arena>,
+	) -> Connective {
+		let mut connective = connective.unwrap_or(Connective {
+			word: None,
+			kind: ConnectiveKind::Tagged,
+			tag: None,
+			frees: Vec::new(),
+		});
+		connective.tag = tag.map(|tag| self.tag(tag, &mut connective.frees));
+		self.frees(frees, &mut connective.frees);
+		connective
+	}
+
+	pub(super) fn word_with_frees<'arena, T: ToWord>(
+		&self,
+		token: &'arena cst::WithFree<'arena, T>,
+		frees: &mut Vec<Free>,
+	) -> Word {
+		self.frees(&token.frees, frees);
+		self.word(&token.inner)
+	}
+
+	pub(super) fn frees<'arena>(&self, frees: &'arena cst::Frees<'arena>, sink: &mut Vec<Free>) {
+		for free in frees.0.as_slice() {
+			let lowered = self.free(free, sink);
+			sink.push(lowered);
+		}
+	}
+
+	/// Lower a free modifier, pushing the free modifiers inside of it to `frees`.
+	fn free<'arena>(&self, free: &'arena cst::Free<'arena>, frees: &mut Vec<Free>) -> Free {
+		match free {
+			cst::Free::Sei(sei, args, tail, sehu) => {
+				let sei = self.word_with_frees(sei, frees);
+				let terms = self.args(args, frees);
+				let selbri = tail.as_ref().map(|tail| {
+					self.frees(&tail.1, frees);
+					self.selbri(&tail.2, frees)
+				});
+				if let Some(sehu) = sehu {
+					self.frees(&sehu.frees, frees);
+				}
+				Free::Discursive { sei, terms, selbri }
+			}
+			cst::Free::Soi(soi, sumti, _) => Free::Soi {
+				soi: self.word_with_frees(soi, frees),
+				first: self.sumti(&sumti.0),
+				second: sumti.1.as_ref().map(|second| self.sumti(second)),
+			},
+			cst::Free::Vocative(cst::Vocative(words, before, value, after, _)) => {
+				let words = match words {
+					cst::VocativeWords::Coi(coi, doi) => {
+						let mut words = Vec::new();
+						for (coi, nai) in coi.as_slice() {
+							words.push(self.word(coi));
+							words.extend(nai.as_ref().map(|token| self.word(token)));
+						}
+						words.extend(doi.as_ref().map(|token| self.word(token)));
+						words
+					}
+					cst::VocativeWords::Doi(doi) => vec![self.word(doi)],
+				};
+				let mut relative_clauses = before
+					.as_ref()
+					.map_or_else(Vec::new, |before| self.relative_clauses(before));
+				let addressee = match value {
+					cst::VocativeValue::Selbri(selbri) => Some(Addressee::Selbri(self.selbri(selbri, frees))),
+					cst::VocativeValue::Cmevla(names) => Some(Addressee::Name(
+						names.iter().map(|token| self.word(token)).collect(),
+					)),
+					cst::VocativeValue::Sumti(sumti) => {
+						sumti.map(|sumti| Addressee::Sumti(self.sumti(sumti)))
+					}
+				};
+				if let Some(after) = after {
+					relative_clauses.extend(self.relative_clauses(after));
+				}
+				Free::Vocative {
+					words,
+					addressee,
+					relative_clauses,
+				}
+			}
+			cst::Free::Mai(number, mai) => Free::Ordinal {
+				number: self.misc_numbers(number),
+				mai: self.word(mai),
+			},
+			cst::Free::To(_, text, _) => Free::Parenthetical(Box::new(self.text(text))),
+			cst::Free::Xi(subscript) => {
+				self.frees(&subscript.0.frees, frees);
+				self.frees(&subscript.1.frees, frees);
+				Free::Subscript(self.subscript(&subscript.1.inner, frees))
+			}
+		}
+	}
+
+	fn subscript<'arena>(
+		&self,
+		subscript: &'arena cst::SubscriptValue<'arena>,
+		frees: &mut Vec<Free>,
+	) -> Quantifier {
+		match subscript {
+			cst::SubscriptValue::Mekso(vei, mekso, _, trailing_frees) => {
+				self.frees(&vei.frees, frees);
+				self.frees(trailing_frees, frees);
+				Quantifier::Mekso(self.mekso(mekso))
+			}
+			cst::SubscriptValue::Number(numbers, _, trailing_frees) => {
+				self.frees(trailing_frees, frees);
+				Quantifier::Number(self.misc_numbers(numbers))
+			}
+		}
+	}
+
+	pub(super) fn number(&self, number: &cst::Number<'_>) -> Numeral {
+		let mut words = vec![NumeralWord::Pa(self.word(&number.first))];
+		self.numeral_words(&number.rest, &mut words);
+		Numeral {
+			words,
+			span: self.span(number),
+		}
+	}
+
+	pub(super) fn misc_numbers(&self, numbers: &cst::MiscNumbers<'_>) -> Numeral {
+		let mut words = Vec::new();
+		self.numeral_words(&numbers.0, &mut words);
+		Numeral {
+			words,
+			span: self.span(numbers),
+		}
+	}
+
+	pub(super) fn lerfu_string(&self, string: &cst::LerfuString<'_>) -> Numeral {
+		let mut words = vec![self.lerfu_word(&string.first)];
+		self.numeral_words(&string.rest, &mut words);
+		Numeral {
+			words,
+			span: self.span(string),
+		}
+	}
+
+	fn numeral_words(&self, rest: &[cst::NumberRest<'_>], words: &mut Vec<NumeralWord>) {
+		words.extend(rest.iter().map(|rest| match rest {
+			cst::NumberRest::Pa(pa) => NumeralWord::Pa(self.word(pa)),
+			cst::NumberRest::Lerfu(word) => self.lerfu_word(word),
+		}));
+	}
+
+	fn lerfu_word(&self, word: &cst::LerfuWord<'_>) -> NumeralWord {
+		match word {
+			cst::LerfuWord::Lerfu(lerfu) => self.lerfu(None, lerfu),
+			cst::LerfuWord::Lau { lau, lerfu } => self.lerfu(Some(self.word(lau)), lerfu),
+			cst::LerfuWord::Tei { tei, inner, foi } => NumeralWord::Composite {
+				tei: self.word(tei),
+				inner: self.lerfu_string(inner),
+				foi: self.word(foi),
+			},
+		}
+	}
+
+	fn lerfu(&self, lau: Option<Word>, lerfu: &cst::Lerfu<'_>) -> NumeralWord {
+		match lerfu {
+			cst::Lerfu::Bu(_, cst::BuInner(token), bus, indicators) => {
+				let mut bu = bus
+					.iter()
+					.map(|bu| self.word_at(Selmaho::Bu, self.token_span(bu.token), Vec::new()))
+					.collect::<Vec<_>>();
+				if let Some(last) = bu.last_mut() {
+					last.indicators = indicators
+						.iter()
+						.flat_map(|indicators| self.indicators(indicators))
+						.collect();
+				}
+				NumeralWord::Lerfu {
+					lau,
+					word: self.word_at(token.selmaho, self.token_span(token.token), Vec::new()),
+					bu,
+				}
+			}
+			cst::Lerfu::By(by) => NumeralWord::Lerfu {
+				lau,
+				word: self.word(by),
+				bu: Vec::new(),
+			},
+		}
+	}
+}
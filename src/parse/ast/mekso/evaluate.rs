@@ -0,0 +1,461 @@
+use super::{Expression, ExpressionKind, Operator, OperatorKind};
+use crate::parse::ast::number::{self, Quantity, Rational};
+use crate::parse::ast::{Conversion, Numeral};
+use crate::span::Span;
+
+impl Expression {
+	/// Evaluate the expression.
+	///
+	/// Numbers are evaluated with [`number::evaluate`], and the arithmetic `VUhU` operators, such as `su'i` and `te'a`, are applied to them.
+	///
+	/// # Errors
+	/// Returns an error if the expression contains parts that have no numeric value, such as lerfu variables, `ni'e` selbri, indefinite numbers, or operators other than the arithmetic ones, or if the arithmetic itself fails, as in division by zero.
+	pub fn evaluate(&self) -> Result<Value, Error> {
+		let error = |kind| Error {
+			span: self.span,
+			kind,
+		};
+		match &self.kind {
+			ExpressionKind::Number(numeral) => number(numeral),
+			ExpressionKind::Apply { operator, operands } => {
+				let operands = operands
+					.iter()
+					.map(Expression::evaluate)
+					.collect::<Result<_, _>>()?;
+				apply(operator, operands)
+			}
+			ExpressionKind::Selbri(_) => Err(error(ErrorKind::Selbri)),
+			ExpressionKind::Sumti(_) => Err(error(ErrorKind::Sumti)),
+			ExpressionKind::Vector(_) => Err(error(ErrorKind::Vector)),
+			ExpressionKind::Modified { .. } => Err(error(ErrorKind::Modified)),
+			ExpressionKind::Connected { .. } => Err(error(ErrorKind::Connective)),
+		}
+	}
+}
+
+/// The value of a mekso expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+	/// An exact value, from expressions that use only exact numbers and operators whose results are rational.
+	Exact(Rational),
+	/// An inexact value, from expressions with approximate numbers (`ji'i`), irrational results such as roots, or exact results too large to represent.
+	Float(f64),
+}
+
+impl Value {
+	/// The value as a floating-point number.
+	#[must_use]
+	pub fn to_f64(self) -> f64 {
+		match self {
+			Self::Exact(value) => value.to_f64(),
+			Self::Float(value) => value,
+		}
+	}
+
+	fn is_zero(self) -> bool {
+		match self {
+			Self::Exact(value) => value.numerator() == 0,
+			Self::Float(value) => value == 0.0,
+		}
+	}
+
+	#[allow(clippy::float_cmp)] // the remainder of an integer is exact
+	fn is_odd_integer(self) -> bool {
+		match self {
+			Self::Exact(value) => value.is_integer() && value.numerator() % 2 != 0,
+			Self::Float(value) => (value % 2.0).abs() == 1.0,
+		}
+	}
+
+	fn float(value: f64) -> Result<Self, ErrorKind> {
+		if value.is_nan() {
+			Err(ErrorKind::Undefined)
+		} else if value.is_infinite() {
+			Err(ErrorKind::Overflow)
+		} else {
+			Ok(Self::Float(value))
+		}
+	}
+
+	/// Apply an operation exactly if both values are exact and the result fits, and in floating point otherwise.
+	fn binary(
+		self,
+		other: Self,
+		exact: impl FnOnce(Rational, Rational) -> Option<Rational>,
+		float: impl FnOnce(f64, f64) -> f64,
+	) -> Result<Self, ErrorKind> {
+		if let (Self::Exact(left), Self::Exact(right)) = (self, other) {
+			if let Some(result) = exact(left, right) {
+				return Ok(Self::Exact(result));
+			}
+		}
+		Self::float(float(self.to_f64(), other.to_f64()))
+	}
+
+	fn add(self, other: Self) -> Result<Self, ErrorKind> {
+		self.binary(other, Rational::checked_add, |left, right| left + right)
+	}
+
+	fn sub(self, other: Self) -> Result<Self, ErrorKind> {
+		self.binary(other, Rational::checked_sub, |left, right| left - right)
+	}
+
+	fn mul(self, other: Self) -> Result<Self, ErrorKind> {
+		self.binary(other, Rational::checked_mul, |left, right| left * right)
+	}
+
+	fn div(self, other: Self) -> Result<Self, ErrorKind> {
+		if other.is_zero() {
+			return Err(ErrorKind::DivisionByZero);
+		}
+		self.binary(other, Rational::checked_div, |left, right| left / right)
+	}
+
+	fn neg(self) -> Result<Self, ErrorKind> {
+		Self::Exact(Rational::from(0)).sub(self)
+	}
+
+	fn abs(self) -> Result<Self, ErrorKind> {
+		if self.to_f64() < 0.0 {
+			self.neg()
+		} else {
+			Ok(self)
+		}
+	}
+
+	fn pow(self, exponent: Self) -> Result<Self, ErrorKind> {
+		if self.is_zero() && exponent.to_f64() < 0.0 {
+			return Err(ErrorKind::DivisionByZero);
+		}
+		self.binary(
+			exponent,
+			|base, exponent| {
+				let exponent = i32::try_from(exponent.numerator())
+					.ok()
+					.filter(|_| exponent.is_integer())?;
+				base.checked_pow(exponent)
+			},
+			f64::powf,
+		)
+	}
+
+	#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)] // only used to guess at an exact root, which is then checked
+	fn root(self, index: Self) -> Result<Self, ErrorKind> {
+		if index.is_zero() || (self.is_zero() && index.to_f64() < 0.0) {
+			return Err(ErrorKind::DivisionByZero);
+		}
+		// an odd root of a negative number is the negative of the root of its absolute value
+		if self.to_f64() < 0.0 && index.is_odd_integer() {
+			return self.neg()?.root(index)?.neg();
+		}
+		if let (Self::Exact(radicand), Self::Exact(index)) = (self, index) {
+			let exact = i32::try_from(index.numerator())
+				.ok()
+				.filter(|_| index.is_integer())
+				.and_then(|index| {
+					let nearest = |part: i128| (part as f64).powf(f64::from(index).recip()).round() as i128;
+					let root = Rational::new(
+						nearest(radicand.numerator()),
+						nearest(radicand.denominator()),
+					)?;
+					(root.checked_pow(index)? == radicand).then_some(root)
+				});
+			if let Some(root) = exact {
+				return Ok(Self::Exact(root));
+			}
+		}
+		Self::float(self.to_f64().powf(index.to_f64().recip()))
+	}
+
+	fn factorial(self) -> Result<Self, ErrorKind> {
+		let Self::Exact(value) = self else {
+			return Err(ErrorKind::Undefined);
+		};
+		if !value.is_integer() || value.numerator() < 0 {
+			return Err(ErrorKind::Undefined);
+		}
+		(1..=value.numerator()).try_fold(Self::Exact(Rational::from(1)), |product, factor| {
+			product.mul(Self::Exact(Rational::from(factor)))
+		})
+	}
+
+	#[allow(clippy::float_cmp)] // only a base of exactly 1 has no logarithms
+	fn log(self, base: Self) -> Result<Self, ErrorKind> {
+		if self.to_f64() <= 0.0 || base.to_f64() <= 0.0 || base.to_f64() == 1.0 {
+			return Err(ErrorKind::Undefined);
+		}
+		let logarithm = self.to_f64().log(base.to_f64());
+		// a logarithm is exact when rounding it gives a power of the base that is the argument
+		if let (Self::Exact(_), Self::Exact(_)) = (self, base) {
+			#[allow(clippy::cast_possible_truncation)] // the logarithm of an `i128` is small
+			let rounded = Self::Exact(Rational::from(logarithm.round() as i128));
+			if logarithm.is_finite() && base.pow(rounded) == Ok(self) {
+				return Ok(rounded);
+			}
+		}
+		Self::float(logarithm)
+	}
+}
+
+impl std::fmt::Display for Value {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Exact(value) => value.fmt(formatter),
+			Self::Float(value) => value.fmt(formatter),
+		}
+	}
+}
+
+/// An error in evaluating a mekso expression, with the part of the expression that caused it.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} at {span:?}")]
+pub struct Error {
+	/// The span of the part of the expression that could not be evaluated.
+	pub span: Span,
+	/// Why it could not be evaluated.
+	pub kind: ErrorKind,
+}
+
+/// The reasons that a mekso expression cannot be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// A lerfu string, which stands for a variable.
+	#[error("lerfu variable")]
+	Variable,
+	/// An indefinite number, such as `so'i`.
+	#[error("indefinite number")]
+	Indefinite,
+	/// A number that cannot be evaluated for another reason.
+	#[error("invalid number")]
+	Number(#[source] number::Error),
+	/// A selbri used as an operand with `ni'e` or as an operator with `na'u`.
+	#[error("selbri")]
+	Selbri,
+	/// A sumti used as an operand with `mo'e`.
+	#[error("sumti")]
+	Sumti,
+	/// A vector made with `jo'i`.
+	#[error("vector")]
+	Vector,
+	/// An operand with a modifier such as `la'e`.
+	#[error("modified operand")]
+	Modified,
+	/// Operands or operators joined with logical or non-logical connectives.
+	#[error("connected operands or operators")]
+	Connective,
+	/// An operator without a known arithmetic meaning, such as `ri'o`, an operator made from an expression with `ma'o`, or an operator with `na'e`.
+	#[error("operator without a known value")]
+	Operator,
+	/// An operator applied to a number of operands that it does not take.
+	#[error("operator applied to {0} operands")]
+	Arity(usize),
+	/// A division by zero, including zero raised to a negative power.
+	#[error("division by zero")]
+	DivisionByZero,
+	/// An operation whose result is not a real number, such as the square root of a negative number or the factorial of a fraction.
+	#[error("undefined result")]
+	Undefined,
+	/// An operation whose result is too large to represent even as a floating-point number.
+	#[error("result too large")]
+	Overflow,
+}
+
+fn number(numeral: &Numeral) -> Result<Value, Error> {
+	let value = number::evaluate(numeral).map_err(|error| match error {
+		number::Error::Lerfu(span) => Error {
+			span,
+			kind: ErrorKind::Variable,
+		},
+		other => Error {
+			span: numeral.span,
+			kind: ErrorKind::Number(other),
+		},
+	})?;
+	match value.quantity {
+		Quantity::Exact(exact) if value.approximate => Ok(Value::Float(exact.to_f64())),
+		Quantity::Exact(exact) => Ok(Value::Exact(exact)),
+		Quantity::Indefinite { .. } => Err(Error {
+			span: numeral.span,
+			kind: ErrorKind::Indefinite,
+		}),
+	}
+}
+
+fn apply(operator: &Operator, mut operands: Vec<Value>) -> Result<Value, Error> {
+	let error = |kind| Error {
+		span: operator.span,
+		kind,
+	};
+	// the outermost conversion applies first, so `se te su'i` takes its operands in the order 3, 1, 2
+	for conversion in &operator.conversions {
+		let Conversion::Se(se) = conversion else {
+			return Err(error(ErrorKind::Operator));
+		};
+		let place = match se.text.as_str() {
+			"se" => 1,
+			"te" => 2,
+			"ve" => 3,
+			"xe" => 4,
+			_ => return Err(error(ErrorKind::Operator)),
+		};
+		if place >= operands.len() {
+			return Err(error(ErrorKind::Arity(operands.len())));
+		}
+		operands.swap(0, place);
+	}
+	match &operator.kind {
+		OperatorKind::Vuhu(word) => arithmetic(&word.text, &operands).map_err(error),
+		OperatorKind::Expression(_) => Err(error(ErrorKind::Operator)),
+		OperatorKind::Selbri(_) => Err(error(ErrorKind::Selbri)),
+		OperatorKind::Connected { .. } => Err(error(ErrorKind::Connective)),
+	}
+}
+
+/// Apply the `VUhU` operator `word` to `operands`.
+fn arithmetic(word: &str, operands: &[Value]) -> Result<Value, ErrorKind> {
+	match (word, operands) {
+		("su'i", [first, rest @ ..]) => rest.iter().try_fold(*first, |sum, &next| sum.add(next)),
+		("pi'i", [first, rest @ ..]) => rest
+			.iter()
+			.try_fold(*first, |product, &next| product.mul(next)),
+		("vu'u", [first, rest @ ..]) if !rest.is_empty() => rest
+			.iter()
+			.try_fold(*first, |difference, &next| difference.sub(next)),
+		("fe'i", [first, rest @ ..]) if !rest.is_empty() => rest
+			.iter()
+			.try_fold(*first, |quotient, &next| quotient.div(next)),
+		("pa'i", [numerator, denominator]) => numerator.div(*denominator),
+		("te'a", [base, exponent]) => base.pow(*exponent),
+		("fe'a", [radicand]) => radicand.root(Value::Exact(Rational::from(2))),
+		("fe'a", [radicand, index]) => radicand.root(*index),
+		("de'o", [argument, base]) => argument.log(*base),
+		("va'a", [value]) => value.neg(),
+		("fa'i", [value]) => Value::Exact(Rational::from(1)).div(*value),
+		("cu'a", [value]) => value.abs(),
+		("ne'o", [value]) => value.factorial(),
+		(
+			"su'i" | "pi'i" | "vu'u" | "fe'i" | "pa'i" | "te'a" | "fe'a" | "de'o" | "va'a" | "fa'i"
+			| "cu'a" | "ne'o",
+			_,
+		) => Err(ErrorKind::Arity(operands.len())),
+		_ => Err(ErrorKind::Operator),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::parse::ast::mekso::test::first_expression;
+
+	fn evaluate_first(input: &str) -> Result<Value, (ErrorKind, &str)> {
+		first_expression(input)
+			.evaluate()
+			.map_err(|error| (error.kind, error.span.slice(input).unwrap()))
+	}
+
+	fn exact(numerator: i128, denominator: i128) -> Value {
+		Value::Exact(Rational::new(numerator, denominator).unwrap())
+	}
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:expr) => {
+			#[test]
+			fn $name() {
+				assert_eq!(evaluate_first($input), $expected);
+			}
+		};
+	}
+
+	make_test!(addition, "li re su'i re", Ok(exact(4, 1)));
+	make_test!(left_grouping, "li ci su'i vo pi'i mu", Ok(exact(35, 1)));
+	make_test!(bihe, "li ci su'i vo bi'e pi'i mu", Ok(exact(23, 1)));
+	make_test!(
+		bihe_right_grouping,
+		"li re bi'e te'a ci bi'e te'a re",
+		Ok(exact(512, 1))
+	);
+	make_test!(
+		parenthesized,
+		"li ci pi'i vei vo su'i mu ve'o",
+		Ok(exact(27, 1))
+	);
+	make_test!(
+		forethought,
+		"li pe'o su'i re boi ci boi vo ku'e",
+		Ok(exact(9, 1))
+	);
+	make_test!(
+		reverse_polish,
+		"li fu'a re boi ci su'i vo pi'i",
+		Ok(exact(20, 1))
+	);
+	make_test!(conversion, "li ci se vu'u re", Ok(exact(-1, 1)));
+	make_test!(fraction, "li pa fe'i re", Ok(exact(1, 2)));
+	make_test!(negative_power, "li re te'a ni'u re", Ok(exact(1, 4)));
+	make_test!(exact_root, "li pe'o fe'a vo ku'e", Ok(exact(2, 1)));
+	make_test!(
+		negative_odd_root,
+		"li pe'o fe'a ni'u bi boi ci ku'e",
+		Ok(exact(-2, 1))
+	);
+	make_test!(
+		negative_odd_inexact_root,
+		"li pe'o fe'a ni'u re boi ci ku'e",
+		Ok(Value::Float(-2_f64.powf(3_f64.recip())))
+	);
+	make_test!(
+		inexact_root,
+		"li pe'o fe'a re ku'e",
+		Ok(Value::Float(2_f64.sqrt()))
+	);
+	make_test!(factorial, "li pe'o ne'o mu ku'e", Ok(exact(120, 1)));
+	make_test!(logarithm, "li bi de'o re", Ok(exact(3, 1)));
+	make_test!(approximate, "li ji'i re su'i re", Ok(Value::Float(4.0)));
+	make_test!(
+		overflow_to_float,
+		"li re te'a pacino",
+		Ok(Value::Float(2_f64.powi(130)))
+	);
+	make_test!(
+		division_by_zero,
+		"li pa fe'i no",
+		Err((ErrorKind::DivisionByZero, "fe'i"))
+	);
+	make_test!(
+		undefined,
+		"li pe'o fe'a ni'u vo ku'e",
+		Err((ErrorKind::Undefined, "fe'a"))
+	);
+	make_test!(
+		overflow,
+		"li re te'a pa no no no no no no no no no no",
+		Err((ErrorKind::Overflow, "te'a"))
+	);
+	make_test!(variable, "li xy su'i re", Err((ErrorKind::Variable, "xy")));
+	make_test!(
+		selbri,
+		"li ni'e clani te'u su'i re",
+		Err((ErrorKind::Selbri, "ni'e clani te'u"))
+	);
+	make_test!(
+		indefinite,
+		"li so'i su'i re",
+		Err((ErrorKind::Indefinite, "so'i"))
+	);
+	make_test!(
+		unknown_operator,
+		"li re ju'u ci",
+		Err((ErrorKind::Operator, "ju'u"))
+	);
+	make_test!(
+		arity,
+		"li pe'o va'a re boi ci ku'e",
+		Err((ErrorKind::Arity(2), "va'a"))
+	);
+	make_test!(
+		connected_operands,
+		"li re boi a ci su'i re",
+		Err((ErrorKind::Connective, "re boi a ci"))
+	);
+}
@@ -0,0 +1,340 @@
+use super::{Expression, ExpressionKind, Operator, OperatorKind};
+use crate::parse::ast::lower::{join_right, Lowerer};
+use crate::parse::ast::{Connective, Conversion};
+use crate::parse::cst::rules::selmaho::Bihe;
+use crate::parse::cst::rules::{
+	mekso as cst, NaheGuhekTGik, SelbriLikeConnectedPost, Separated, SumtiLikeConnectedPost,
+	SumtiModifier, WithFree,
+};
+use crate::span::Span;
+
+impl Lowerer<'_> {
+	fn connected(&self, connective: Connective, left: Expression, right: Expression) -> Expression {
+		let span = Span::new(left.span.start, right.span.end);
+		Expression {
+			kind: ExpressionKind::Connected {
+				connective,
+				left: Box::new(left),
+				right: Box::new(right),
+			},
+			frees: Vec::new(),
+			span,
+			text: self.text_at(span),
+		}
+	}
+
+	fn apply(&self, operator: Operator, operands: Vec<Expression>, span: Span) -> Expression {
+		Expression {
+			kind: ExpressionKind::Apply { operator, operands },
+			frees: Vec::new(),
+			span,
+			text: self.text_at(span),
+		}
+	}
+
+	fn connected_operator(
+		&self,
+		connective: Connective,
+		left: Operator,
+		right: Operator,
+	) -> Operator {
+		let span = Span::new(left.span.start, right.span.end);
+		Operator {
+			conversions: Vec::new(),
+			kind: OperatorKind::Connected {
+				connective,
+				left: Box::new(left),
+				right: Box::new(right),
+			},
+			frees: Vec::new(),
+			span,
+			text: self.text_at(span),
+		}
+	}
+
+	pub(in crate::parse::ast) fn mekso<'arena>(
+		&self,
+		expression: &'arena cst::Expression<'arena>,
+	) -> Expression {
+		match expression {
+			cst::Expression::ReversePolish(fuha, reverse_polish) => {
+				let mut lowered = self.reverse_polish(reverse_polish);
+				self.frees(&fuha.frees, &mut lowered.frees);
+				lowered.span = self.span(expression);
+				lowered.text = self.text_at(lowered.span);
+				lowered
+			}
+			// operators without `bi'e` all bind equally and group from the left
+			cst::Expression::Normal(separated) => {
+				let mut result = self.tightly_bound(separated.first);
+				for (operator, next) in separated.rest.as_slice() {
+					let right = self.tightly_bound(next);
+					let span = Span::new(result.span.start, right.span.end);
+					result = self.apply(self.operator(operator), vec![result, right], span);
+				}
+				result
+			}
+		}
+	}
+
+	/// Lower operands joined by operators with `bi'e`, which group from the right.
+	fn tightly_bound<'arena>(
+		&self,
+		separated: &'arena Separated<
+			'arena,
+			cst::Expression1<'arena>,
+			(WithFree<'arena, Bihe<'arena>>, cst::Operator<'arena>),
+		>,
+	) -> Expression {
+		let mut operands = vec![self.expression1(separated.first)];
+		let mut operators = Vec::new();
+		for ((bihe, operator), operand) in separated.rest.as_slice() {
+			let mut operator = self.operator(operator);
+			self.frees(&bihe.frees, &mut operator.frees);
+			operators.push(operator);
+			operands.push(self.expression1(operand));
+		}
+		join_right(operands, operators, |operator, left, right| {
+			let span = Span::new(left.span.start, right.span.end);
+			self.apply(operator, vec![left, right], span)
+		})
+	}
+
+	fn reverse_polish<'arena>(
+		&self,
+		reverse_polish: &'arena cst::ReversePolish<'arena>,
+	) -> Expression {
+		let mut result = self.operand(&reverse_polish.0);
+		for cst::RPTail(operand, operator) in reverse_polish.1.as_slice() {
+			let right = self.reverse_polish(operand);
+			let span = Span::new(result.span.start, self.span(operator).end);
+			result = self.apply(self.operator(operator), vec![result, right], span);
+		}
+		result
+	}
+
+	fn expression1<'arena>(&self, expression: &'arena cst::Expression1<'arena>) -> Expression {
+		match expression {
+			cst::Expression1::Operand(operand) => self.operand(operand),
+			cst::Expression1::Forethought(forethought) => {
+				let mut frees = Vec::new();
+				if let Some(peho) = &forethought.peho {
+					self.frees(&peho.frees, &mut frees);
+				}
+				let operator = self.operator(&forethought.operator);
+				let operands = forethought
+					.operands
+					.iter()
+					.map(|operand| self.expression1(operand))
+					.collect();
+				self.frees(&forethought.frees, &mut frees);
+				Expression {
+					kind: ExpressionKind::Apply { operator, operands },
+					frees,
+					span: self.span(expression),
+					text: self.text_at(self.span(expression)),
+				}
+			}
+		}
+	}
+
+	fn operand<'arena>(&self, operand: &'arena cst::Operand<'arena>) -> Expression {
+		let mut result = self.operand1(&operand.0);
+		for post in operand.1.as_slice() {
+			let (connective, right) = match post {
+				SumtiLikeConnectedPost::Normal(connective, next) => {
+					(self.joik_ek(connective), self.operand1(next))
+				}
+				SumtiLikeConnectedPost::Grouped(connective, tag, ke, inner, _, trailing_frees) => {
+					let connective = self.tagged(Some(self.joik_ek(connective)), tag.as_ref(), &ke.frees);
+					let mut right = self.operand(inner);
+					self.frees(trailing_frees, &mut right.frees);
+					(connective, right)
+				}
+			};
+			result = self.connected(connective, result, right);
+		}
+		result
+	}
+
+	fn operand1<'arena>(&self, operand: &'arena cst::Operand1<'arena>) -> Expression {
+		let mut operands = vec![self.operand2(operand.0.first)];
+		let mut connectives = Vec::new();
+		for ((connective, tag, bo), next) in operand.0.rest.as_slice() {
+			connectives.push(self.tagged(Some(self.joik_ek(connective)), tag.as_ref(), &bo.frees));
+			operands.push(self.operand2(next));
+		}
+		join_right(operands, connectives, |connective, left, right| {
+			self.connected(connective, left, right)
+		})
+	}
+
+	fn operand2<'arena>(&self, operand: &'arena cst::Operand2<'arena>) -> Expression {
+		let mut result = self.operand3(&operand.1);
+		for pre in operand.0.iter().rev() {
+			result = self.connected(self.gek(&pre.0, &pre.2), self.operand(&pre.1), result);
+			result.span.start = self.span(pre).start;
+			result.text = self.text_at(result.span);
+		}
+		result
+	}
+
+	fn operand3<'arena>(&self, operand: &'arena cst::Operand3<'arena>) -> Expression {
+		let mut frees = Vec::new();
+		let kind = match operand {
+			cst::Operand3::Nihe(nihe, selbri, _, trailing_frees) => {
+				self.frees(&nihe.frees, &mut frees);
+				let selbri = self.selbri(selbri, &mut frees);
+				self.frees(trailing_frees, &mut frees);
+				ExpressionKind::Selbri(selbri)
+			}
+			cst::Operand3::Mohe(mohe, sumti, _, trailing_frees) => {
+				self.frees(&mohe.frees, &mut frees);
+				let sumti = self.sumti(sumti);
+				self.frees(trailing_frees, &mut frees);
+				ExpressionKind::Sumti(Box::new(sumti))
+			}
+			cst::Operand3::Johi(johi, operands, _, trailing_frees) => {
+				self.frees(&johi.frees, &mut frees);
+				let operands = operands
+					.iter()
+					.map(|operand| self.expression1(operand))
+					.collect();
+				self.frees(trailing_frees, &mut frees);
+				ExpressionKind::Vector(operands)
+			}
+			cst::Operand3::Modified(modifier, inner, _) => ExpressionKind::Modified {
+				modifier: match modifier {
+					SumtiModifier::Lahe(lahe) => self.word(lahe),
+					SumtiModifier::NaheBo(nahe, _) => self.word(nahe),
+				},
+				operand: Box::new(self.operand(inner)),
+			},
+			cst::Operand3::Parenthesized(vei, inner, _, trailing_frees) => {
+				let mut lowered = self.mekso(inner);
+				self.frees(&vei.frees, &mut lowered.frees);
+				self.frees(trailing_frees, &mut lowered.frees);
+				lowered.span = self.span(operand);
+				lowered.text = self.text_at(lowered.span);
+				return lowered;
+			}
+			cst::Operand3::Number(number, _, trailing_frees) => {
+				self.frees(trailing_frees, &mut frees);
+				ExpressionKind::Number(self.misc_numbers(number))
+			}
+		};
+		Expression {
+			kind,
+			frees,
+			span: self.span(operand),
+			text: self.text_at(self.span(operand)),
+		}
+	}
+
+	pub(in crate::parse::ast) fn operator<'arena>(
+		&self,
+		operator: &'arena cst::Operator<'arena>,
+	) -> Operator {
+		let mut result = self.operator1(&operator.0);
+		for post in operator.1.as_slice() {
+			let (connective, right) = match post {
+				SelbriLikeConnectedPost::Normal(connective, next) => {
+					(self.joik_jek(connective), self.operator1(next))
+				}
+				SelbriLikeConnectedPost::Parenthesized(connective, tag, ke, inner, _, trailing_frees) => {
+					let connective = self.tagged(Some(self.joik_jek(connective)), tag.as_ref(), &ke.frees);
+					let mut right = self.operator(inner);
+					self.frees(trailing_frees, &mut right.frees);
+					(connective, right)
+				}
+			};
+			result = self.connected_operator(connective, result, right);
+		}
+		result
+	}
+
+	fn operator1<'arena>(&self, operator: &'arena cst::Operator1<'arena>) -> Operator {
+		let mut result = self.operator2(&operator.1);
+		for pre in operator.0.iter().rev() {
+			let NaheGuhekTGik(nahe, nahe_frees, guhek, first, gik) = pre;
+			let first = self.operator1(first);
+			result = self.connected_operator(self.guhek(guhek, gik), first, result);
+			if let Some(nahe) = nahe {
+				result
+					.conversions
+					.insert(0, Conversion::Nahe(self.word(nahe)));
+			}
+			self.frees(nahe_frees, &mut result.frees);
+			result.span = Span::new(self.span(pre).start, result.span.end);
+			result.text = self.text_at(result.span);
+		}
+		result
+	}
+
+	fn operator2<'arena>(&self, operator: &'arena cst::Operator2<'arena>) -> Operator {
+		let mut operators = vec![self.operator3(operator.0.first)];
+		let mut connectives = Vec::new();
+		for ((connective, tag, bo), next) in operator.0.rest.as_slice() {
+			connectives.push(self.tagged(Some(self.joik_jek(connective)), tag.as_ref(), &bo.frees));
+			operators.push(self.operator3(next));
+		}
+		join_right(operators, connectives, |connective, left, right| {
+			self.connected_operator(connective, left, right)
+		})
+	}
+
+	fn operator3<'arena>(&self, operator: &'arena cst::Operator3<'arena>) -> Operator {
+		match operator {
+			cst::Operator3::Simple(component) => self.operator_component(component),
+			cst::Operator3::Grouped(ke, inner, _, trailing_frees) => {
+				let mut lowered = self.operator(inner);
+				self.frees(&ke.frees, &mut lowered.frees);
+				self.frees(trailing_frees, &mut lowered.frees);
+				lowered.span = self.span(operator);
+				lowered.text = self.text_at(lowered.span);
+				lowered
+			}
+		}
+	}
+
+	fn operator_component<'arena>(
+		&self,
+		component: &'arena cst::OperatorComponent<'arena>,
+	) -> Operator {
+		let mut frees = Vec::new();
+		let conversions = component
+			.0
+			.iter()
+			.map(|pre| match pre {
+				cst::OperatorComponentPre::Nahe(nahe) => {
+					Conversion::Nahe(self.word_with_frees(nahe, &mut frees))
+				}
+				cst::OperatorComponentPre::Se(se) => Conversion::Se(self.word_with_frees(se, &mut frees)),
+			})
+			.collect();
+		let kind = match &component.1 {
+			cst::OperatorComponent1::Maho(maho, expression, _, trailing_frees) => {
+				self.frees(&maho.frees, &mut frees);
+				let expression = self.mekso(expression);
+				self.frees(trailing_frees, &mut frees);
+				OperatorKind::Expression(Box::new(expression))
+			}
+			cst::OperatorComponent1::Nahu(nahu, selbri, _, trailing_frees) => {
+				self.frees(&nahu.frees, &mut frees);
+				let selbri = self.selbri(selbri, &mut frees);
+				self.frees(trailing_frees, &mut frees);
+				OperatorKind::Selbri(selbri)
+			}
+			cst::OperatorComponent1::Vuhu(vuhu) => {
+				OperatorKind::Vuhu(self.word_with_frees(vuhu, &mut frees))
+			}
+		};
+		Operator {
+			conversions,
+			kind,
+			frees,
+			span: self.span(component),
+			text: self.text_at(self.span(component)),
+		}
+	}
+}
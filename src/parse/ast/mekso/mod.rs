@@ -0,0 +1,228 @@
+//! Interpretation of mekso, the mathematical expressions of `li` and `vei`.
+//!
+//! Mekso can be written in infix, forethought (`pe'o`) and reverse-Polish (`fu'a`) notation, which the CST keeps apart.
+//! [Lowering](super::lower) turns all three into the same [`Expression`] tree, in which every operator is [applied](ExpressionKind::Apply) to an explicit list of operands.
+//! For infix expressions, operators marked with `bi'e` bind more tightly than others and group from the right, and other operators all bind equally and group from the left, so `li ci su'i vo pi'i mu` is `(3 + 4) × 5`.
+//!
+//! [`Expression::evaluate`] computes the value of an expression built from numbers and the arithmetic operators of `VUhU`, and [`Expression::render`] writes an expression in conventional [notation](Notation).
+//!
+//! ```rust
+//! use sneturfahi::parse::ast::{self, BridiTail, Statement, SumtiKind, Term};
+//!
+//! let input = "li re su'i re du li vo";
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::parse_in(input, &arena).unwrap();
+//! let text = ast::lower(&cst, input);
+//!
+//! let Statement::Bridi(bridi) = &text.paragraphs[0].sentences[0].statement else {
+//! 	unreachable!();
+//! };
+//! let BridiTail::Selbri { terms, .. } = &bridi.tail else {
+//! 	unreachable!();
+//! };
+//! let value = |term: &Term| match term {
+//! 	Term::Sumti { sumti, .. } => match &sumti.kind {
+//! 		SumtiKind::Mekso(expression) => expression.evaluate().unwrap(),
+//! 		_ => unreachable!(),
+//! 	},
+//! 	_ => unreachable!(),
+//! };
+//! assert_eq!(value(&bridi.head[0]), value(&terms[0]));
+//! ```
+
+use super::{Connective, Conversion, Free, Numeral, Selbri, Sumti, Word};
+use crate::span::Span;
+
+mod evaluate;
+mod lower;
+mod render;
+
+pub use evaluate::{Error, ErrorKind, Value};
+pub use render::Notation;
+
+/// A mekso expression or operand.
+#[derive(Debug, Clone)]
+pub struct Expression {
+	/// What kind of expression it is.
+	pub kind: ExpressionKind,
+	/// The free modifiers in the expression.
+	pub frees: Vec<Free>,
+	/// The span of the expression in the text, including `vei` and `ve'o` if it is parenthesized.
+	pub span: Span,
+	/// The text of the expression as it is written.
+	pub text: String,
+}
+
+/// The kinds of [`Expression`].
+#[derive(Debug, Clone)]
+pub enum ExpressionKind {
+	/// A number or lerfu string, such as `re` or `xy`, which can be evaluated with [`number::evaluate`](super::number::evaluate).
+	Number(Numeral),
+	/// An operator applied to its operands, whichever notation it was written in.
+	/// Operators in infix and reverse-Polish notation always have two operands.
+	Apply {
+		/// The operator.
+		operator: Operator,
+		/// The operands, in order.
+		operands: Vec<Expression>,
+	},
+	/// A selbri used as an operand with `ni'e`.
+	Selbri(Selbri),
+	/// A sumti used as an operand with `mo'e`.
+	Sumti(Box<Sumti>),
+	/// A vector of operands made with `jo'i`.
+	Vector(Vec<Expression>),
+	/// An operand with a modifier such as `la'e`.
+	Modified {
+		/// The `LAhE` or `NAhE` word.
+		modifier: Word,
+		/// The modified operand.
+		operand: Box<Expression>,
+	},
+	/// Two operands joined by a connective.
+	Connected {
+		/// The connective.
+		connective: Connective,
+		/// The first operand.
+		left: Box<Expression>,
+		/// The second operand.
+		right: Box<Expression>,
+	},
+}
+
+/// A mekso operator.
+#[derive(Debug, Clone)]
+pub struct Operator {
+	/// The `SE` and `NAhE` words before the operator, outermost first.
+	pub conversions: Vec<Conversion>,
+	/// What kind of operator it is.
+	pub kind: OperatorKind,
+	/// The free modifiers in the operator.
+	pub frees: Vec<Free>,
+	/// The span of the operator in the text.
+	pub span: Span,
+	/// The text of the operator as it is written.
+	pub text: String,
+}
+
+/// The kinds of [`Operator`].
+#[derive(Debug, Clone)]
+pub enum OperatorKind {
+	/// A `VUhU` word, such as `su'i`.
+	Vuhu(Word),
+	/// An operator made from an expression with `ma'o`.
+	Expression(Box<Expression>),
+	/// An operator made from a selbri with `na'u`.
+	Selbri(Selbri),
+	/// Two operators joined by a connective.
+	Connected {
+		/// The connective.
+		connective: Connective,
+		/// The first operator.
+		left: Box<Operator>,
+		/// The second operator.
+		right: Box<Operator>,
+	},
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::parse::ast::lower::Lowerer;
+	use crate::parse::cst::node::NodeRef;
+
+	/// Lower the first mekso expression in `input`, for the tests of this module and its submodules.
+	pub(super) fn first_expression(input: &str) -> Expression {
+		let arena = crate::Arena::new();
+		let cst = crate::parse_in(input, &arena).unwrap();
+		let found =
+			crate::parse::select::select(cst.root(), cst.tokens(), input, "Expression").unwrap();
+		let NodeRef::Expression(expression) = found[0].node.downcast() else {
+			unreachable!();
+		};
+		Lowerer::new(input, cst.tokens()).mekso(expression)
+	}
+
+	/// Write an expression as an s-expression of the words in it.
+	fn write(expression: &Expression, input: &str) -> String {
+		let slice = |span: Span| span.slice(input).unwrap().to_owned();
+		match &expression.kind {
+			ExpressionKind::Number(number) => slice(number.span),
+			ExpressionKind::Apply { operator, operands } => {
+				let mut written = format!("({}", slice(operator.span));
+				for operand in operands {
+					written.push(' ');
+					written.push_str(&write(operand, input));
+				}
+				written + ")"
+			}
+			ExpressionKind::Connected {
+				connective,
+				left,
+				right,
+			} => format!(
+				"({} {} {})",
+				slice(connective.word.as_ref().unwrap().span),
+				write(left, input),
+				write(right, input)
+			),
+			_ => slice(expression.span),
+		}
+	}
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:literal) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				assert_eq!(write(&first_expression(input), input), $expected);
+			}
+		};
+	}
+
+	make_test!(infix, "li re su'i ci", "(su'i re ci)");
+	make_test!(forethought, "li pe'o su'i re boi ci ku'e", "(su'i re ci)");
+	make_test!(reverse_polish, "li fu'a re boi ci su'i", "(su'i re ci)");
+	make_test!(
+		left_grouping,
+		"li ci su'i vo pi'i mu",
+		"(pi'i (su'i ci vo) mu)"
+	);
+	make_test!(bihe, "li ci su'i vo bi'e pi'i mu", "(su'i ci (pi'i vo mu))");
+	make_test!(
+		bihe_right_grouping,
+		"li re bi'e te'a ci bi'e te'a re",
+		"(te'a re (te'a ci re))"
+	);
+	make_test!(
+		nested_reverse_polish,
+		"li fu'a re boi ci su'i vo pi'i",
+		"(pi'i (su'i re ci) vo)"
+	);
+	make_test!(
+		forethought_arity,
+		"li pe'o su'i re boi ci boi vo ku'e",
+		"(su'i re ci vo)"
+	);
+	make_test!(
+		parenthesized,
+		"li ci pi'i vei vo su'i mu ve'o",
+		"(pi'i ci (su'i vo mu))"
+	);
+	make_test!(conversion, "li ci se vu'u re", "(se vu'u ci re)");
+	make_test!(
+		selbri,
+		"li ni'e clani te'u su'i re",
+		"(su'i ni'e clani te'u re)"
+	);
+	make_test!(
+		connected_operands,
+		"li re boi a ci su'i re",
+		"(su'i (a re ci) re)"
+	);
+	make_test!(
+		grouped_operator,
+		"li re ke su'i ke'e ci",
+		"(ke su'i ke'e re ci)"
+	);
+}
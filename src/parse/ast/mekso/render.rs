@@ -0,0 +1,896 @@
+use std::fmt::Write as _;
+
+use super::{Expression, ExpressionKind, Operator, OperatorKind};
+use crate::parse::ast::number::{self, Quantity, Rational};
+use crate::parse::ast::{lerfu, Conversion, Numeral, NumeralWord};
+
+/// The notations that an [`Expression`] can be [rendered](Expression::render) in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+	/// Plain text, with ASCII operators and parentheses where they are needed, as in `(3 + 4) * 5`.
+	Plain,
+	/// LaTeX math mode, without the surrounding `$`, as in `\left(3 + 4\right) \times 5`.
+	Latex,
+	/// Presentation `MathML`, as a `<math>` element.
+	MathMl,
+}
+
+impl Expression {
+	/// Render the expression in conventional mathematical notation.
+	///
+	/// The arithmetic `VUhU` operators are written with their symbols, such as `+` for `su'i` and a superscript for `te'a`, and other operators are written as function applications, as in `ju'u(a, b)`.
+	/// Numbers are written as they are said, with `fi'u` as a fraction, `pi` as a decimal point, the digits after `ra'e` marked as repeating, and `ce'i` as a percent sign.
+	/// Lerfu strings are written as their [letters](crate::parse::ast::lerfu), and parentheses are added where the precedence of the symbols would otherwise group the expression differently.
+	///
+	/// ```rust
+	/// use sneturfahi::parse::ast::mekso::Notation;
+	/// use sneturfahi::parse::ast::{self, Statement, SumtiKind, Term};
+	///
+	/// let input = "li ci su'i vo pi'i mu cu namcu";
+	/// let arena = sneturfahi::Arena::new();
+	/// let cst = sneturfahi::parse_in(input, &arena).unwrap();
+	/// let text = ast::lower(&cst, input);
+	///
+	/// let Statement::Bridi(bridi) = &text.paragraphs[0].sentences[0].statement else {
+	/// 	unreachable!();
+	/// };
+	/// let Term::Sumti { sumti, .. } = &bridi.head[0] else {
+	/// 	unreachable!();
+	/// };
+	/// let SumtiKind::Mekso(expression) = &sumti.kind else {
+	/// 	unreachable!();
+	/// };
+	/// assert_eq!(expression.render(Notation::Plain), "(3 + 4) * 5");
+	/// assert_eq!(
+	/// 	expression.render(Notation::Latex),
+	/// 	r"\left(3 + 4\right) \times 5"
+	/// );
+	/// ```
+	#[must_use]
+	pub fn render(&self, notation: Notation) -> String {
+		let node = node(self);
+		let mut writer = Writer {
+			notation,
+			output: String::new(),
+		};
+		if notation == Notation::MathMl {
+			writer
+				.output
+				.push_str(r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#);
+			writer.node(&node);
+			writer.output.push_str("</math>");
+		} else {
+			writer.node(&node);
+		}
+		writer.output
+	}
+}
+
+/// An expression as conventional notation, before it is written in a particular [`Notation`].
+enum Node {
+	Number(Digits),
+	Identifier(String),
+	Text(String),
+	Infix(Infix, Vec<Node>),
+	Divide(Box<Node>, Box<Node>),
+	Power(Box<Node>, Box<Node>),
+	Negate(Box<Node>),
+	Approximate(Box<Node>),
+	Factorial(Box<Node>),
+	Absolute(Box<Node>),
+	Root(Box<Node>, Option<Box<Node>>),
+	Logarithm(Box<Node>, Box<Node>),
+	Function(String, Vec<Node>),
+	Tuple(Vec<Node>),
+}
+
+/// A number in digits, as in `-1.2(34)%` for `ni'u pa pi re ra'e ci vo ce'i`.
+struct Digits {
+	negative: bool,
+	/// The digits before the repeating ones, with the decimal point if there is one.
+	fixed: String,
+	/// The digits that repeat forever after `fixed`, which may be empty.
+	repeating: String,
+	percent: bool,
+}
+
+impl Digits {
+	fn new(fixed: String) -> Self {
+		Self {
+			negative: false,
+			fixed,
+			repeating: String::new(),
+			percent: false,
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Infix {
+	Add,
+	Subtract,
+	Multiply,
+	Ratio,
+}
+
+impl Infix {
+	fn precedence(self) -> u8 {
+		match self {
+			Self::Add | Self::Subtract => 1,
+			Self::Multiply | Self::Ratio => 2,
+		}
+	}
+
+	fn symbol(self, notation: Notation) -> &'static str {
+		match (self, notation) {
+			(Self::Add, _) => "+",
+			(Self::Subtract, Notation::MathMl) => "\u{2212}",
+			(Self::Subtract, _) => "-",
+			(Self::Multiply, Notation::Plain) => "*",
+			(Self::Multiply, Notation::Latex) => r"\times",
+			(Self::Multiply, Notation::MathMl) => "\u{d7}",
+			(Self::Ratio, _) => ":",
+		}
+	}
+}
+
+const PREFIX: u8 = 3;
+const POWER: u8 = 4;
+const POSTFIX: u8 = 5;
+const ATOM: u8 = 6;
+
+impl Node {
+	/// How tightly the node binds, from 1 for addition to [`ATOM`] for nodes that never need parentheses.
+	fn precedence(&self, notation: Notation) -> u8 {
+		match self {
+			Self::Number(number) if number.negative => PREFIX,
+			Self::Infix(infix, _) => infix.precedence(),
+			Self::Divide(..) if notation == Notation::Plain => Infix::Multiply.precedence(),
+			Self::Negate(_) | Self::Approximate(_) => PREFIX,
+			Self::Power(..) => POWER,
+			Self::Factorial(_) => POSTFIX,
+			_ => ATOM,
+		}
+	}
+}
+
+/// Turn an [`Expression`] into a [`Node`].
+fn node(expression: &Expression) -> Node {
+	match &expression.kind {
+		ExpressionKind::Number(numeral) => number(numeral),
+		ExpressionKind::Apply { operator, operands } => {
+			apply(operator, operands.iter().map(node).collect())
+		}
+		ExpressionKind::Selbri(_) | ExpressionKind::Sumti(_) => Node::Text(expression.text.clone()),
+		ExpressionKind::Vector(operands) => Node::Tuple(operands.iter().map(node).collect()),
+		ExpressionKind::Modified { modifier, operand } => {
+			Node::Function(modifier.text.clone(), vec![node(operand)])
+		}
+		ExpressionKind::Connected {
+			connective,
+			left,
+			right,
+		} => {
+			let name = connective
+				.word
+				.as_ref()
+				.map_or_else(String::new, |word| word.text.clone());
+			Node::Function(name, vec![node(left), node(right)])
+		}
+	}
+}
+
+fn number(numeral: &Numeral) -> Node {
+	let text = || words(numeral).join(" ");
+	match number::evaluate(numeral) {
+		Ok(value) => {
+			let node = match value.quantity {
+				Quantity::Exact(exact) => number::written(numeral).map_or_else(|| rational(exact), written),
+				Quantity::Indefinite { .. } => Node::Text(text()),
+			};
+			if value.approximate {
+				Node::Approximate(Box::new(node))
+			} else {
+				node
+			}
+		}
+		Err(number::Error::Lerfu(_)) => Node::Identifier(
+			lerfu::interpret(numeral).map_or_else(|_| text(), |letters| letters.to_string()),
+		),
+		Err(_) => match text().as_str() {
+			"pai" => Node::Identifier("\u{3c0}".to_owned()),
+			"te'o" => Node::Identifier("e".to_owned()),
+			"ka'o" => Node::Identifier("i".to_owned()),
+			text => Node::Text(text.to_owned()),
+		},
+	}
+}
+
+/// The text of the words of a numeral, including those of its lerfu.
+fn words(numeral: &Numeral) -> Vec<&str> {
+	let mut words = Vec::new();
+	for word in &numeral.words {
+		match word {
+			NumeralWord::Pa(word) => words.push(word.text.as_str()),
+			NumeralWord::Lerfu { lau, word, bu } => {
+				words.extend(
+					lau
+						.iter()
+						.chain([word])
+						.chain(bu)
+						.map(|word| word.text.as_str()),
+				);
+			}
+			NumeralWord::Composite { tei, inner, foi } => {
+				words.push(tei.text.as_str());
+				words.extend(self::words(inner));
+				words.push(foi.text.as_str());
+			}
+		}
+	}
+	words
+}
+
+fn apply(operator: &Operator, mut operands: Vec<Node>) -> Node {
+	let function = |operands| Node::Function(operator.text.clone(), operands);
+	let OperatorKind::Vuhu(word) = &operator.kind else {
+		return function(operands);
+	};
+	// conversions that cannot be shown by reordering the operands make the operator a function
+	let mut places = Vec::new();
+	for conversion in &operator.conversions {
+		let place = match conversion {
+			Conversion::Se(se) => match se.text.as_str() {
+				"se" => 1,
+				"te" => 2,
+				"ve" => 3,
+				"xe" => 4,
+				_ => return function(operands),
+			},
+			_ => return function(operands),
+		};
+		if place >= operands.len() {
+			return function(operands);
+		}
+		places.push(place);
+	}
+	// the outermost conversion applies first, as in evaluation
+	for place in places {
+		operands.swap(0, place);
+	}
+	vuhu(&word.text, operands)
+}
+
+/// Write a number with the digits and fraction bar that it was said with.
+fn written(written: number::Written) -> Node {
+	let numerator = written
+		.numerator
+		.map_or_else(|| Node::Number(Digits::new("1".to_owned())), part);
+	match written.denominator {
+		Some(denominator) => Node::Divide(Box::new(numerator), Box::new(part(denominator))),
+		None => numerator,
+	}
+}
+
+/// Write one side of a fraction with the digits that it was said with.
+fn part(part: number::Part) -> Node {
+	let number::Part {
+		negative,
+		integer,
+		fraction,
+		repeating,
+		percent,
+		..
+	} = part;
+	let text = |digits: Vec<u8>| {
+		digits
+			.into_iter()
+			.map(|digit| char::from(b'0' + digit))
+			.collect::<String>()
+	};
+	let mut fixed = if integer.is_empty() {
+		"0".to_owned()
+	} else {
+		text(integer)
+	};
+	if let Some(fraction) = fraction {
+		fixed.push('.');
+		fixed.push_str(&text(fraction));
+	}
+	Node::Number(Digits {
+		negative,
+		fixed,
+		repeating: repeating.map(text).unwrap_or_default(),
+		percent,
+	})
+}
+
+/// Write a rational as a decimal if it has one that ends, and as a fraction otherwise.
+fn rational(value: Rational) -> Node {
+	let negative = value.numerator() < 0;
+	let numerator = value.numerator().unsigned_abs();
+	let denominator = value.denominator().unsigned_abs();
+	let mut digits = 0;
+	let mut scaled = Some(numerator);
+	while let Some(current) = scaled {
+		if current % denominator == 0 {
+			break;
+		}
+		digits += 1;
+		scaled = current.checked_mul(10).filter(|_| digits <= 38);
+	}
+	let node = if let Some(scaled) = scaled {
+		let whole = (scaled / denominator).to_string();
+		if digits == 0 {
+			Node::Number(Digits::new(whole))
+		} else {
+			let whole = format!("{whole:0>width$}", width = digits + 1);
+			let (integer, fraction) = whole.split_at(whole.len() - digits);
+			Node::Number(Digits::new(format!("{integer}.{fraction}")))
+		}
+	} else {
+		Node::Divide(
+			Box::new(Node::Number(Digits::new(numerator.to_string()))),
+			Box::new(Node::Number(Digits::new(denominator.to_string()))),
+		)
+	};
+	match node {
+		Node::Number(number) if negative => Node::Number(Digits {
+			negative: true,
+			..number
+		}),
+		node if negative => Node::Negate(Box::new(node)),
+		node => node,
+	}
+}
+
+/// Apply the `VUhU` operator `word` to `operands`.
+fn vuhu(word: &str, mut operands: Vec<Node>) -> Node {
+	let last = |operands: &mut Vec<Node>| Box::new(operands.pop().expect("the arity was checked"));
+	match (word, operands.len()) {
+		("su'i", 2..) => Node::Infix(Infix::Add, operands),
+		("vu'u", 2..) => Node::Infix(Infix::Subtract, operands),
+		("pi'i", 2..) => Node::Infix(Infix::Multiply, operands),
+		("pa'i", 2) => Node::Infix(Infix::Ratio, operands),
+		("fe'i", 2..) => {
+			let mut operands = operands.into_iter();
+			let first = operands.next().expect("the arity was checked");
+			operands.fold(first, |quotient, next| {
+				Node::Divide(Box::new(quotient), Box::new(next))
+			})
+		}
+		("te'a", 2) => {
+			let exponent = last(&mut operands);
+			Node::Power(last(&mut operands), exponent)
+		}
+		("fe'a", 1) => Node::Root(last(&mut operands), None),
+		("fe'a", 2) => {
+			let index = last(&mut operands);
+			Node::Root(last(&mut operands), Some(index))
+		}
+		("de'o", 2) => {
+			let base = last(&mut operands);
+			Node::Logarithm(last(&mut operands), base)
+		}
+		("va'a", 1) => Node::Negate(last(&mut operands)),
+		("fa'i", 1) => Node::Divide(
+			Box::new(Node::Number(Digits::new("1".to_owned()))),
+			last(&mut operands),
+		),
+		("cu'a", 1) => Node::Absolute(last(&mut operands)),
+		("ne'o", 1) => Node::Factorial(last(&mut operands)),
+		_ => Node::Function(word.to_owned(), operands),
+	}
+}
+
+/// Writes [`Node`]s in a [`Notation`].
+struct Writer {
+	notation: Notation,
+	output: String,
+}
+
+impl Writer {
+	fn node(&mut self, node: &Node) {
+		match self.notation {
+			Notation::Plain => self.plain(node),
+			Notation::Latex => self.latex(node),
+			Notation::MathMl => self.mathml(node),
+		}
+	}
+
+	/// Write `node` in parentheses if it binds less tightly than `precedence`.
+	fn operand(&mut self, node: &Node, precedence: u8) {
+		let parenthesize = node.precedence(self.notation) < precedence;
+		self.grouped(node, parenthesize);
+	}
+
+	fn grouped(&mut self, node: &Node, parenthesize: bool) {
+		if parenthesize {
+			self.parenthesized(|writer| writer.node(node));
+		} else {
+			self.node(node);
+		}
+	}
+
+	fn parenthesized(&mut self, inner: impl FnOnce(&mut Self)) {
+		let (open, close) = match self.notation {
+			Notation::Plain => ("(", ")"),
+			Notation::Latex => (r"\left(", r"\right)"),
+			Notation::MathMl => ("<mrow><mo>(</mo>", "<mo>)</mo></mrow>"),
+		};
+		self.output.push_str(open);
+		inner(self);
+		self.output.push_str(close);
+	}
+
+	/// Write the operands of an infix operator, which groups from the left, so that later operands need parentheses even for operators of the same precedence, and for a sign, as in `3 + (-2)`.
+	fn infix(&mut self, operands: &[Node], precedence: u8, mut separator: impl FnMut(&mut Self)) {
+		for (index, operand) in operands.iter().enumerate() {
+			if index == 0 {
+				self.operand(operand, precedence);
+			} else {
+				separator(self);
+				let binds = operand.precedence(self.notation);
+				self.grouped(operand, binds <= precedence || binds == PREFIX);
+			}
+		}
+	}
+
+	fn arguments(&mut self, arguments: &[Node], separator: &str) {
+		for (index, argument) in arguments.iter().enumerate() {
+			if index > 0 {
+				self.output.push_str(separator);
+			}
+			self.node(argument);
+		}
+	}
+
+	fn plain(&mut self, node: &Node) {
+		match node {
+			Node::Number(number) => {
+				if number.negative {
+					self.output.push('-');
+				}
+				self.output.push_str(&number.fixed);
+				if !number.repeating.is_empty() {
+					write!(self.output, "({})", number.repeating).unwrap();
+				}
+				if number.percent {
+					self.output.push('%');
+				}
+			}
+			Node::Identifier(text) | Node::Text(text) => self.output.push_str(text),
+			Node::Infix(infix, operands) => {
+				let symbol = infix.symbol(Notation::Plain);
+				self.infix(operands, infix.precedence(), |writer| {
+					write!(writer.output, " {symbol} ").unwrap();
+				});
+			}
+			Node::Divide(numerator, denominator) => {
+				let precedence = Infix::Multiply.precedence();
+				self.operand(numerator, precedence);
+				self.output.push_str(" / ");
+				self.operand(denominator, POWER);
+			}
+			Node::Power(base, exponent) => {
+				self.operand(base, POWER + 1);
+				self.output.push('^');
+				self.operand(exponent, POWER);
+			}
+			Node::Negate(operand) => {
+				self.output.push('-');
+				self.operand(operand, POWER);
+			}
+			Node::Approximate(operand) => {
+				self.output.push('~');
+				self.operand(operand, POWER);
+			}
+			Node::Factorial(operand) => {
+				self.operand(operand, ATOM);
+				self.output.push('!');
+			}
+			Node::Absolute(operand) => {
+				self.output.push('|');
+				self.node(operand);
+				self.output.push('|');
+			}
+			Node::Root(radicand, None) => {
+				self.output.push_str("sqrt");
+				self.parenthesized(|writer| writer.node(radicand));
+			}
+			Node::Root(radicand, Some(index)) => {
+				self.output.push_str("root");
+				self.parenthesized(|writer| {
+					writer.node(radicand);
+					writer.output.push_str(", ");
+					writer.node(index);
+				});
+			}
+			Node::Logarithm(argument, base) => {
+				self.output.push_str("log");
+				self.parenthesized(|writer| {
+					writer.node(argument);
+					writer.output.push_str(", ");
+					writer.node(base);
+				});
+			}
+			Node::Function(name, arguments) => {
+				self.output.push_str(name);
+				self.parenthesized(|writer| writer.arguments(arguments, ", "));
+			}
+			Node::Tuple(operands) => self.parenthesized(|writer| writer.arguments(operands, ", ")),
+		}
+	}
+
+	fn latex(&mut self, node: &Node) {
+		match node {
+			Node::Number(number) => {
+				if number.negative {
+					self.output.push('-');
+				}
+				self.output.push_str(&number.fixed);
+				if !number.repeating.is_empty() {
+					write!(self.output, r"\overline{{{}}}", number.repeating).unwrap();
+				}
+				if number.percent {
+					self.output.push_str(r"\%");
+				}
+			}
+			Node::Identifier(text) => self.output.push_str(text),
+			Node::Text(text) => {
+				write!(self.output, r"\text{{{}}}", latex_escape(text)).unwrap();
+			}
+			Node::Infix(infix, operands) => {
+				let symbol = infix.symbol(Notation::Latex);
+				self.infix(operands, infix.precedence(), |writer| {
+					write!(writer.output, " {symbol} ").unwrap();
+				});
+			}
+			Node::Divide(numerator, denominator) => {
+				self.output.push_str(r"\frac{");
+				self.node(numerator);
+				self.output.push_str("}{");
+				self.node(denominator);
+				self.output.push('}');
+			}
+			Node::Power(base, exponent) => {
+				self.output.push('{');
+				self.operand(base, POWER + 1);
+				self.output.push_str("}^{");
+				self.node(exponent);
+				self.output.push('}');
+			}
+			Node::Negate(operand) => {
+				self.output.push('-');
+				self.operand(operand, POWER);
+			}
+			Node::Approximate(operand) => {
+				self.output.push_str(r"\approx ");
+				self.operand(operand, POWER);
+			}
+			Node::Factorial(operand) => {
+				self.operand(operand, ATOM);
+				self.output.push('!');
+			}
+			Node::Absolute(operand) => {
+				self.output.push_str(r"\left|");
+				self.node(operand);
+				self.output.push_str(r"\right|");
+			}
+			Node::Root(radicand, index) => {
+				self.output.push_str(r"\sqrt");
+				if let Some(index) = index {
+					self.output.push('[');
+					self.node(index);
+					self.output.push(']');
+				}
+				self.output.push('{');
+				self.node(radicand);
+				self.output.push('}');
+			}
+			Node::Logarithm(argument, base) => {
+				self.output.push_str(r"\log_{");
+				self.node(base);
+				self.output.push('}');
+				self.parenthesized(|writer| writer.node(argument));
+			}
+			Node::Function(name, arguments) => {
+				write!(
+					self.output,
+					r"\operatorname{{\text{{{}}}}}",
+					latex_escape(name)
+				)
+				.unwrap();
+				self.parenthesized(|writer| writer.arguments(arguments, ", "));
+			}
+			Node::Tuple(operands) => self.parenthesized(|writer| writer.arguments(operands, ", ")),
+		}
+	}
+
+	fn mathml(&mut self, node: &Node) {
+		let element = |name: &str, text: &str| format!("<{name}>{}</{name}>", xml_escape(text));
+		match node {
+			Node::Number(number) => {
+				let mut elements = Vec::new();
+				if number.negative {
+					elements.push(element("mo", "\u{2212}"));
+				}
+				elements.push(element("mn", &number.fixed));
+				if !number.repeating.is_empty() {
+					elements.push(format!(
+						"<mover accent=\"true\">{}<mo>\u{af}</mo></mover>",
+						element("mn", &number.repeating)
+					));
+				}
+				if number.percent {
+					elements.push(element("mo", "%"));
+				}
+				if let [element] = elements.as_slice() {
+					self.output.push_str(element);
+				} else {
+					write!(self.output, "<mrow>{}</mrow>", elements.concat()).unwrap();
+				}
+			}
+			Node::Identifier(text) => self.output.push_str(&element("mi", text)),
+			Node::Text(text) => self.output.push_str(&element("mtext", text)),
+			Node::Infix(infix, operands) => {
+				let symbol = element("mo", infix.symbol(Notation::MathMl));
+				self.output.push_str("<mrow>");
+				self.infix(operands, infix.precedence(), |writer| {
+					writer.output.push_str(&symbol);
+				});
+				self.output.push_str("</mrow>");
+			}
+			Node::Divide(numerator, denominator) => {
+				self.output.push_str("<mfrac><mrow>");
+				self.node(numerator);
+				self.output.push_str("</mrow><mrow>");
+				self.node(denominator);
+				self.output.push_str("</mrow></mfrac>");
+			}
+			Node::Power(base, exponent) => {
+				self.output.push_str("<msup><mrow>");
+				self.operand(base, POWER + 1);
+				self.output.push_str("</mrow><mrow>");
+				self.node(exponent);
+				self.output.push_str("</mrow></msup>");
+			}
+			Node::Negate(operand) | Node::Approximate(operand) => {
+				let symbol = if matches!(node, Node::Negate(_)) {
+					"\u{2212}"
+				} else {
+					"\u{2248}"
+				};
+				write!(self.output, "<mrow><mo>{symbol}</mo>").unwrap();
+				self.operand(operand, POWER);
+				self.output.push_str("</mrow>");
+			}
+			Node::Factorial(operand) => {
+				self.output.push_str("<mrow>");
+				self.operand(operand, ATOM);
+				self.output.push_str("<mo>!</mo></mrow>");
+			}
+			Node::Absolute(operand) => {
+				self.output.push_str("<mrow><mo>|</mo>");
+				self.node(operand);
+				self.output.push_str("<mo>|</mo></mrow>");
+			}
+			Node::Root(radicand, None) => {
+				self.output.push_str("<msqrt>");
+				self.node(radicand);
+				self.output.push_str("</msqrt>");
+			}
+			Node::Root(radicand, Some(index)) => {
+				self.output.push_str("<mroot><mrow>");
+				self.node(radicand);
+				self.output.push_str("</mrow><mrow>");
+				self.node(index);
+				self.output.push_str("</mrow></mroot>");
+			}
+			Node::Logarithm(argument, base) => {
+				self.output.push_str("<mrow><msub><mi>log</mi><mrow>");
+				self.node(base);
+				self.output.push_str("</mrow></msub><mo>\u{2061}</mo>");
+				self.parenthesized(|writer| writer.node(argument));
+				self.output.push_str("</mrow>");
+			}
+			Node::Function(name, arguments) => {
+				write!(
+					self.output,
+					"<mrow>{}<mo>\u{2061}</mo>",
+					element("mi", name)
+				)
+				.unwrap();
+				self.parenthesized(|writer| writer.arguments(arguments, "<mo>,</mo>"));
+				self.output.push_str("</mrow>");
+			}
+			Node::Tuple(operands) => {
+				self.parenthesized(|writer| writer.arguments(operands, "<mo>,</mo>"));
+			}
+		}
+	}
+}
+
+fn latex_escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for letter in text.chars() {
+		match letter {
+			'\\' => escaped.push_str(r"\textbackslash{}"),
+			'{' | '}' | '%' | '&' | '#' | '_' | '$' => {
+				escaped.push('\\');
+				escaped.push(letter);
+			}
+			'^' | '~' => write!(escaped, r"\{letter}{{}}").unwrap(),
+			other => escaped.push(other),
+		}
+	}
+	escaped
+}
+
+fn xml_escape(text: &str) -> String {
+	text
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::parse::ast::mekso::test::first_expression;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $notation:ident, $expected:literal) => {
+			#[test]
+			fn $name() {
+				assert_eq!(
+					first_expression($input).render(Notation::$notation),
+					$expected
+				);
+			}
+		};
+	}
+
+	make_test!(plain_sum, "li re su'i ci", Plain, "2 + 3");
+	make_test!(
+		plain_grouping,
+		"li ci su'i vo pi'i mu",
+		Plain,
+		"(3 + 4) * 5"
+	);
+	make_test!(plain_bihe, "li ci su'i vo bi'e pi'i mu", Plain, "3 + 4 * 5");
+	make_test!(
+		plain_left_operand_of_subtraction,
+		"li vei ci vu'u vo ve'o vu'u mu",
+		Plain,
+		"3 - 4 - 5"
+	);
+	make_test!(
+		plain_right_operand_of_subtraction,
+		"li ci vu'u vei vo vu'u mu ve'o",
+		Plain,
+		"3 - (4 - 5)"
+	);
+	make_test!(
+		plain_power_groups_right,
+		"li re bi'e te'a ci bi'e te'a re",
+		Plain,
+		"2^3^2"
+	);
+	make_test!(
+		plain_power_of_power,
+		"li vei re te'a ci ve'o te'a re",
+		Plain,
+		"(2^3)^2"
+	);
+	make_test!(plain_negative_base, "li ni'u re te'a re", Plain, "(-2)^2");
+	make_test!(
+		plain_negative_operand,
+		"li ci su'i ni'u re",
+		Plain,
+		"3 + (-2)"
+	);
+	make_test!(plain_decimal, "li pa pi mu pi'i re", Plain, "1.5 * 2");
+	make_test!(plain_fraction, "li pa fi'u ci", Plain, "1 / 3");
+	make_test!(plain_fraction_as_written, "li re fi'u vo", Plain, "2 / 4");
+	make_test!(
+		plain_fraction_without_numerator,
+		"li fi'u re",
+		Plain,
+		"1 / 2"
+	);
+	make_test!(plain_repeating, "li pa pi ci ra'e ci", Plain, "1.3(3)");
+	make_test!(
+		plain_leading_repeating,
+		"li ni'u pi ra'e ci",
+		Plain,
+		"-0.(3)"
+	);
+	make_test!(plain_percent, "li remu ce'i", Plain, "25%");
+	make_test!(plain_thousands, "li pa ki'o re", Plain, "1002");
+	make_test!(plain_approximate, "li ji'i pa pi mu", Plain, "~1.5");
+	make_test!(plain_conversion, "li ci se vu'u re", Plain, "2 - 3");
+	make_test!(
+		plain_forethought,
+		"li pe'o su'i re boi ci boi vo ku'e",
+		Plain,
+		"2 + 3 + 4"
+	);
+	make_test!(plain_root, "li pe'o fe'a re ku'e", Plain, "sqrt(2)");
+	make_test!(plain_factorial, "li pe'o ne'o mu ku'e", Plain, "5!");
+	make_test!(plain_constant, "li pai pi'i re", Plain, "\u{3c0} * 2");
+	make_test!(
+		plain_unknown_operator,
+		"li re ju'u pa no",
+		Plain,
+		"ju'u(2, 10)"
+	);
+	make_test!(plain_lerfu, "li xy su'i pa", Plain, "x + 1");
+	make_test!(
+		plain_greek_lerfu,
+		"li ge'o py pi'i re",
+		Plain,
+		"\u{3c0} * 2"
+	);
+	make_test!(
+		latex_grouping,
+		"li ci su'i vo pi'i mu",
+		Latex,
+		r"\left(3 + 4\right) \times 5"
+	);
+	make_test!(
+		latex_fraction,
+		"li ci su'i vo fe'i mu",
+		Latex,
+		r"\frac{3 + 4}{5}"
+	);
+	make_test!(
+		latex_fraction_number,
+		"li pa fi'u re",
+		Latex,
+		r"\frac{1}{2}"
+	);
+	make_test!(
+		latex_repeating,
+		"li pi re ra'e ci vo",
+		Latex,
+		r"0.2\overline{34}"
+	);
+	make_test!(latex_percent, "li remu ce'i", Latex, r"25\%");
+	make_test!(
+		latex_power,
+		"li re bi'e te'a ci bi'e su'i pa",
+		Latex,
+		r"{2}^{3 + 1}"
+	);
+	make_test!(
+		latex_unknown_operator,
+		"li re ju'u pa no",
+		Latex,
+		r"\operatorname{\text{ju'u}}\left(2, 10\right)"
+	);
+	make_test!(
+		mathml_sum,
+		"li re su'i ci",
+		MathMl,
+		r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mrow><mn>2</mn><mo>+</mo><mn>3</mn></mrow></math>"#
+	);
+	make_test!(
+		mathml_fraction_number,
+		"li pa fi'u re",
+		MathMl,
+		r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mfrac><mrow><mn>1</mn></mrow><mrow><mn>2</mn></mrow></mfrac></math>"#
+	);
+	make_test!(
+		mathml_repeating,
+		"li ni'u pi ra'e ci",
+		MathMl,
+		"<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow><mo>\u{2212}</mo><mn>0.</mn><mover accent=\"true\"><mn>3</mn><mo>\u{af}</mo></mover></mrow></math>"
+	);
+	make_test!(
+		mathml_power,
+		"li re te'a ci",
+		MathMl,
+		r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><msup><mrow><mn>2</mn></mrow><mrow><mn>3</mn></mrow></msup></math>"#
+	);
+}
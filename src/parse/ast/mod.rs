@@ -0,0 +1,1040 @@
+//! A semantic tree lowered from the [`Cst`], for analyses that care about what a text means rather than how it was parsed.
+//!
+//! The CST mirrors the grammar, with rules like `SentenceTail1` through `SentenceTail4` that exist only to encode precedence, and with free modifiers wherever the grammar allows them.
+//! The AST instead has one node per meaningful construct:
+//! a [`Bridi`] has head terms, a selbri and tail terms; a [`Sumti`] is a description, name, quote or other [kind](SumtiKind) with its quantifier and relative clauses;
+//! and connected constructs are binary nodes with an explicit [`Connective`].
+//! Free modifiers attach to the smallest enclosing node with a `frees` field, and indicators attach to the [`Word`] that they follow.
+//!
+//! Parts that have their own interpretation are lowered with their words: numbers and lerfu strings into [`Numeral`]s, and mekso into [`mekso::Expression`]s.
+//! The [`number`], [`mekso`] and [`lerfu`] modules interpret them, and [`places`] works out which sumti fill which places of each bridi.
+//!
+//! ```rust
+//! use sneturfahi::parse::ast::{self, BridiTail, Statement, SumtiKind, TanruUnitKind, Term};
+//!
+//! let input = "mi klama lo zdani";
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::parse_in(input, &arena).unwrap();
+//! let text = ast::lower(&cst, input);
+//!
+//! let Statement::Bridi(bridi) = &text.paragraphs[0].sentences[0].statement else {
+//! 	unreachable!();
+//! };
+//! assert!(matches!(&bridi.head[..], [Term::Sumti { place: None, sumti }] if matches!(sumti.kind, SumtiKind::ProSumti(_))));
+//! let BridiTail::Selbri { selbri, terms } = &bridi.tail else {
+//! 	unreachable!();
+//! };
+//! assert_eq!(selbri.span.slice(input), Some("klama"));
+//! assert!(matches!(&terms[..], [Term::Sumti { sumti, .. }] if matches!(sumti.kind, SumtiKind::Description(_))));
+//! ```
+//!
+//! [`Cst`]: crate::Cst
+
+#![allow(clippy::large_enum_variant)] // the AST is built once and then read, so keeping nodes inline is simpler than boxing them for size
+
+use crate::lex::Selmaho;
+use crate::span::Span;
+use crate::Cst;
+
+pub mod lerfu;
+mod lower;
+pub mod mekso;
+pub mod number;
+pub mod places;
+
+/// Lower the CST of an entire text into its AST.
+///
+/// `input` must be the text that `cst` was parsed from, because some distinctions, such as which logical connective was used, depend on the words themselves rather than their selmaho.
+#[must_use]
+pub fn lower(cst: &Cst<'_>, input: &str) -> Text {
+	lower::Lowerer::new(input, cst.tokens()).text(cst.root())
+}
+
+/// The text of the word at `span`, normalized so that it can be compared with the dictionary form of a cmavo.
+fn word_text(span: Span, input: &str) -> String {
+	span
+		.slice(input)
+		.unwrap_or_default()
+		.chars()
+		.filter(|&letter| letter != ',')
+		.map(|letter| match letter.to_ascii_lowercase() {
+			'h' => '\'',
+			other => other,
+		})
+		.collect()
+}
+
+/// A word of the text, with the indicators that follow it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+	/// The selmaho of the word.
+	pub selmaho: Selmaho,
+	/// The text of the word, in lower case, without commas and with `'` for `h`, so that it can be compared with the dictionary form of a cmavo.
+	pub text: String,
+	/// The span of the word in the text.
+	pub span: Span,
+	/// The indicators that follow the word and modify it.
+	pub indicators: Vec<Indicator>,
+}
+
+/// An attitudinal or other indicator, with the `CAI` and `NAI` words that modify it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Indicator {
+	/// The indicator itself, such as `ui`, `y` or `fu'o`.
+	pub word: Word,
+	/// The words that modify the indicator, such as `cai` or `nai`.
+	pub modifiers: Vec<Word>,
+}
+
+/// An entire text.
+#[derive(Debug, Clone)]
+pub struct Text {
+	/// The paragraphs of the text, which are separated by `ni'o`.
+	pub paragraphs: Vec<Paragraph>,
+	/// The indicators at the start of the text, which apply to all of it.
+	pub indicators: Vec<Indicator>,
+	/// The free modifiers at the start of the text.
+	pub frees: Vec<Free>,
+}
+
+/// A paragraph of a text.
+#[derive(Debug, Clone)]
+pub struct Paragraph {
+	/// The `ni'o` words before the paragraph, with their indicators.
+	pub niho: Vec<Word>,
+	/// The sentences of the paragraph.
+	pub sentences: Vec<Sentence>,
+	/// The free modifiers after the `ni'o` words.
+	pub frees: Vec<Free>,
+}
+
+/// A statement of a paragraph with the `.i` before it.
+#[derive(Debug, Clone)]
+pub struct Sentence {
+	/// The `.i` before the statement, with its indicators, or `None` if the statement starts its paragraph without one.
+	pub i: Option<Word>,
+	/// The statement.
+	pub statement: Statement,
+	/// The free modifiers after the `.i` and those of parts of the statement that have no `frees` field of their own.
+	pub frees: Vec<Free>,
+}
+
+/// A statement, which is usually a single bridi.
+#[derive(Debug, Clone)]
+pub enum Statement {
+	/// A single bridi.
+	Bridi(Bridi),
+	/// Two statements joined by a connective, as in `.i je` or `.i ba bo`.
+	///
+	/// Connectives without `bo` group from left to right, and connectives with `bo` bind more tightly and group from right to left.
+	Connected {
+		/// The `.i` before the connective, with its indicators.
+		i: Word,
+		/// The connective that joins the statements.
+		connective: Connective,
+		/// The first statement.
+		left: Box<Statement>,
+		/// The second statement.
+		right: Box<Statement>,
+	},
+	/// Paragraphs grouped with `tu'e` and `tu'u`.
+	Group {
+		/// The tag that applies to the whole group.
+		tag: Option<Tag>,
+		/// The grouped paragraphs.
+		paragraphs: Vec<Paragraph>,
+		/// The free modifiers of the group.
+		frees: Vec<Free>,
+	},
+	/// A statement with a prenex, whose terms are in scope for all of the statement.
+	Prenex {
+		/// The terms before `zo'u`.
+		terms: Vec<Term>,
+		/// The statement after `zo'u`.
+		body: Box<Statement>,
+		/// The free modifiers after `zo'u`.
+		frees: Vec<Free>,
+	},
+	/// A statement that is not a complete bridi, such as the answer to a question.
+	Fragment(Fragment),
+}
+
+/// A statement that is not a complete bridi.
+#[derive(Debug, Clone)]
+pub enum Fragment {
+	/// Terms without a selbri, such as `lo mlatu` in answer to `ma`.
+	Terms(Vec<Term>),
+	/// A connective, in answer to a connective question.
+	Connective(Connective),
+	/// A number, in answer to a number question.
+	Number(Quantifier),
+	/// A negation, in answer to a yes-no question.
+	Negation(Word),
+}
+
+/// A bridi: a selbri and the terms that fill its places or otherwise modify it.
+#[derive(Debug, Clone)]
+pub struct Bridi {
+	/// The terms before the selbri, which are shared by every selbri in a connected [tail](Self::tail).
+	pub head: Vec<Term>,
+	/// The selbri and the terms after it.
+	pub tail: BridiTail,
+	/// The free modifiers of the bridi, such as those after `cu` or `vau`.
+	pub frees: Vec<Free>,
+	/// The span of the entire bridi.
+	pub span: Span,
+}
+
+/// The selbri of a [`Bridi`] with the terms that follow it, which may be several selbri joined by `GIhA` connectives.
+#[derive(Debug, Clone)]
+pub enum BridiTail {
+	/// A single selbri.
+	Selbri {
+		/// The selbri.
+		selbri: Selbri,
+		/// The terms after the selbri.
+		terms: Vec<Term>,
+	},
+	/// Two tails joined by a `GIhA` connective, as in `mi klama gi'e citka`.
+	Connected {
+		/// The connective that joins the tails.
+		connective: Connective,
+		/// The first tail.
+		left: Box<BridiTail>,
+		/// The second tail.
+		right: Box<BridiTail>,
+		/// The terms after both tails, which are shared by both.
+		terms: Vec<Term>,
+	},
+	/// Two bridi joined by a forethought connective, as in `ge mi klama gi do citka`.
+	Forethought {
+		/// The `na` words that negate the whole connection.
+		negations: Vec<Word>,
+		/// The tag that applies to the whole connection, as in `pu ke ge ... ke'e`.
+		tag: Option<Tag>,
+		/// The connective that joins the bridi.
+		connective: Connective,
+		/// The first bridi.
+		first: Box<Statement>,
+		/// The second bridi.
+		second: Box<Statement>,
+		/// The terms after both bridi, which are shared by both.
+		terms: Vec<Term>,
+	},
+}
+
+/// A term: something that fills a place of a bridi or otherwise modifies it.
+#[derive(Debug, Clone)]
+pub enum Term {
+	/// A sumti, possibly with a `FA` tag that says which place it fills.
+	Sumti {
+		/// The `FA` word that explicitly selects the place, if any.
+		place: Option<Word>,
+		/// The sumti.
+		sumti: Sumti,
+	},
+	/// A tense or modal tag with the sumti that it applies to, or `None` if it is used alone or with `ku`.
+	Tagged {
+		/// The tag.
+		tag: Tag,
+		/// The sumti, if any.
+		sumti: Option<Sumti>,
+	},
+	/// `na ku`, which negates the rest of the bridi.
+	Negation(Word),
+	/// Terms grouped with `nu'i` or `ce'e` that act as one.
+	Termset(Vec<Term>),
+	/// Two terms joined by a connective.
+	Connected {
+		/// The connective that joins the terms.
+		connective: Connective,
+		/// The first term.
+		left: Box<Term>,
+		/// The second term.
+		right: Box<Term>,
+	},
+}
+
+/// A sumti.
+#[derive(Debug, Clone)]
+pub struct Sumti {
+	/// The quantifier outside of the sumti, as in `ro lo prenu` or `re mi`.
+	pub quantifier: Option<Quantifier>,
+	/// What kind of sumti this is.
+	pub kind: SumtiKind,
+	/// The relative clauses that modify the whole sumti.
+	pub relative_clauses: Vec<RelativeClause>,
+	/// The free modifiers of the sumti.
+	pub frees: Vec<Free>,
+	/// The span of the entire sumti.
+	pub span: Span,
+}
+
+/// The kinds of [`Sumti`].
+#[derive(Debug, Clone)]
+pub enum SumtiKind {
+	/// A pro-sumti such as `mi` or `ko'a`.
+	ProSumti(Word),
+	/// A description such as `lo zdani` or `re gerku`.
+	Description(Box<Description>),
+	/// A name such as `la djan`.
+	Name {
+		/// The `LA` word.
+		la: Word,
+		/// The cmevla of the name.
+		names: Vec<Word>,
+	},
+	/// A quote.
+	Quote(Quote),
+	/// A string of letters such as `by cy`, usually used as a pro-sumti, which can be read with [`lerfu::interpret`].
+	Lerfu(Numeral),
+	/// A mekso expression introduced by `li`.
+	Mekso(mekso::Expression),
+	/// A sumti modified by a `LAhE` word such as `la'e` or `lu'i`, or by `NAhE bo`.
+	Modified {
+		/// The modifying word.
+		modifier: Word,
+		/// The modified sumti.
+		sumti: Box<Sumti>,
+	},
+	/// Two sumti joined by a connective, as in `mi .e do` or `ge mi gi do`.
+	Connected {
+		/// The connective that joins the sumti.
+		connective: Connective,
+		/// The first sumti.
+		left: Box<Sumti>,
+		/// The second sumti.
+		right: Box<Sumti>,
+	},
+}
+
+/// A description: a selbri or sumti turned into a sumti by a descriptor.
+#[derive(Debug, Clone)]
+pub struct Description {
+	/// The descriptor, such as `lo` or `le`, or `None` for the shorthand `re gerku` for `re lo gerku`.
+	pub descriptor: Option<Word>,
+	/// The possessor, as in `le mi gerku`.
+	pub possessor: Option<Sumti>,
+	/// The quantifier inside of the description, as in `lo re gerku`.
+	pub quantifier: Option<Quantifier>,
+	/// The selbri or sumti that is described.
+	pub inner: DescriptionInner,
+	/// The relative clauses inside of the description, as in `lo poi ke'a gerku` or `lo gerku poi ke'a barda ku`.
+	pub relative_clauses: Vec<RelativeClause>,
+}
+
+/// What a [`Description`] describes.
+#[derive(Debug, Clone)]
+pub enum DescriptionInner {
+	/// A selbri, as in `lo gerku`.
+	Selbri(Selbri),
+	/// A quantified sumti, as in `lo re mi`.
+	Sumti(Sumti),
+}
+
+/// A quote.
+#[derive(Debug, Clone)]
+pub enum Quote {
+	/// A grammatical text quoted with `lu` and `li'u`.
+	Text(Box<Text>),
+	/// Lojban words quoted with `lo'u` and `le'u`, which need not be grammatical, with the span of the quoted words.
+	Words(Option<Span>),
+	/// A single word quoted with `zo`.
+	Word(Span),
+	/// Non-Lojban text quoted with `zoi` or `la'o`, with the span of the text between the delimiters.
+	NonLojban(Span),
+}
+
+/// A number or mekso used as a quantifier.
+#[derive(Debug, Clone)]
+pub enum Quantifier {
+	/// A number, such as `re` or `ci pi mu`, which can be evaluated with [`number::evaluate`].
+	Number(Numeral),
+	/// A mekso expression in `vei` and `ve'o`.
+	Mekso(mekso::Expression),
+}
+
+/// A string of PA cmavo and lerfu, as in a quantifier, a lerfu string or a mekso operand.
+///
+/// [`number::evaluate`] reads its PA cmavo as a number, and [`lerfu::interpret`] reads it as letters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Numeral {
+	/// The words, in order.
+	pub words: Vec<NumeralWord>,
+	/// The span of the numeral in the text.
+	pub span: Span,
+}
+
+/// A word of a [`Numeral`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumeralWord {
+	/// A PA cmavo, such as `pa`, `pi` or `so'i`.
+	Pa(Word),
+	/// A lerfu: a `BY` word such as `by` or `ge'o`, or a word followed by `bu`.
+	Lerfu {
+		/// The `LAU` word before the lerfu, as in `tau by`.
+		lau: Option<Word>,
+		/// The `BY` word, or the word before `bu`.
+		word: Word,
+		/// The `bu` words, more than one in `.obubu`, or none for a `BY` word.
+		bu: Vec<Word>,
+	},
+	/// A composite lerfu in `tei` and `foi`, as in `tei py sy foi`.
+	Composite {
+		/// The `tei` word.
+		tei: Word,
+		/// The parts of the composite.
+		inner: Numeral,
+		/// The `foi` word.
+		foi: Word,
+	},
+}
+
+impl NumeralWord {
+	/// The span of the word, including its `LAU` word and `bu`.
+	#[must_use]
+	pub fn span(&self) -> Span {
+		match self {
+			Self::Pa(word) => word.span,
+			Self::Lerfu { lau, word, bu } => Span::new(
+				lau.as_ref().unwrap_or(word).span.start,
+				bu.last().unwrap_or(word).span.end,
+			),
+			Self::Composite { tei, foi, .. } => Span::new(tei.span.start, foi.span.end),
+		}
+	}
+}
+
+/// A relative clause.
+#[derive(Debug, Clone)]
+pub enum RelativeClause {
+	/// A clause with a bridi, introduced by a `NOI` word such as `poi` or `noi`.
+	Bridi {
+		/// The `NOI` word.
+		word: Word,
+		/// The bridi of the clause.
+		body: Box<Statement>,
+		/// The free modifiers of the clause.
+		frees: Vec<Free>,
+	},
+	/// An association with a term, introduced by a `GOI` word such as `pe` or `goi`.
+	Association {
+		/// The `GOI` word.
+		word: Word,
+		/// The associated term.
+		term: Box<Term>,
+		/// The free modifiers of the clause.
+		frees: Vec<Free>,
+	},
+}
+
+/// A selbri: a tanru with the negations and tags that apply to it.
+#[derive(Debug, Clone)]
+pub struct Selbri {
+	/// The `na` words and tags before the tanru, in order.
+	pub modifiers: Vec<SelbriModifier>,
+	/// The tanru.
+	pub tanru: Tanru,
+	/// The span of the entire selbri.
+	pub span: Span,
+}
+
+/// A negation or tag that applies to a [`Selbri`].
+#[derive(Debug, Clone)]
+pub enum SelbriModifier {
+	/// A `na` word.
+	Negation(Word),
+	/// A tense or modal tag.
+	Tag(Tag),
+}
+
+/// A tanru, in which each modifier applies to the head that follows it.
+#[derive(Debug, Clone)]
+pub enum Tanru {
+	/// A single unit.
+	Unit(Box<TanruUnit>),
+	/// A tanru whose `head` is modified by `modifier`.
+	///
+	/// Plain tanru group from left to right, so `melbi cmalu nixli` is `melbi cmalu` modifying `nixli`.
+	/// `bo` and `ke` change the grouping, and `co` swaps the modifier and the head, so `nixli co cmalu` means the same as `cmalu nixli`.
+	Modified {
+		/// The modifier.
+		modifier: Box<Tanru>,
+		/// The modified head.
+		head: Box<Tanru>,
+	},
+	/// Two tanru joined by a connective, as in `blanu je zdani` or `gu'e barda gi xunre`.
+	Connected {
+		/// The connective that joins the tanru.
+		connective: Connective,
+		/// The first tanru.
+		left: Box<Tanru>,
+		/// The second tanru.
+		right: Box<Tanru>,
+	},
+	/// A selbri with its own negations or tags, which can only appear as the first part of a forethought connection, as in `gu'e na barda gi xunre`.
+	Selbri(Box<Selbri>),
+	/// A unit with the units it is assigned to with `cei`.
+	Assignment {
+		/// The unit whose meaning is assigned.
+		value: Box<TanruUnit>,
+		/// The units after `cei`, usually pro-bridi like `broda`, that are assigned the meaning.
+		assigned: Vec<TanruUnit>,
+	},
+}
+
+/// A unit of a tanru, with its conversions and bound arguments.
+#[derive(Debug, Clone)]
+pub struct TanruUnit {
+	/// The `SE`, `jai` and `NAhE` words before the unit, in order.
+	pub conversions: Vec<Conversion>,
+	/// What kind of unit this is.
+	pub kind: TanruUnitKind,
+	/// The terms bound to its places with `be` and `bei`.
+	pub bound_arguments: Vec<Term>,
+	/// The free modifiers of the unit.
+	pub frees: Vec<Free>,
+	/// The span of the entire unit.
+	pub span: Span,
+}
+
+/// A word that changes the place structure or meaning of a [`TanruUnit`] or a mekso [`Operator`](mekso::Operator).
+#[derive(Debug, Clone)]
+pub enum Conversion {
+	/// A `SE` word, which swaps the first place with another.
+	Se(Word),
+	/// `jai`, which either makes the first place the abstraction of the bridi, or with a tag, makes the first place the tagged place.
+	Jai {
+		/// The `jai` word.
+		jai: Word,
+		/// The tag, if any.
+		tag: Option<Tag>,
+	},
+	/// A `NAhE` word, which negates the unit on a scale.
+	Nahe(Word),
+}
+
+/// The kinds of [`TanruUnit`].
+#[derive(Debug, Clone)]
+pub enum TanruUnitKind {
+	/// A brivla: a gismu, lujvo or fu'ivla.
+	Brivla(Word),
+	/// A word made with `zei`, as a single [`Word`] of all of its parts.
+	Zei(Word),
+	/// A pro-bridi such as `broda` or `go'i`.
+	ProBridi {
+		/// The pro-bridi.
+		word: Word,
+		/// The `ra'o` that makes the pro-bridi refer to its antecedent's sumti as well, if any.
+		raho: Option<Word>,
+	},
+	/// A number with `MOI`, as in `pamoi`.
+	Number {
+		/// The number.
+		number: Numeral,
+		/// The `MOI` word.
+		moi: Word,
+	},
+	/// A sumti turned into a selbri with `me`.
+	Me {
+		/// The sumti.
+		sumti: Box<Sumti>,
+		/// The `MOI` word after the sumti, if any.
+		moi: Option<Word>,
+	},
+	/// An abstraction such as `nu mi klama`.
+	Abstraction {
+		/// The abstractor.
+		abstractor: Abstractor,
+		/// The abstracted bridi.
+		body: Box<Statement>,
+	},
+	/// A mekso operator turned into a selbri with `nu'a`.
+	Operator(mekso::Operator),
+	/// A tanru grouped with `ke` and `ke'e`.
+	Group(Box<Tanru>),
+}
+
+/// The abstractor of an abstraction, such as `nu` or `du'u`.
+#[derive(Debug, Clone)]
+pub enum Abstractor {
+	/// A single abstractor.
+	Single {
+		/// The `NU` word.
+		word: Word,
+		/// The `nai` that negates it, if any.
+		nai: Option<Word>,
+	},
+	/// Two abstractors joined by a connective, as in `nu je ka`.
+	Connected {
+		/// The connective that joins the abstractors.
+		connective: Connective,
+		/// The first abstractor.
+		left: Box<Abstractor>,
+		/// The second abstractor.
+		right: Box<Abstractor>,
+	},
+}
+
+/// A tense or modal tag.
+#[derive(Debug, Clone)]
+pub enum Tag {
+	/// A single tag.
+	Single(TagUnit),
+	/// Two tags joined by a connective, as in `pu je ba`.
+	Connected {
+		/// The connective that joins the tags.
+		connective: Box<Connective>,
+		/// The first tag.
+		left: Box<Tag>,
+		/// The second tag.
+		right: Box<Tag>,
+	},
+}
+
+/// A single tag, without connectives.
+#[derive(Debug, Clone)]
+pub enum TagUnit {
+	/// A modal from `BAI`, such as `ri'a` or `se pi'o`.
+	Modal {
+		/// The `NAhE` that scalar-negates the modal, if any.
+		nahe: Option<Word>,
+		/// The `SE` that converts the modal, if any.
+		se: Option<Word>,
+		/// The modal.
+		word: Word,
+		/// The `nai` that negates the modal, if any.
+		nai: Option<Word>,
+		/// The `ki` after the modal, if any.
+		ki: Option<Word>,
+	},
+	/// A tense, made of time, space and `CAhA` words such as `pu` or `ca ze'u` or `vi pu`.
+	Tense {
+		/// The `NAhE` that scalar-negates the tense, if any.
+		nahe: Option<Word>,
+		/// The words of the tense, in order.
+		words: Vec<Word>,
+		/// The `ki` after the tense, if any.
+		ki: Option<Word>,
+	},
+	/// `ki` alone, which resets sticky tenses.
+	Ki(Word),
+	/// A `CUhE` word, which asks for a tense.
+	Cuhe(Word),
+	/// A selbri turned into a modal with `fi'o`.
+	Selbri {
+		/// The `fi'o` word.
+		fiho: Word,
+		/// The selbri.
+		selbri: Box<Selbri>,
+	},
+}
+
+/// A connective that joins two constructs of the same kind.
+#[derive(Debug, Clone)]
+pub struct Connective {
+	/// The connective word, such as `.e`, `je`, `gi'e`, `ga` or `joi`, or `None` if the constructs are joined only by a tag or by `bo`, as in `.i ba bo`.
+	pub word: Option<Word>,
+	/// What the connective means.
+	pub kind: ConnectiveKind,
+	/// The tag that relates the constructs, as in `.i ba bo` or `gi'e ba bo`.
+	pub tag: Option<Tag>,
+	/// The free modifiers of the connective.
+	pub frees: Vec<Free>,
+}
+
+/// What a [`Connective`] means.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectiveKind {
+	/// A logical connective from `A`, `JA`, `GIhA`, `GA` or `GUhA`.
+	Logical {
+		/// The truth function before negations and conversion.
+		function: TruthFunction,
+		/// Whether the first construct is negated, with `na` before an afterthought connective or `nai` after a forethought one.
+		negate_first: bool,
+		/// Whether the second construct is negated, with `nai` after the connective or after `gi`.
+		negate_second: bool,
+		/// Whether the constructs are swapped with `se`.
+		swapped: bool,
+	},
+	/// A non-logical connective from `JOI`, such as `joi` or `ce`.
+	NonLogical {
+		/// Whether the constructs are swapped with `se`.
+		swapped: bool,
+		/// Whether the connective is negated with `nai`.
+		negated: bool,
+	},
+	/// An interval from `BIhI`, possibly with `ga'o` brackets.
+	Interval {
+		/// Whether the constructs are swapped with `se`.
+		swapped: bool,
+		/// Whether the connective is negated with `nai`.
+		negated: bool,
+		/// The `ga'o` words before and after the interval that say whether its ends are included.
+		brackets: Option<(Word, Word)>,
+	},
+	/// No connective word, only a tag or `bo`.
+	Tagged,
+}
+
+/// The truth function of a logical connective, named after the vowel of its cmavo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TruthFunction {
+	/// Inclusive or, from `a`, `ja`, `gi'a`, `ga` and `gu'a`.
+	Or,
+	/// And, from `e`, `je`, `gi'e`, `ge` and `gu'e`.
+	And,
+	/// If and only if, from `o`, `jo`, `gi'o`, `go` and `gu'o`.
+	Iff,
+	/// The first regardless of the second, from `u`, `ju`, `gi'u`, `gu` and `gu'u`.
+	Whether,
+	/// A question asking for the truth function, from `ji`, `je'i`, `gi'i`, `ge'i` and `gu'i`.
+	Question,
+}
+
+/// A free modifier, which can appear almost anywhere without affecting the grammar around it.
+#[derive(Debug, Clone)]
+pub enum Free {
+	/// A discursive bridi with `sei` or `ti'o`, as in `sei la djan cusku`.
+	Discursive {
+		/// The `sei` word.
+		sei: Word,
+		/// The terms of the bridi.
+		terms: Vec<Term>,
+		/// The selbri of the bridi, if any.
+		selbri: Option<Selbri>,
+	},
+	/// A reciprocal or reflexive scope with `soi`.
+	Soi {
+		/// The `soi` word.
+		soi: Word,
+		/// The first sumti.
+		first: Sumti,
+		/// The second sumti, if any.
+		second: Option<Sumti>,
+	},
+	/// A vocative, as in `coi la djan` or `doi ro do`.
+	Vocative {
+		/// The `COI` and `DOI` words, with the `nai` after each `COI` word.
+		words: Vec<Word>,
+		/// Who is addressed.
+		addressee: Option<Addressee>,
+		/// The relative clauses on the addressee.
+		relative_clauses: Vec<RelativeClause>,
+	},
+	/// An utterance ordinal with `MAI`, as in `pamai`.
+	Ordinal {
+		/// The number.
+		number: Numeral,
+		/// The `MAI` word.
+		mai: Word,
+	},
+	/// A parenthetical text in `to` and `toi`.
+	Parenthetical(Box<Text>),
+	/// A subscript with `xi`, which is a number or a mekso expression in `vei` and `ve'o`.
+	Subscript(Quantifier),
+}
+
+/// Who is addressed by a vocative.
+#[derive(Debug, Clone)]
+pub enum Addressee {
+	/// A selbri, as in `doi pendo`.
+	Selbri(Selbri),
+	/// A name made of cmevla, as in `doi djan`.
+	Name(Vec<Word>),
+	/// A sumti, as in `doi la djan`.
+	Sumti(Sumti),
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, |$statement:ident, $slice:ident| $check:block) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let arena = crate::Arena::new();
+				let cst = crate::parse_in(input, &arena).unwrap();
+				let text = lower(&cst, input);
+				let $statement = &text.paragraphs[0].sentences[0].statement;
+				#[allow(unused_variables)]
+				let $slice = |span: Span| span.slice(input).unwrap();
+				$check
+			}
+		};
+	}
+
+	fn bridi(statement: &Statement) -> &Bridi {
+		match statement {
+			Statement::Bridi(bridi) => bridi,
+			_ => panic!("expected a bridi, got {statement:?}"),
+		}
+	}
+
+	fn selbri(statement: &Statement) -> &Selbri {
+		match &bridi(statement).tail {
+			BridiTail::Selbri { selbri, .. } => selbri,
+			tail => panic!("expected a single selbri, got {tail:?}"),
+		}
+	}
+
+	fn brivla(tanru: &Tanru) -> Span {
+		match tanru {
+			Tanru::Unit(unit) => match &unit.kind {
+				TanruUnitKind::Brivla(word) => word.span,
+				kind => panic!("expected a brivla, got {kind:?}"),
+			},
+			_ => panic!("expected a unit, got {tanru:?}"),
+		}
+	}
+
+	make_test!(place_tags, "fe do fa mi klama", |statement, slice| {
+		let head = &bridi(statement).head;
+		let places: Vec<_> = head
+			.iter()
+			.map(|term| match term {
+				Term::Sumti {
+					place: Some(place), ..
+				} => slice(place.span),
+				_ => panic!("expected a sumti with a place tag, got {term:?}"),
+			})
+			.collect();
+		assert_eq!(places, ["fe", "fa"]);
+	});
+
+	make_test!(
+		tanru_grouping,
+		"mi melbi cmalu nixli",
+		|statement, slice| {
+			let Tanru::Modified { modifier, head } = &selbri(statement).tanru else {
+				panic!();
+			};
+			assert_eq!(slice(brivla(head)), "nixli");
+			let Tanru::Modified { modifier, head } = &**modifier else {
+				panic!();
+			};
+			assert_eq!(slice(brivla(modifier)), "melbi");
+			assert_eq!(slice(brivla(head)), "cmalu");
+		}
+	);
+
+	make_test!(co_inversion, "mi nixli co cmalu", |statement, slice| {
+		let Tanru::Modified { modifier, head } = &selbri(statement).tanru else {
+			panic!();
+		};
+		assert_eq!(slice(brivla(modifier)), "cmalu");
+		assert_eq!(slice(brivla(head)), "nixli");
+	});
+
+	make_test!(
+		connected_tails,
+		"mi klama gi'e citka vau lo cidja",
+		|statement, slice| {
+			let BridiTail::Connected {
+				connective,
+				left,
+				right,
+				terms,
+			} = &bridi(statement).tail
+			else {
+				panic!();
+			};
+			assert_eq!(
+				connective.kind,
+				ConnectiveKind::Logical {
+					function: TruthFunction::And,
+					negate_first: false,
+					negate_second: false,
+					swapped: false,
+				}
+			);
+			assert!(matches!(&**left, BridiTail::Selbri { terms, .. } if terms.is_empty()));
+			assert!(
+				matches!(&**right, BridiTail::Selbri { selbri, .. } if slice(selbri.span) == "citka")
+			);
+			assert_eq!(terms.len(), 1);
+		}
+	);
+
+	make_test!(
+		negated_connective,
+		"mi na.enai do klama",
+		|statement, _slice| {
+			let [Term::Sumti { sumti, .. }] = &bridi(statement).head[..] else {
+				panic!();
+			};
+			let SumtiKind::Connected { connective, .. } = &sumti.kind else {
+				panic!();
+			};
+			assert_eq!(
+				connective.kind,
+				ConnectiveKind::Logical {
+					function: TruthFunction::And,
+					negate_first: true,
+					negate_second: true,
+					swapped: false,
+				}
+			);
+		}
+	);
+
+	make_test!(
+		forethought_connective,
+		"go mi gi do klama",
+		|statement, slice| {
+			let [Term::Sumti { sumti, .. }] = &bridi(statement).head[..] else {
+				panic!();
+			};
+			let SumtiKind::Connected {
+				connective,
+				left,
+				right,
+			} = &sumti.kind
+			else {
+				panic!();
+			};
+			assert!(matches!(
+				connective.kind,
+				ConnectiveKind::Logical {
+					function: TruthFunction::Iff,
+					..
+				}
+			));
+			assert_eq!(slice(left.span), "mi");
+			assert_eq!(slice(right.span), "do");
+			assert_eq!(slice(sumti.span), "go mi gi do");
+		}
+	);
+
+	make_test!(indicators, "mi ui cai klama", |statement, slice| {
+		let [Term::Sumti { sumti, .. }] = &bridi(statement).head[..] else {
+			panic!();
+		};
+		let SumtiKind::ProSumti(word) = &sumti.kind else {
+			panic!();
+		};
+		let [indicator] = &word.indicators[..] else {
+			panic!();
+		};
+		assert_eq!(slice(indicator.word.span), "ui");
+		assert_eq!(indicator.modifiers.len(), 1);
+		assert_eq!(slice(indicator.modifiers[0].span), "cai");
+	});
+
+	#[test]
+	fn sentence_indicators() {
+		let input = "mi klama .i ku'i do stali";
+		let arena = crate::Arena::new();
+		let cst = crate::parse_in(input, &arena).unwrap();
+		let text = lower(&cst, input);
+		let sentences = &text.paragraphs[0].sentences;
+		assert_eq!(sentences.len(), 2);
+		assert!(sentences[0].i.is_none());
+		let i = sentences[1].i.as_ref().unwrap();
+		assert_eq!(i.indicators[0].word.span.slice(input), Some("ku'i"));
+	}
+
+	make_test!(
+		relative_clause,
+		"lo gerku poi barda ku'o cu klama",
+		|statement, slice| {
+			let [Term::Sumti { sumti, .. }] = &bridi(statement).head[..] else {
+				panic!();
+			};
+			let SumtiKind::Description(description) = &sumti.kind else {
+				panic!();
+			};
+			assert_eq!(slice(description.descriptor.as_ref().unwrap().span), "lo");
+			let [RelativeClause::Bridi { word, body, .. }] = &description.relative_clauses[..] else {
+				panic!();
+			};
+			assert_eq!(slice(word.span), "poi");
+			assert_eq!(slice(selbri(body).span), "barda");
+		}
+	);
+
+	make_test!(
+		tagged_statements,
+		"mi klama .i ba bo do citka",
+		|statement, slice| {
+			let Statement::Connected {
+				i,
+				connective,
+				left,
+				right,
+			} = statement
+			else {
+				panic!();
+			};
+			assert_eq!(slice(i.span), "i");
+			assert!(connective.word.is_none());
+			assert_eq!(connective.kind, ConnectiveKind::Tagged);
+			assert!(matches!(
+				&connective.tag,
+				Some(Tag::Single(TagUnit::Tense { words, .. })) if words.len() == 1
+			));
+			assert_eq!(slice(bridi(left).span), "mi klama");
+			assert_eq!(slice(bridi(right).span), "do citka");
+		}
+	);
+
+	make_test!(tense, "mi pu ze'u klama", |statement, slice| {
+		let [_, Term::Tagged {
+			tag: Tag::Single(TagUnit::Tense { words, .. }),
+			sumti: None,
+		}] = &bridi(statement).head[..]
+		else {
+			panic!();
+		};
+		let words: Vec<_> = words.iter().map(|word| slice(word.span)).collect();
+		assert_eq!(words, ["pu", "ze'u"]);
+	});
+
+	make_test!(frees, "mi klama to do citka toi", |statement, _slice| {
+		let Tanru::Unit(unit) = &selbri(statement).tanru else {
+			panic!();
+		};
+		assert!(matches!(&unit.frees[..], [Free::Parenthetical(_)]));
+	});
+
+	make_test!(prenex, "ro da zo'u da klama", |statement, _slice| {
+		let Statement::Prenex { terms, body, .. } = statement else {
+			panic!();
+		};
+		assert_eq!(terms.len(), 1);
+		bridi(body);
+	});
+
+	make_test!(fragment, "lo mlatu", |statement, _slice| {
+		assert!(matches!(statement, Statement::Fragment(Fragment::Terms(terms)) if terms.len() == 1));
+	});
+
+	make_test!(
+		abstraction,
+		"mi djica lo nu do klama",
+		|statement, _slice| {
+			let BridiTail::Selbri { terms, .. } = &bridi(statement).tail else {
+				panic!();
+			};
+			let [Term::Sumti { sumti, .. }] = &terms[..] else {
+				panic!();
+			};
+			let SumtiKind::Description(description) = &sumti.kind else {
+				panic!();
+			};
+			let DescriptionInner::Selbri(inner) = &description.inner else {
+				panic!();
+			};
+			let Tanru::Unit(unit) = &inner.tanru else {
+				panic!();
+			};
+			let TanruUnitKind::Abstraction { abstractor, body } = &unit.kind else {
+				panic!();
+			};
+			assert!(matches!(abstractor, Abstractor::Single { nai: None, .. }));
+			bridi(body);
+		}
+	);
+}
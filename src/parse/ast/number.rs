@@ -0,0 +1,989 @@
+//! Numeric values of strings of PA cmavo.
+//!
+//! [`Numeral`]s keep their PA cmavo as words; this module reads them as a number.
+//! Digits are read in base ten, with `pi` as the decimal point, `ni'u` and `ma'u` as signs, `ki'o` as the thousands separator, `fi'u` as a fraction bar, `ra'e` before the repeating digits of a decimal, and `ce'i` as a percent sign.
+//! `ji'i` anywhere makes the value [approximate](Value::approximate).
+//! Indefinite numbers such as `so'i`, `ro`, and `su'o re` are [reported as such](Quantity::Indefinite) rather than given a value.
+//!
+//! [`spell`] goes the other way, writing a value as PA cmavo.
+//!
+//! ```rust
+//! use sneturfahi::parse::ast::number::{self, Rational};
+//! use sneturfahi::parse::ast::{self, Quantifier, Statement, Term};
+//!
+//! let input = "paremu lo gerku cu klama";
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::parse_in(input, &arena).unwrap();
+//! let text = ast::lower(&cst, input);
+//!
+//! let Statement::Bridi(bridi) = &text.paragraphs[0].sentences[0].statement else {
+//! 	unreachable!();
+//! };
+//! let Term::Sumti { sumti, .. } = &bridi.head[0] else {
+//! 	unreachable!();
+//! };
+//! let Some(Quantifier::Number(numeral)) = &sumti.quantifier else {
+//! 	unreachable!();
+//! };
+//! let value = number::evaluate(numeral).unwrap();
+//! assert_eq!(value.exact(), Some(Rational::from(125)));
+//! ```
+
+use super::{Numeral, NumeralWord, Word};
+use crate::span::Span;
+
+/// Evaluate a [`Numeral`], as used in quantifiers, mekso operands and before MOI.
+///
+/// # Errors
+/// Returns an error if the number contains lerfu, uses PA cmavo that have no value on their own such as `pai` or `pi'e`, or is malformed.
+pub fn evaluate(numeral: &Numeral) -> Result<Value, Error> {
+	let words = numeral
+		.words
+		.iter()
+		.map(|word| match word {
+			NumeralWord::Pa(word) => Ok(word),
+			lerfu => Err(Error::Lerfu(lerfu.span())),
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	evaluate_words(&words)
+}
+
+/// The value of a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Value {
+	/// What the number stands for.
+	pub quantity: Quantity,
+	/// Whether the number contained `ji'i`.
+	pub approximate: bool,
+}
+
+impl Value {
+	/// The exact value of the number, if it has one.
+	#[must_use]
+	pub fn exact(&self) -> Option<Rational> {
+		match self.quantity {
+			Quantity::Exact(value) => Some(value),
+			Quantity::Indefinite { .. } => None,
+		}
+	}
+}
+
+/// What a number stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+	/// A definite number, such as `pa pi mu`.
+	Exact(Rational),
+	/// An indefinite number, such as `so'i` or `su'o re`.
+	Indefinite {
+		/// The indefinite number word.
+		word: Indefinite,
+		/// The number that follows the word, for those that take one, as in `su'o re`.
+		operand: Option<Rational>,
+	},
+}
+
+/// The indefinite numbers of PA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Indefinite {
+	/// `ro`, all.
+	All,
+	/// `so'a`, almost all.
+	AlmostAll,
+	/// `so'e`, most.
+	Most,
+	/// `so'i`, many.
+	Many,
+	/// `so'o`, several.
+	Several,
+	/// `so'u`, a few.
+	Few,
+	/// `rau`, enough.
+	Enough,
+	/// `du'e`, too many.
+	TooMany,
+	/// `mo'a`, too few.
+	TooFew,
+	/// `no'o`, the typical number.
+	Typical,
+	/// `xo`, which number?
+	Question,
+	/// `su'o`, at least. On its own, at least one.
+	AtLeast,
+	/// `su'e`, at most.
+	AtMost,
+	/// `za'u`, more than.
+	MoreThan,
+	/// `me'i`, less than.
+	LessThan,
+	/// `da'a`, all except.
+	AllExcept,
+}
+
+impl Indefinite {
+	fn from_word(word: &str) -> Option<Self> {
+		Some(match word {
+			"ro" => Self::All,
+			"so'a" => Self::AlmostAll,
+			"so'e" => Self::Most,
+			"so'i" => Self::Many,
+			"so'o" => Self::Several,
+			"so'u" => Self::Few,
+			"rau" => Self::Enough,
+			"du'e" => Self::TooMany,
+			"mo'a" => Self::TooFew,
+			"no'o" => Self::Typical,
+			"xo" => Self::Question,
+			"su'o" => Self::AtLeast,
+			"su'e" => Self::AtMost,
+			"za'u" => Self::MoreThan,
+			"me'i" => Self::LessThan,
+			"da'a" => Self::AllExcept,
+			_ => return None,
+		})
+	}
+
+	/// Whether the word can be followed by a number that it applies to, as in `su'o re`.
+	#[must_use]
+	pub fn takes_operand(self) -> bool {
+		matches!(
+			self,
+			Self::AtLeast | Self::AtMost | Self::MoreThan | Self::LessThan | Self::AllExcept
+		)
+	}
+}
+
+/// An exact rational number, always in lowest terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+	numerator: i128,
+	denominator: i128,
+}
+
+impl Rational {
+	/// Create the rational `numerator / denominator`, or `None` if the denominator is zero or the result does not fit.
+	#[must_use]
+	pub fn new(numerator: i128, denominator: i128) -> Option<Self> {
+		if denominator == 0 {
+			return None;
+		}
+		let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs());
+		let divisor = i128::try_from(divisor).ok()?;
+		let (numerator, denominator) = if denominator < 0 {
+			(numerator.checked_neg()?, denominator.checked_neg()?)
+		} else {
+			(numerator, denominator)
+		};
+		Some(Self {
+			numerator: numerator / divisor,
+			denominator: denominator / divisor,
+		})
+	}
+
+	/// The numerator, which carries the sign.
+	#[must_use]
+	pub fn numerator(self) -> i128 {
+		self.numerator
+	}
+
+	/// The denominator, which is always positive.
+	#[must_use]
+	pub fn denominator(self) -> i128 {
+		self.denominator
+	}
+
+	/// Whether the number is a whole number.
+	#[must_use]
+	pub fn is_integer(self) -> bool {
+		self.denominator == 1
+	}
+
+	/// The nearest floating-point number.
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)] // precision is what a float gives up
+	pub fn to_f64(self) -> f64 {
+		self.numerator as f64 / self.denominator as f64
+	}
+
+	/// Add two rationals, or return `None` on overflow.
+	#[must_use]
+	pub fn checked_add(self, other: Self) -> Option<Self> {
+		Self::new(
+			self
+				.numerator
+				.checked_mul(other.denominator)?
+				.checked_add(other.numerator.checked_mul(self.denominator)?)?,
+			self.denominator.checked_mul(other.denominator)?,
+		)
+	}
+
+	/// Multiply two rationals, or return `None` on overflow.
+	#[must_use]
+	pub fn checked_mul(self, other: Self) -> Option<Self> {
+		Self::new(
+			self.numerator.checked_mul(other.numerator)?,
+			self.denominator.checked_mul(other.denominator)?,
+		)
+	}
+
+	/// Divide two rationals, or return `None` on overflow or division by zero.
+	#[must_use]
+	pub fn checked_div(self, other: Self) -> Option<Self> {
+		Self::new(
+			self.numerator.checked_mul(other.denominator)?,
+			self.denominator.checked_mul(other.numerator)?,
+		)
+	}
+
+	/// Subtract two rationals, or return `None` on overflow.
+	#[must_use]
+	pub fn checked_sub(self, other: Self) -> Option<Self> {
+		self.checked_add(other.checked_neg()?)
+	}
+
+	/// Raise a rational to an integer power, or return `None` on overflow or if zero is raised to a negative power.
+	#[must_use]
+	pub fn checked_pow(self, exponent: i32) -> Option<Self> {
+		let power = self.numerator.checked_pow(exponent.unsigned_abs())?;
+		let denominator = self.denominator.checked_pow(exponent.unsigned_abs())?;
+		if exponent < 0 {
+			Self::new(denominator, power)
+		} else {
+			Self::new(power, denominator)
+		}
+	}
+
+	/// Negate a rational, or return `None` on overflow.
+	#[must_use]
+	pub fn checked_neg(self) -> Option<Self> {
+		Some(Self {
+			numerator: self.numerator.checked_neg()?,
+			denominator: self.denominator,
+		})
+	}
+}
+
+impl From<i128> for Rational {
+	fn from(value: i128) -> Self {
+		Self {
+			numerator: value,
+			denominator: 1,
+		}
+	}
+}
+
+impl std::fmt::Display for Rational {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.is_integer() {
+			write!(formatter, "{}", self.numerator)
+		} else {
+			write!(formatter, "{}/{}", self.numerator, self.denominator)
+		}
+	}
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+	while b != 0 {
+		(a, b) = (b, a % b);
+	}
+	a.max(1)
+}
+
+/// An error in evaluating a number.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+	/// The number contains lerfu, which stand for unknown digits.
+	#[error("lerfu at {0:?} have no numeric value")]
+	Lerfu(Span),
+	/// The number contains a PA cmavo that has no value of its own, such as `pai` or `pi'e`.
+	#[error("the PA cmavo at {0:?} cannot be evaluated")]
+	Unsupported(Span),
+	/// A PA cmavo appeared where it makes no sense, such as a second `pi` or a fourth digit in a `ki'o` group.
+	#[error("unexpected PA cmavo at {0:?}")]
+	Unexpected(Span),
+	/// The number ended where more was expected, such as after `fi'u` or `ni'u`.
+	#[error("incomplete number at {0:?}")]
+	Incomplete(Span),
+	/// The number has a denominator of zero.
+	#[error("division by zero in the number at {0:?}")]
+	DivisionByZero(Span),
+	/// The number is too large to represent.
+	#[error("the number at {0:?} is too large")]
+	Overflow(Span),
+}
+
+/// The value of a digit of PA, including the hexadecimal digits `dau` to `vai`.
+pub(super) fn digit(word: &str) -> Option<u8> {
+	Some(match word {
+		"no" | "0" => 0,
+		"pa" | "1" => 1,
+		"re" | "2" => 2,
+		"ci" | "3" => 3,
+		"vo" | "4" => 4,
+		"mu" | "5" => 5,
+		"xa" | "6" => 6,
+		"ze" | "7" => 7,
+		"bi" | "8" => 8,
+		"so" | "9" => 9,
+		"dau" => 10,
+		"fei" => 11,
+		"gai" => 12,
+		"jau" => 13,
+		"rei" => 14,
+		"vai" => 15,
+		_ => return None,
+	})
+}
+
+fn evaluate_words(words: &[&Word]) -> Result<Value, Error> {
+	let whole = Span::new(
+		words.first().map_or(0, |word| word.span.start),
+		words.last().map_or(0, |word| word.span.end),
+	);
+	let mut approximate = false;
+	let mut indefinite = None;
+	let mut numerator = Part::default();
+	let mut denominator: Option<Part> = None;
+
+	for word in words {
+		match word.text.as_str() {
+			"ji'i" => approximate = true,
+			"fi'u" if denominator.is_none() => denominator = Some(Part::default()),
+			other => {
+				if let Some(found) = Indefinite::from_word(other) {
+					if indefinite.is_some() || !numerator.is_empty() || denominator.is_some() {
+						return Err(Error::Unexpected(word.span));
+					}
+					indefinite = Some(found);
+				} else if indefinite.is_some_and(|found: Indefinite| !found.takes_operand()) {
+					return Err(Error::Unexpected(word.span));
+				} else {
+					denominator.as_mut().unwrap_or(&mut numerator).push(word)?;
+				}
+			}
+		}
+	}
+
+	let quantity = match indefinite {
+		Some(word) if numerator.is_empty() && denominator.is_none() => Quantity::Indefinite {
+			word,
+			operand: None,
+		},
+		Some(word) => Quantity::Indefinite {
+			word,
+			operand: Some(fraction(numerator, denominator, whole)?),
+		},
+		None => Quantity::Exact(fraction(numerator, denominator, whole)?),
+	};
+	Ok(Value {
+		quantity,
+		approximate,
+	})
+}
+
+fn fraction(numerator: Part, denominator: Option<Part>, whole: Span) -> Result<Rational, Error> {
+	let Some(denominator) = denominator else {
+		return numerator.value(whole);
+	};
+	// `fi'u ci` is a third
+	let numerator = if numerator.is_empty() {
+		Rational::from(1)
+	} else {
+		numerator.value(whole)?
+	};
+	let denominator = denominator.value(whole)?;
+	if denominator.numerator() == 0 {
+		return Err(Error::DivisionByZero(whole));
+	}
+	numerator
+		.checked_div(denominator)
+		.ok_or(Error::Overflow(whole))
+}
+
+/// A number as it is written, for [rendering](super::mekso::Expression::render) it with the same digits and fraction bar.
+pub(super) struct Written {
+	/// The side before `fi'u`, or `None` if there is none, as in `fi'u ci`.
+	pub(super) numerator: Option<Part>,
+	/// The side after `fi'u`, if there is one.
+	pub(super) denominator: Option<Part>,
+}
+
+/// Read `numeral` as it is written, or `None` if it has no exact value or has digits above nine.
+///
+/// `ji'i` is left out, and the `ki'o` groups are padded, so that `pa ki'o re` is written 1002.
+pub(super) fn written(numeral: &Numeral) -> Option<Written> {
+	evaluate(numeral).ok()?.exact()?;
+	let mut numerator = Part::default();
+	let mut denominator: Option<Part> = None;
+	for word in &numeral.words {
+		let NumeralWord::Pa(word) = word else {
+			return None;
+		};
+		match word.text.as_str() {
+			"ji'i" => {}
+			"fi'u" => denominator = Some(Part::default()),
+			_ => denominator
+				.as_mut()
+				.unwrap_or(&mut numerator)
+				.push(word)
+				.ok()?,
+		}
+	}
+	let parts = [Some(&mut numerator), denominator.as_mut()];
+	for part in parts.into_iter().flatten() {
+		part.close_group();
+		let digits = part.integer.iter().chain(part.fraction.iter().flatten());
+		if digits
+			.chain(part.repeating.iter().flatten())
+			.any(|&digit| digit > 9)
+		{
+			return None;
+		}
+	}
+	Some(Written {
+		numerator: (!numerator.is_empty()).then_some(numerator),
+		denominator,
+	})
+}
+
+/// One side of a fraction, as its digits.
+#[derive(Default)]
+pub(super) struct Part {
+	/// Whether there was a `ni'u`.
+	pub(super) negative: bool,
+	signed: bool,
+	/// The digits before `pi`.
+	pub(super) integer: Vec<u8>,
+	/// The number of digits in the current `ki'o` group, once there has been a `ki'o`.
+	group: Option<usize>,
+	/// The digits after `pi` and before `ra'e`, if there was a `pi`.
+	pub(super) fraction: Option<Vec<u8>>,
+	/// The digits after `ra'e`, which repeat forever, if there was a `ra'e`.
+	pub(super) repeating: Option<Vec<u8>>,
+	/// Whether there was a `ce'i`.
+	pub(super) percent: bool,
+}
+
+impl Part {
+	fn is_empty(&self) -> bool {
+		!self.signed && self.integer.is_empty() && self.fraction.is_none()
+	}
+
+	fn push(&mut self, word: &Word) -> Result<(), Error> {
+		if self.percent {
+			return Err(Error::Unexpected(word.span));
+		}
+		if let Some(digit) = digit(&word.text) {
+			if let Some(repeating) = &mut self.repeating {
+				repeating.push(digit);
+			} else if let Some(fraction) = &mut self.fraction {
+				fraction.push(digit);
+			} else {
+				if let Some(group) = &mut self.group {
+					// a `ki'o` group has at most three digits
+					if *group == 3 {
+						return Err(Error::Unexpected(word.span));
+					}
+					*group += 1;
+				}
+				self.integer.push(digit);
+			}
+			return Ok(());
+		}
+		match word.text.as_str() {
+			"ma'u" | "ni'u" if self.is_empty() => {
+				self.signed = true;
+				self.negative = word.text == "ni'u";
+			}
+			"ki'o" if self.fraction.is_none() && !self.integer.is_empty() => {
+				self.close_group();
+				self.group = Some(0);
+			}
+			"pi" if self.fraction.is_none() => {
+				self.close_group();
+				self.fraction = Some(Vec::new());
+			}
+			"ra'e" if self.fraction.is_some() && self.repeating.is_none() => {
+				self.repeating = Some(Vec::new());
+			}
+			"ce'i" if !self.integer.is_empty() || self.fraction.is_some() => {
+				self.close_group();
+				self.percent = true;
+			}
+			"ma'u" | "ni'u" | "ki'o" | "pi" | "ra'e" | "ce'i" | "fi'u" => {
+				return Err(Error::Unexpected(word.span));
+			}
+			_ => return Err(Error::Unsupported(word.span)),
+		}
+		Ok(())
+	}
+
+	/// Pad the last `ki'o` group with leading zeros, so that `pa ki'o re` is 1002.
+	fn close_group(&mut self) {
+		if let Some(group) = self.group.take() {
+			let start = self.integer.len() - group;
+			for _ in group..3 {
+				self.integer.insert(start, 0);
+			}
+		}
+	}
+
+	fn value(mut self, whole: Span) -> Result<Rational, Error> {
+		self.close_group();
+		let fraction = self.fraction.take().unwrap_or_default();
+		let repeating = self.repeating.take();
+		let no_digits = self.integer.is_empty() && fraction.is_empty() && repeating.is_none();
+		if no_digits || repeating.as_ref().is_some_and(Vec::is_empty) {
+			return Err(Error::Incomplete(whole));
+		}
+
+		let overflow = Error::Overflow(whole);
+
+		let negative = self.negative;
+		let mut value = Rational::from(digits(&self.integer, negative).ok_or(overflow.clone())?);
+		let shift = power(fraction.len()).ok_or(overflow.clone())?;
+		let decimal = Rational::new(digits(&fraction, negative).ok_or(overflow.clone())?, shift);
+		value = decimal
+			.and_then(|decimal| value.checked_add(decimal))
+			.ok_or(overflow.clone())?;
+		if let Some(repeating) = repeating {
+			// 0.0(12) is 12 / (10 * 99)
+			let nines = power(repeating.len())
+				.and_then(|power| power.checked_sub(1))
+				.and_then(|nines| nines.checked_mul(shift));
+			let repeated = nines.and_then(|nines| Rational::new(digits(&repeating, negative)?, nines));
+			value = repeated
+				.and_then(|repeated| value.checked_add(repeated))
+				.ok_or(overflow.clone())?;
+		}
+		if self.percent {
+			value = value.checked_div(Rational::from(100)).ok_or(overflow)?;
+		}
+		Ok(value)
+	}
+}
+
+/// The value of base ten digits, negated if `negative`, or `None` if it overflows.
+///
+/// Negative values are built up as negative, since `i128::MIN` has no positive counterpart.
+fn digits(digits: &[u8], negative: bool) -> Option<i128> {
+	digits.iter().try_fold(0_i128, |value, &digit| {
+		let value = value.checked_mul(10)?;
+		if negative {
+			value.checked_sub(i128::from(digit))
+		} else {
+			value.checked_add(i128::from(digit))
+		}
+	})
+}
+
+/// Ten to the power of `count`, or `None` if it overflows.
+fn power(count: usize) -> Option<i128> {
+	u32::try_from(count)
+		.ok()
+		.and_then(|count| 10_i128.checked_pow(count))
+}
+
+/// Spell a number as a string of PA cmavo, the inverse of [`evaluate`].
+///
+/// The digits are written together as one compound cmavo, as in `ni'upaki'oremoi`, which [`lex`](crate::lex) splits back into words.
+/// Evaluating the result gives `value` back.
+#[must_use]
+pub fn spell(value: Rational, spelling: Spelling) -> String {
+	let mut words = Vec::new();
+	if value.numerator() < 0 {
+		words.push("ni'u");
+	}
+	let numerator = value.numerator().unsigned_abs();
+	let denominator = value.denominator().unsigned_abs();
+	let decimal = match spelling.fractions {
+		Fractions::Decimal => decimal(numerator, denominator)
+			.filter(|(fixed, repeating)| evaluates(numerator / denominator, fixed, repeating)),
+		Fractions::Bar => None,
+	};
+	if denominator == 1 {
+		spell_integer(numerator, spelling.thousands, &mut words);
+	} else if let Some((fixed, repeating)) = decimal {
+		spell_integer(numerator / denominator, spelling.thousands, &mut words);
+		words.push("pi");
+		words.extend(fixed.iter().map(|&digit| DIGITS[usize::from(digit)]));
+		if !repeating.is_empty() {
+			words.push("ra'e");
+			words.extend(repeating.iter().map(|&digit| DIGITS[usize::from(digit)]));
+		}
+	} else {
+		spell_integer(numerator, spelling.thousands, &mut words);
+		words.push("fi'u");
+		spell_integer(denominator, spelling.thousands, &mut words);
+	}
+	match spelling.suffix {
+		Some(Suffix::Moi) => words.push("moi"),
+		Some(Suffix::Mei) => words.push("mei"),
+		None => {}
+	}
+	words.concat()
+}
+
+/// How [`spell`] writes a number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Spelling {
+	/// Whether to separate groups of three digits before the decimal point with `ki'o`, as in `pa ki'o re` for 1002.
+	/// Leading zeros of each group are left out, since `ki'o` pads them back.
+	pub thousands: bool,
+	/// How to write numbers that are not integers.
+	pub fractions: Fractions,
+	/// The MOI cmavo to put after the number, if any.
+	pub suffix: Option<Suffix>,
+}
+
+/// How [`spell`] writes numbers that are not integers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Fractions {
+	/// As a decimal with `pi`, with `ra'e` before the repeating digits if there are any, as in `no pi ra'e ci` for 1/3.
+	/// Decimals that would need too many digits to evaluate are written with `fi'u` instead.
+	#[default]
+	Decimal,
+	/// As a fraction with `fi'u`, as in `pa fi'u ci` for 1/3.
+	Bar,
+}
+
+/// The MOI cmavo that [`spell`] can put after a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suffix {
+	/// `moi`, for ordinals such as `pamoi`, first.
+	Moi,
+	/// `mei`, for cardinals such as `remei`, a pair.
+	Mei,
+}
+
+const DIGITS: [&str; 10] = ["no", "pa", "re", "ci", "vo", "mu", "xa", "ze", "bi", "so"];
+
+fn spell_integer(value: u128, thousands: bool, words: &mut Vec<&str>) {
+	let digits = value.to_string();
+	let digits = digits
+		.bytes()
+		.map(|digit| DIGITS[usize::from(digit - b'0')]);
+	if !thousands || value < 1000 {
+		words.extend(digits);
+		return;
+	}
+	let digits = digits.collect::<Vec<_>>();
+	let first = digits.len() % 3;
+	let first = if first == 0 { 3 } else { first };
+	words.extend(&digits[..first]);
+	for group in digits[first..].chunks(3) {
+		words.push("ki'o");
+		words.extend(group.iter().skip_while(|&&digit| digit == "no"));
+	}
+}
+
+/// Whether a decimal can be evaluated without overflow, following the steps of [`Part::value`].
+fn evaluates(integer: u128, fixed: &[u8], repeating: &[u8]) -> bool {
+	let value = || {
+		let shift = power(fixed.len())?;
+		let mut value = Rational::from(i128::try_from(integer).ok()?)
+			.checked_add(Rational::new(digits(fixed, false)?, shift)?)?;
+		if !repeating.is_empty() {
+			let nines = power(repeating.len())?.checked_sub(1)?.checked_mul(shift)?;
+			value = value.checked_add(Rational::new(digits(repeating, false)?, nines)?)?;
+		}
+		Some(value)
+	};
+	value().is_some()
+}
+
+/// The digits after the decimal point of `numerator / denominator`, split into those before the repeating digits and the repeating digits, or `None` if there are too many to evaluate.
+fn decimal(numerator: u128, denominator: u128) -> Option<(Vec<u8>, Vec<u8>)> {
+	// `evaluate` needs ten to the power of the number of digits to fit in an `i128`
+	const MAX_DIGITS: usize = 38;
+	let mut remainders = Vec::new();
+	let mut digits = Vec::new();
+	let mut remainder = numerator % denominator;
+	while remainder != 0 {
+		if let Some(start) = remainders.iter().position(|&seen| seen == remainder) {
+			let repeating = digits.split_off(start);
+			return Some((digits, repeating));
+		}
+		if digits.len() == MAX_DIGITS {
+			return None;
+		}
+		remainders.push(remainder);
+		let shifted = remainder.checked_mul(10)?;
+		digits.push(u8::try_from(shifted / denominator).expect("a digit is less than ten"));
+		remainder = shifted % denominator;
+	}
+	Some((digits, Vec::new()))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::parse::ast::lower::Lowerer;
+	use crate::parse::cst::node::NodeRef;
+
+	fn evaluate_first(input: &str) -> Result<Value, Error> {
+		let arena = crate::Arena::new();
+		let cst = crate::parse_in(input, &arena).unwrap();
+		let found =
+			crate::parse::select::select(cst.root(), cst.tokens(), input, "Number, MiscNumbers").unwrap();
+		let lowerer = Lowerer::new(input, cst.tokens());
+		let numeral = match found[0].node.downcast() {
+			NodeRef::Number(number) => lowerer.number(number),
+			NodeRef::MiscNumbers(numbers) => lowerer.misc_numbers(numbers),
+			other => panic!("expected a number, got {:?}", other.kind()),
+		};
+		evaluate(&numeral)
+	}
+
+	fn exact(numerator: i128, denominator: i128) -> Value {
+		Value {
+			quantity: Quantity::Exact(Rational::new(numerator, denominator).unwrap()),
+			approximate: false,
+		}
+	}
+
+	fn indefinite(word: Indefinite, operand: Option<i128>) -> Value {
+		Value {
+			quantity: Quantity::Indefinite {
+				word,
+				operand: operand.map(Rational::from),
+			},
+			approximate: false,
+		}
+	}
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:expr) => {
+			#[test]
+			fn $name() {
+				assert_eq!(evaluate_first($input), $expected);
+			}
+		};
+	}
+
+	make_test!(digits, "li paremu", Ok(exact(125, 1)));
+	make_test!(quantifier, "ci lo gerku", Ok(exact(3, 1)));
+	make_test!(arabic_digits, "li 1 2 3", Ok(exact(123, 1)));
+	make_test!(moi, "paremoi", Ok(exact(12, 1)));
+	make_test!(leading_zero, "li nopa", Ok(exact(1, 1)));
+	make_test!(decimal, "li pa pi mu", Ok(exact(3, 2)));
+	make_test!(leading_decimal, "li pi re mu", Ok(exact(1, 4)));
+	make_test!(negative, "li ni'u ci", Ok(exact(-3, 1)));
+	make_test!(positive, "li ma'u ci", Ok(exact(3, 1)));
+	make_test!(thousands, "li pa ki'o", Ok(exact(1000, 1)));
+	make_test!(thousands_padded, "li pa ki'o re", Ok(exact(1002, 1)));
+	make_test!(thousands_full, "li pa ki'o recivo", Ok(exact(1234, 1)));
+	make_test!(
+		thousands_too_long,
+		"li pa ki'o recivomu",
+		Err(Error::Unexpected(Span::new(17, 19)))
+	);
+	make_test!(millions, "li pa ki'o ki'o", Ok(exact(1_000_000, 1)));
+	make_test!(thousands_decimal, "li pa ki'o pi mu", Ok(exact(2001, 2)));
+	make_test!(fraction, "li pa fi'u re", Ok(exact(1, 2)));
+	make_test!(fraction_without_numerator, "li fi'u ci", Ok(exact(1, 3)));
+	make_test!(negative_fraction, "li ni'u ci fi'u vo", Ok(exact(-3, 4)));
+	make_test!(repeating, "li pi ra'e ci", Ok(exact(1, 3)));
+	make_test!(
+		repeating_after_digits,
+		"li pi pa ra'e re ci",
+		Ok(exact(61, 495))
+	);
+	make_test!(percent, "li remu ce'i", Ok(exact(1, 4)));
+	make_test!(hexadecimal_digit, "li dau", Ok(exact(10, 1)));
+	make_test!(
+		approximate,
+		"li ji'i remu",
+		Ok(Value {
+			quantity: Quantity::Exact(Rational::from(25)),
+			approximate: true,
+		})
+	);
+	make_test!(
+		many,
+		"so'i lo gerku",
+		Ok(indefinite(Indefinite::Many, None))
+	);
+	make_test!(all, "ro lo gerku", Ok(indefinite(Indefinite::All, None)));
+	make_test!(
+		at_least_one,
+		"su'o lo gerku",
+		Ok(indefinite(Indefinite::AtLeast, None))
+	);
+	make_test!(
+		at_least_two,
+		"su'ore lo gerku",
+		Ok(indefinite(Indefinite::AtLeast, Some(2)))
+	);
+	make_test!(
+		digits_after_many,
+		"li so'i re",
+		Err(Error::Unexpected(Span::new(8, 10)))
+	);
+	make_test!(lerfu, "li pa by", Err(Error::Lerfu(Span::new(6, 8))));
+	make_test!(pai, "li pai", Err(Error::Unsupported(Span::new(3, 6))));
+	make_test!(
+		two_decimal_points,
+		"li pa pi re pi ci",
+		Err(Error::Unexpected(Span::new(12, 14)))
+	);
+	make_test!(
+		incomplete,
+		"li pa fi'u",
+		Err(Error::Incomplete(Span::new(3, 10)))
+	);
+	make_test!(
+		zero_denominator,
+		"li pa fi'u no",
+		Err(Error::DivisionByZero(Span::new(3, 13)))
+	);
+	make_test!(
+		overflow,
+		"li pa no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no no",
+		Err(Error::Overflow(Span::new(3, 125)))
+	);
+	make_test!(apostrophe_as_h, "li nihu ci", Ok(exact(-3, 1)));
+
+	#[test]
+	fn rational() {
+		let half = Rational::new(2, -4).unwrap();
+		assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+		assert_eq!(half.to_string(), "-1/2");
+		assert_eq!(Rational::from(3).to_string(), "3");
+		assert_eq!(Rational::new(1, 0), None);
+		assert_eq!(half.checked_add(Rational::from(1)), Rational::new(1, 2));
+	}
+
+	/// Spell a number and check that it evaluates back to the same value, after `li`, or after `mi` if it has a MOI suffix.
+	macro_rules! make_spell_test {
+		($name:ident, $numerator:expr, $denominator:expr, $spelling:expr, $expected:literal) => {
+			#[test]
+			fn $name() {
+				let value = Rational::new($numerator, $denominator).unwrap();
+				let spelling: Spelling = $spelling;
+				let spelled = spell(value, spelling);
+				assert_eq!(spelled, $expected);
+				let prefix = if spelling.suffix.is_some() {
+					"mi"
+				} else {
+					"li"
+				};
+				assert_eq!(
+					evaluate_first(&format!("{prefix} {spelled}")),
+					Ok(exact($numerator, $denominator))
+				);
+			}
+		};
+	}
+
+	const THOUSANDS: Spelling = Spelling {
+		thousands: true,
+		fractions: Fractions::Decimal,
+		suffix: None,
+	};
+
+	make_spell_test!(spell_zero, 0, 1, Spelling::default(), "no");
+	make_spell_test!(spell_integer, 1234, 1, Spelling::default(), "parecivo");
+	make_spell_test!(spell_negative, -25, 1, Spelling::default(), "ni'uremu");
+	make_spell_test!(spell_thousands, 1234, 1, THOUSANDS, "paki'orecivo");
+	make_spell_test!(
+		spell_thousands_padded,
+		1_000_002,
+		1,
+		THOUSANDS,
+		"paki'oki'ore"
+	);
+	make_spell_test!(spell_thousands_short, 999, 1, THOUSANDS, "sososo");
+	make_spell_test!(spell_decimal, 5, 2, Spelling::default(), "repimu");
+	make_spell_test!(
+		spell_decimal_below_one,
+		1,
+		8,
+		Spelling::default(),
+		"nopiparemu"
+	);
+	make_spell_test!(spell_repeating, 1, 3, Spelling::default(), "nopira'eci");
+	make_spell_test!(
+		spell_repeating_after_digits,
+		61,
+		495,
+		Spelling::default(),
+		"nopipara'ereci"
+	);
+	make_spell_test!(spell_thousands_decimal, 2001, 2, THOUSANDS, "paki'opimu");
+	make_spell_test!(
+		spell_fraction,
+		-3,
+		4,
+		Spelling {
+			fractions: Fractions::Bar,
+			..Spelling::default()
+		},
+		"ni'ucifi'uvo"
+	);
+	make_spell_test!(
+		spell_long_repeating,
+		1,
+		1_000_000_007,
+		Spelling::default(),
+		"pafi'upanonononononononoze"
+	);
+	make_spell_test!(
+		spell_moi,
+		1,
+		1,
+		Spelling {
+			suffix: Some(Suffix::Moi),
+			..Spelling::default()
+		},
+		"pamoi"
+	);
+	make_spell_test!(
+		spell_mei,
+		2,
+		1,
+		Spelling {
+			suffix: Some(Suffix::Mei),
+			..Spelling::default()
+		},
+		"remei"
+	);
+
+	#[test]
+	fn spell_round_trips() {
+		let spellings = [
+			Spelling::default(),
+			THOUSANDS,
+			Spelling {
+				fractions: Fractions::Bar,
+				..THOUSANDS
+			},
+		];
+		for numerator in [
+			-1_000_001,
+			-7,
+			0,
+			1,
+			10,
+			999,
+			1000,
+			1001,
+			123_456_789,
+			i128::MAX,
+			i128::MIN,
+		] {
+			for denominator in [1, 2, 3, 7, 12, 1000, 1024] {
+				let value = Rational::new(numerator, denominator).unwrap();
+				for spelling in spellings {
+					let spelled = spell(value, spelling);
+					assert_eq!(
+						evaluate_first(&format!("li {spelled}")).map(|value| value.exact()),
+						Ok(Some(value)),
+						"{spelled}"
+					);
+				}
+			}
+		}
+	}
+}
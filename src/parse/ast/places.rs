@@ -0,0 +1,507 @@
+//! Which sumti fill which places of a selbri.
+//!
+//! Sumti fill places in order, starting from the first place before the selbri and the second after it if none came before.
+//! A `FA` tag such as `fe` fills the place it names, and the sumti after it continue from there.
+//! `SE` and `jai` conversions change which place of the selbri each of these is, and `be` and `bei` fill places of a tanru unit from inside the selbri.
+//! [`resolve`] undoes all of this, giving the places of the unit at the head of the selbri, such as `klama` in `mi se klama be do`.
+//!
+//! ```rust
+//! use sneturfahi::parse::ast;
+//! use sneturfahi::parse::ast::places::{self, Place};
+//!
+//! let input = "le zarci cu se klama mi";
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::parse_in(input, &arena).unwrap();
+//! let text = ast::lower(&cst, input);
+//!
+//! let maps = places::resolve(&text.paragraphs[0].sentences[0]);
+//! let places = maps[0]
+//! 	.fillers
+//! 	.iter()
+//! 	.map(|filler| (filler.place, filler.span.slice(input).unwrap()))
+//! 	.collect::<Vec<_>>();
+//! assert_eq!(
+//! 	places,
+//! 	[(Place::Numbered(2), "le zarci"), (Place::Numbered(1), "mi")]
+//! );
+//! assert!(maps[0].conflicts.is_empty());
+//! ```
+
+use super::{
+	Bridi, BridiTail, Conversion, Paragraph, Selbri, Sentence, Statement, Tanru, TanruUnit,
+	TanruUnitKind, Term, Word,
+};
+use crate::span::Span;
+
+/// Resolve the places of every bridi in a sentence, with one [`PlaceMap`] for each selbri.
+///
+/// A bridi with several selbri joined by `GIhA` or forethought connectives has a map for each, sharing the terms that apply to all of them.
+/// Bridi nested inside the sentence, such as in abstractions and relative clauses, are not included; use [`resolve_bridi`] for them.
+#[must_use]
+pub fn resolve(sentence: &Sentence) -> Vec<PlaceMap> {
+	let mut maps = Vec::new();
+	statement(&sentence.statement, &[], &[], &mut maps);
+	maps
+}
+
+/// Resolve the places of a single bridi, with one [`PlaceMap`] for each selbri.
+#[must_use]
+pub fn resolve_bridi(bridi: &Bridi) -> Vec<PlaceMap> {
+	let mut maps = Vec::new();
+	tail(
+		&bridi.tail,
+		&bridi.head.iter().collect::<Vec<_>>(),
+		&[],
+		&mut maps,
+	);
+	maps
+}
+
+/// The places of one selbri and the sumti that fill them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceMap {
+	/// The span of the selbri.
+	pub selbri: Span,
+	/// The span of the tanru unit at the head of the selbri, whose places are given.
+	/// For a unit grouped with `ke`, this is the innermost unit at the head of the group.
+	pub head: Span,
+	/// The word at the head, if the head unit is a single brivla, `zei` word or pro-bridi, such as `klama` in `se klama be do`.
+	pub word: Option<Word>,
+	/// The sumti that fill places, in the order they appear in the text.
+	pub fillers: Vec<Filler>,
+	/// Places that are filled more than once, and other problems with the place structure.
+	pub conflicts: Vec<Conflict>,
+}
+
+impl PlaceMap {
+	/// The sumti that fill `place`, usually only one.
+	pub fn filling(&self, place: Place) -> impl Iterator<Item = &Filler> {
+		self
+			.fillers
+			.iter()
+			.filter(move |filler| filler.place == place)
+	}
+}
+
+/// A sumti that fills a place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Filler {
+	/// The place it fills.
+	pub place: Place,
+	/// The span of the sumti.
+	pub span: Span,
+	/// The span of the `FA` word before the sumti, if any.
+	pub tag: Option<Span>,
+	/// Whether the sumti is bound to the selbri with `be` or `bei`.
+	pub bound: bool,
+}
+
+/// A place of a selbri.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+	/// A numbered place of the head unit before any conversion, starting from 1.
+	Numbered(usize),
+	/// The first place made by the `jai` at the given span: the tagged place with a tag, as in `jai gau`, or the abstraction of the bridi without one.
+	Jai(Span),
+	/// The place asked about with `fi'a`.
+	Question,
+}
+
+/// A problem with the place structure of a bridi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+	/// A place filled by two sumti, not counting the two sides of a connective.
+	Repeated {
+		/// The place.
+		place: Place,
+		/// The span of the sumti that filled it first.
+		first: Span,
+		/// The span of the sumti that filled it again.
+		second: Span,
+	},
+	/// `fai` in a bridi whose selbri has no `jai`, so that there is no place for it to fill.
+	Fai(Span),
+}
+
+fn statement<'ast>(
+	statement: &'ast Statement,
+	head: &[&'ast Term],
+	shared: &[&'ast Term],
+	maps: &mut Vec<PlaceMap>,
+) {
+	match statement {
+		Statement::Bridi(bridi) => {
+			let head = head.iter().copied().chain(&bridi.head).collect::<Vec<_>>();
+			tail(&bridi.tail, &head, shared, maps);
+		}
+		Statement::Connected { left, right, .. } => {
+			self::statement(left, head, shared, maps);
+			self::statement(right, head, shared, maps);
+		}
+		Statement::Group { paragraphs, .. } => {
+			for Paragraph { sentences, .. } in paragraphs {
+				for sentence in sentences {
+					self::statement(&sentence.statement, head, shared, maps);
+				}
+			}
+		}
+		Statement::Prenex { body, .. } => self::statement(body, head, shared, maps),
+		Statement::Fragment(_) => {}
+	}
+}
+
+fn tail<'ast>(
+	tail: &'ast BridiTail,
+	head: &[&'ast Term],
+	shared: &[&'ast Term],
+	maps: &mut Vec<PlaceMap>,
+) {
+	match tail {
+		BridiTail::Selbri { selbri, terms } => {
+			let terms = terms
+				.iter()
+				.chain(shared.iter().copied())
+				.collect::<Vec<_>>();
+			maps.push(Resolver::new(selbri).resolve(head, &terms));
+		}
+		BridiTail::Connected {
+			left, right, terms, ..
+		} => {
+			let shared = terms
+				.iter()
+				.chain(shared.iter().copied())
+				.collect::<Vec<_>>();
+			self::tail(left, head, &shared, maps);
+			self::tail(right, head, &shared, maps);
+		}
+		BridiTail::Forethought {
+			first,
+			second,
+			terms,
+			..
+		} => {
+			let shared = terms
+				.iter()
+				.chain(shared.iter().copied())
+				.collect::<Vec<_>>();
+			statement(first, head, &shared, maps);
+			statement(second, head, &shared, maps);
+		}
+	}
+}
+
+/// The units at the head of a tanru, from the outermost to the innermost, which is more than one if the head is grouped with `ke`.
+///
+/// Connected tanru such as `blanu je zdani` share their places, and the first is taken as the head.
+fn head_units<'ast>(tanru: &'ast Tanru, units: &mut Vec<&'ast TanruUnit>) {
+	let unit = match tanru {
+		Tanru::Unit(unit) | Tanru::Assignment { value: unit, .. } => unit,
+		Tanru::Modified { head, .. } => return head_units(head, units),
+		Tanru::Connected { left, .. } => return head_units(left, units),
+		Tanru::Selbri(selbri) => return head_units(&selbri.tanru, units),
+	};
+	units.push(unit);
+	if let TanruUnitKind::Group(inner) = &unit.kind {
+		head_units(inner, units);
+	}
+}
+
+/// A place as it appears to a sumti, before the conversions of the selbri are undone.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Surface {
+	Numbered(usize),
+	Fai,
+	Question,
+}
+
+fn fa(word: &Word) -> Surface {
+	match word.text.as_str() {
+		"fa" => Surface::Numbered(1),
+		"fe" => Surface::Numbered(2),
+		"fi" => Surface::Numbered(3),
+		"fo" => Surface::Numbered(4),
+		"fu" => Surface::Numbered(5),
+		"fai" => Surface::Fai,
+		_ => Surface::Question,
+	}
+}
+
+struct Resolver<'ast> {
+	selbri: Span,
+	units: Vec<&'ast TanruUnit>,
+	fillers: Vec<Filler>,
+	conflicts: Vec<Conflict>,
+	/// The places filled so far and the sumti that filled them first.
+	filled: Vec<(Place, Span)>,
+}
+
+impl<'ast> Resolver<'ast> {
+	fn new(selbri: &'ast Selbri) -> Self {
+		let mut units = Vec::new();
+		head_units(&selbri.tanru, &mut units);
+		Self {
+			selbri: selbri.span,
+			units,
+			fillers: Vec::new(),
+			conflicts: Vec::new(),
+			filled: Vec::new(),
+		}
+	}
+
+	fn resolve(mut self, head: &[&Term], tail: &[&Term]) -> PlaceMap {
+		let mut next = 1;
+		for term in head {
+			next = self.term(term, next, 0, false);
+		}
+		// without sumti before the selbri, the first place is left unfilled
+		next = next.max(2);
+		for level in 0..self.units.len() {
+			let mut bound = 2;
+			for term in &self.units[level].bound_arguments {
+				bound = self.term(term, bound, level, true);
+			}
+		}
+		for term in tail {
+			next = self.term(term, next, 0, false);
+		}
+		PlaceMap {
+			selbri: self.selbri,
+			head: self.units.last().map_or(self.selbri, |unit| unit.span),
+			word: self.units.last().and_then(|unit| match &unit.kind {
+				TanruUnitKind::Brivla(word)
+				| TanruUnitKind::ProBridi { word, .. }
+				| TanruUnitKind::Zei(word) => Some(word.clone()),
+				_ => None,
+			}),
+			fillers: self.fillers,
+			conflicts: self.conflicts,
+		}
+	}
+
+	/// Fill places with `term`, counting from `next`, and return the place to count from after it.
+	///
+	/// `level` is the index of the unit in [`Self::units`] whose places are counted, which is not the outermost for terms bound with `be`.
+	fn term(&mut self, term: &Term, next: usize, level: usize, bound: bool) -> usize {
+		match term {
+			Term::Sumti { place, sumti } => {
+				let surface = match place {
+					Some(word) => fa(word),
+					None => Surface::Numbered(next),
+				};
+				self.fill(
+					surface,
+					level,
+					sumti.span,
+					place.as_ref().map(|word| word.span),
+					bound,
+				);
+				match surface {
+					Surface::Numbered(number) => number + 1,
+					Surface::Fai | Surface::Question => next,
+				}
+			}
+			Term::Termset(terms) => terms
+				.iter()
+				.fold(next, |next, term| self.term(term, next, level, bound)),
+			// both sides of a connective fill the same places, so neither conflicts with the other
+			Term::Connected { left, right, .. } => {
+				let before = self.filled.clone();
+				let after_left = self.term(left, next, level, bound);
+				let left_filled = std::mem::replace(&mut self.filled, before);
+				let after_right = self.term(right, next, level, bound);
+				for (place, span) in left_filled {
+					if !self.filled.iter().any(|(filled, _)| *filled == place) {
+						self.filled.push((place, span));
+					}
+				}
+				after_left.max(after_right)
+			}
+			Term::Tagged { .. } | Term::Negation(_) => next,
+		}
+	}
+
+	fn fill(&mut self, surface: Surface, level: usize, span: Span, tag: Option<Span>, bound: bool) {
+		let Some(place) = self.place(surface, level) else {
+			self.conflicts.push(Conflict::Fai(span));
+			return;
+		};
+		if place != Place::Question {
+			if let Some(&(_, first)) = self.filled.iter().find(|(filled, _)| *filled == place) {
+				self.conflicts.push(Conflict::Repeated {
+					place,
+					first,
+					second: span,
+				});
+			} else {
+				self.filled.push((place, span));
+			}
+		}
+		self.fillers.push(Filler {
+			place,
+			span,
+			tag,
+			bound,
+		});
+	}
+
+	/// Undo the conversions of the units from `level` inwards, outermost first, or return `None` for `fai` without `jai`.
+	fn place(&self, surface: Surface, level: usize) -> Option<Place> {
+		let mut current = surface;
+		let conversions = self.units[level..]
+			.iter()
+			.flat_map(|unit| &unit.conversions);
+		for conversion in conversions {
+			current = match (conversion, current) {
+				(Conversion::Se(se), Surface::Numbered(number)) => {
+					let swapped = match se.text.as_str() {
+						"se" => 2,
+						"te" => 3,
+						"ve" => 4,
+						"xe" => 5,
+						_ => continue,
+					};
+					Surface::Numbered(match number {
+						1 => swapped,
+						_ if number == swapped => 1,
+						_ => number,
+					})
+				}
+				(Conversion::Jai { jai, .. }, Surface::Numbered(1)) => return Some(Place::Jai(jai.span)),
+				(Conversion::Jai { .. }, Surface::Fai) => Surface::Numbered(1),
+				_ => current,
+			};
+		}
+		match current {
+			Surface::Numbered(number) => Some(Place::Numbered(number)),
+			Surface::Question => Some(Place::Question),
+			Surface::Fai => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Describe the places of the first sentence as `1:mi 2:do`, with `be` before bound sumti, `!` before repeated places, and `|` between selbri.
+	fn describe(input: &str) -> String {
+		let arena = crate::Arena::new();
+		let cst = crate::parse_in(input, &arena).unwrap();
+		let text = crate::parse::ast::lower(&cst, input);
+		let place = |place: Place| match place {
+			Place::Numbered(number) => number.to_string(),
+			Place::Jai(_) => "jai".to_owned(),
+			Place::Question => "?".to_owned(),
+		};
+		let maps = resolve(&text.paragraphs[0].sentences[0]);
+		maps
+			.iter()
+			.map(|map| {
+				let fillers = map.fillers.iter().map(|filler| {
+					let bound = if filler.bound { "be" } else { "" };
+					format!(
+						"{bound}{}:{}",
+						place(filler.place),
+						filler.span.slice(input).unwrap()
+					)
+				});
+				let conflicts = map.conflicts.iter().map(|conflict| match conflict {
+					Conflict::Repeated {
+						place: repeated, ..
+					} => format!("!{}", place(*repeated)),
+					Conflict::Fai(span) => format!("!fai:{}", span.slice(input).unwrap()),
+				});
+				fillers.chain(conflicts).collect::<Vec<_>>().join(" ")
+			})
+			.collect::<Vec<_>>()
+			.join(" | ")
+	}
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:literal) => {
+			#[test]
+			fn $name() {
+				assert_eq!(describe($input), $expected);
+			}
+		};
+	}
+
+	make_test!(positional, "mi klama le zarci", "1:mi 2:le zarci");
+	make_test!(cu, "mi cu klama le zarci", "1:mi 2:le zarci");
+	make_test!(observative, "klama le zarci", "2:le zarci");
+	make_test!(all_before, "mi le zarci cu klama", "1:mi 2:le zarci");
+	make_test!(fa, "fe do fa mi klama", "2:do 1:mi");
+	make_test!(
+		fa_continues,
+		"mi klama fi le zarci le zdani",
+		"1:mi 3:le zarci 4:le zdani"
+	);
+	make_test!(fa_before_selbri, "fe do klama mi", "2:do 3:mi");
+	make_test!(fi_a, "fi'a do klama", "?:do");
+	make_test!(se, "mi se klama do", "2:mi 1:do");
+	make_test!(te, "mi te klama do", "3:mi 2:do");
+	make_test!(se_te, "mi se te klama do", "2:mi 3:do");
+	make_test!(
+		be_bei,
+		"mi klama be le zarci bei le zdani",
+		"1:mi be2:le zarci be3:le zdani"
+	);
+	make_test!(be_fa, "mi klama be fi le zdani", "1:mi be3:le zdani");
+	make_test!(se_be, "mi se klama be do", "2:mi be1:do");
+	make_test!(modifier_be, "mi sutra be do klama", "1:mi");
+	make_test!(tanru_head, "mi sutra klama do", "1:mi 2:do");
+	make_test!(ke, "mi se ke sutra klama ke'e do", "2:mi 1:do");
+	make_test!(jai, "mi jai gau klama fai do", "jai:mi 1:do");
+	make_test!(fai_without_jai, "mi klama fai do", "1:mi !fai:do");
+	make_test!(repeated, "mi klama fa do", "1:mi 1:do !1");
+	make_test!(
+		repeated_with_be,
+		"mi klama be le zarci le zdani",
+		"1:mi be2:le zarci 2:le zdani !2"
+	);
+	make_test!(
+		giha,
+		"mi klama le zarci gi'e citka",
+		"1:mi 2:le zarci | 1:mi"
+	);
+	make_test!(
+		giha_shared,
+		"mi klama gi'e citka vau le plise",
+		"1:mi 2:le plise | 1:mi 2:le plise"
+	);
+	make_test!(
+		termset,
+		"mi klama nu'i le zarci le zdani nu'u",
+		"1:mi 2:le zarci 3:le zdani"
+	);
+	make_test!(
+		connected_termsets,
+		"mi klama nu'i ge le zarci le zdani nu'u gi le briju le ckule nu'u",
+		"1:mi 2:le zarci 3:le zdani 2:le briju 3:le ckule"
+	);
+	make_test!(tagged, "mi klama bau la lojban", "1:mi");
+	make_test!(
+		connected_sentences,
+		"mi klama .i je do citka",
+		"1:mi | 1:do"
+	);
+	make_test!(prenex, "ro da zo'u da klama", "1:da");
+	make_test!(fragment, "lo mlatu", "");
+
+	#[test]
+	fn word() {
+		let words = |input: &str| {
+			let arena = crate::Arena::new();
+			let cst = crate::parse_in(input, &arena).unwrap();
+			let text = crate::parse::ast::lower(&cst, input);
+			resolve(&text.paragraphs[0].sentences[0])
+				.into_iter()
+				.map(|map| map.word.map(|word| word.text))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(words("mi se klama be do"), [Some("klama".to_owned())]);
+		assert_eq!(words("mi sutra klama"), [Some("klama".to_owned())]);
+		assert_eq!(words("mi go'i"), [Some("go'i".to_owned())]);
+		assert_eq!(words("mi me do"), [None]);
+	}
+}
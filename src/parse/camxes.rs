@@ -0,0 +1,215 @@
+//! Output in the formats produced by camxes, the reference Lojban parser, for comparing results.
+//!
+//! [`brackets`] renders a tree as the words of the input grouped by nested brackets, optionally with elided terminators and selmaho:
+//!
+//! ```rust
+//! # use sneturfahi::parse::camxes;
+//! let input = "lo mlatu cu klama";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//! let root = cst.root();
+//! assert_eq!(
+//! 	camxes::brackets(root, cst.tokens(), input, camxes::Options::default()),
+//! 	"([lo mlatu] [cu klama])"
+//! );
+//! let options = camxes::Options {
+//! 	terminators: true,
+//! 	selmaho: true,
+//! };
+//! assert_eq!(
+//! 	camxes::brackets(root, cst.tokens(), input, options),
+//! 	"([lo:LE mlatu:gismu KU] [cu:CU {klama:gismu VAU}])"
+//! );
+//! ```
+//!
+//! [`morphology`] renders the lexer output with the morphological class of each word:
+//!
+//! ```rust
+//! # use sneturfahi::parse::camxes;
+//! let input = "mi klama la .djan. lo zdani";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! assert_eq!(
+//! 	camxes::morphology(&tokens, input),
+//! 	"mi:cmavo klama:gismu la:cmavo djan:cmevla lo:cmavo zdani:gismu"
+//! );
+//! ```
+
+use std::fmt::Write as _;
+
+use crate::lex::{Selmaho, Token};
+use crate::parse::tree_node::{Child, TreeNode};
+
+/// What to include in the output of [`brackets`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+	/// Show terminators that were elided from the input in uppercase, such as `KU` in `lo mlatu KU cu klama`.
+	pub terminators: bool,
+	/// Show the selmaho of each word after a colon, such as `lo:LE`.
+	pub selmaho: bool,
+}
+
+/// The brackets used for each level of nesting, cycling back to the start for deeper levels.
+const BRACKETS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// The children of `node` to write, leaving out empty nodes, which have no words.
+fn collect_children(node: &dyn TreeNode, options: Options) -> Vec<Child<'_>> {
+	let mut children = Vec::new();
+	node.for_each_child_or_elided(&mut |child| {
+		let keep = match child {
+			Child::Node(node) => node.start_token().is_some(),
+			Child::Elided(_) => options.terminators,
+		};
+		if keep {
+			children.push(child);
+		}
+	});
+	children
+}
+
+/// Render `root`, which was parsed from `tokens` and `input`, in the bracketed format of camxes.
+///
+/// Every node with more than one child is wrapped in brackets, and nodes with exactly one child are represented by that child.
+/// See the [module-level documentation](self) for an example.
+///
+/// # Panics
+///
+/// Panics if the tree was not parsed from `tokens` and `input`.
+#[must_use]
+pub fn brackets(root: &dyn TreeNode, tokens: &[Token], input: &str, options: Options) -> String {
+	fn write_child(
+		child: Child<'_>,
+		tokens: &[Token],
+		input: &str,
+		options: Options,
+		depth: usize,
+		out: &mut String,
+	) {
+		match child {
+			Child::Node(node) => write_node(node, tokens, input, options, depth, out),
+			Child::Elided(selmaho) => {
+				let terminator = selmaho.elidable_terminator().unwrap();
+				out.push_str(&terminator.to_uppercase());
+			}
+		}
+	}
+
+	fn write_node(
+		mut node: &dyn TreeNode,
+		tokens: &[Token],
+		input: &str,
+		options: Options,
+		depth: usize,
+		out: &mut String,
+	) {
+		let mut children = collect_children(node, options);
+		while let [Child::Node(only_child)] = children[..] {
+			node = only_child;
+			children = collect_children(node, options);
+		}
+
+		match &children[..] {
+			[] => {
+				// leaves are tokens, unless the whole node is empty
+				if let Some(span) = node.span(tokens) {
+					out.push_str(span.slice(input).unwrap());
+					if options.selmaho {
+						write!(out, ":{}", node.name()).unwrap();
+					}
+				}
+			}
+			[only_child] => write_child(*only_child, tokens, input, options, depth, out),
+			children => {
+				let (open, close) = BRACKETS[depth % BRACKETS.len()];
+				out.push(open);
+				for (idx, child) in children.iter().enumerate() {
+					if idx > 0 {
+						out.push(' ');
+					}
+					write_child(*child, tokens, input, options, depth + 1, out);
+				}
+				out.push(close);
+			}
+		}
+	}
+
+	let mut out = String::new();
+	write_node(root, tokens, input, options, 0, &mut out);
+	out
+}
+
+/// The morphological class of a token, as named by camxes.
+fn word_class(selmaho: Selmaho) -> Option<&'static str> {
+	Some(match selmaho {
+		Selmaho::Gismu => "gismu",
+		Selmaho::Lujvo => "lujvo",
+		Selmaho::Fuhivla => "fuhivla",
+		Selmaho::Cmevla => "cmevla",
+		Selmaho::AnyText | Selmaho::ZoiDelimiter => return None,
+		_ => "cmavo",
+	})
+}
+
+/// Render `tokens`, which were lexed from `input`, as words annotated with their morphological class, as with the morphology mode of camxes.
+///
+/// Quoted non-Lojban text is reproduced as-is.
+/// See the [module-level documentation](self) for an example.
+///
+/// # Panics
+///
+/// Panics if the tokens were not lexed from `input`.
+#[must_use]
+pub fn morphology(tokens: &[Token], input: &str) -> String {
+	let mut out = String::new();
+	for (idx, token) in tokens.iter().enumerate() {
+		if idx > 0 {
+			out.push(' ');
+		}
+		let text = token.span.slice(input).unwrap();
+		match word_class(token.selmaho) {
+			Some(class) => write!(out, "{}:{class}", text.trim_matches([',', '.'])).unwrap(),
+			None => out.push_str(text),
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $expected:literal, $expected_with_options:literal) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let arena = crate::Arena::new();
+				let cst = crate::parse_in(input, &arena).unwrap();
+				assert_eq!(
+					brackets(cst.root(), cst.tokens(), input, Options::default()),
+					$expected
+				);
+				let options = Options {
+					terminators: true,
+					selmaho: true,
+				};
+				assert_eq!(
+					brackets(cst.root(), cst.tokens(), input, options),
+					$expected_with_options
+				);
+			}
+		};
+	}
+
+	make_test!(vocative, "coi", "coi", "(coi:COI DO'U)");
+	make_test!(attitudinal, "ui", "ui", "ui:UI");
+	make_test!(faho, "fa'o", "fa'o", "fa'o:FAhO");
+	make_test!(experimental_vocative, "ki'ai", "ki'ai", "(ki'ai:COI DO'U)");
+	make_test!(jo_au, "jo'au", "jo'au", "(jo'au:COI DO'U)");
+	make_test!(
+		trailing_i,
+		"mi klama .i",
+		"([mi klama] i)",
+		"([mi:KOhA {klama:gismu VAU}] i:I)"
+	);
+}
@@ -7,7 +7,7 @@ use std::borrow::Cow;
 use crate::lex::{Selmaho, Token};
 
 /// Errors that can occur while parsing.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 #[non_exhaustive]
 pub enum Error {
 	/// The parser expected one of the selmaho in `expected`, but got the token in `got`.
@@ -46,7 +46,7 @@ impl Error {
 }
 
 /// An error with an embedded location, indicating where the error occurred.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WithLocation<'a> {
 	/// The location where the error occurred, stored as the tokens that were remaining when the error occurred.
 	pub location: &'a [Token],
@@ -0,0 +1,201 @@
+//! Typed rewriting of the CST into a new arena.
+//!
+//! This module centers around [`Fold`], which has a method for each rule type, and [`FoldWith`], which is implemented by every node and dispatches to the appropriate method.
+//! The default implementation of each method rebuilds the node from its folded children, so a folder only needs to override the methods for the nodes it wants to change.
+//! Tokens are all handled by [`Fold::fold_token`].
+//!
+//! For example, to drop the relative clauses from sumti:
+//!
+//! ```rust
+//! use sneturfahi::parse::cst::fold::{Fold, FoldWith as _};
+//! use sneturfahi::parse::cst::rules::Sumti4;
+//! use sneturfahi::parse::export;
+//!
+//! struct DropRelativeClauses<'new>(&'new sneturfahi::Arena);
+//!
+//! impl<'new> Fold<'new> for DropRelativeClauses<'new> {
+//! 	fn arena(&self) -> &'new sneturfahi::Arena {
+//! 		self.0
+//! 	}
+//!
+//! 	fn fold_sumti4(&mut self, sumti: &Sumti4<'_>) -> Sumti4<'new> {
+//! 		match sumti {
+//! 			Sumti4::Normal {
+//! 				quantifier, inner, ..
+//! 			} => Sumti4::Normal {
+//! 				quantifier: quantifier.fold_with(self),
+//! 				inner: inner.fold_with(self),
+//! 				relative_clauses: None,
+//! 			},
+//! 			_ => sumti.fold_children(self),
+//! 		}
+//! 	}
+//! }
+//!
+//! let input = "ko'a noi barda cu klama";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//!
+//! let new_arena = sneturfahi::Arena::new();
+//! let folded = cst.fold(&mut DropRelativeClauses(&new_arena));
+//! let options = export::Options { collapse: true };
+//! assert_eq!(
+//! 	export::sexp(folded.root(), folded.tokens(), input, options),
+//! 	r#"(Sentence (KOhA "ko'a") (SentenceTail (CU "cu") (gismu "klama")))"#
+//! );
+//! ```
+
+#![allow(clippy::wildcard_imports)]
+
+use crate::lex::Selmaho;
+use crate::parse::cst::rules::connectives::*;
+use crate::parse::cst::rules::mekso::*;
+use crate::parse::cst::rules::*;
+use crate::parse::cst::visit::with_rule_types;
+use crate::parse::Arena;
+
+/// A node, or a container of nodes, that can be rebuilt by a [`Fold`].
+pub trait FoldWith<'new> {
+	/// The type of the rebuilt node, which is the same as `Self` but with its data in the arena of the folder.
+	type Output: 'new;
+
+	/// Call the method of `folder` that corresponds to this node.
+	///
+	/// For containers such as `Option` and slices, this folds each contained node.
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output;
+
+	/// Rebuild this node by folding each of its children.
+	///
+	/// This is the default behavior of the methods of [`Fold`].
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output;
+}
+
+macro_rules! define_fold {
+	(
+		arena: [$($name:ident $(<$($param:ident),+>)?),* $(,)?],
+		no_arena: [$($no_arena_name:ident),* $(,)?],
+	) => {paste::paste! {
+		/// A typed rewrite of the CST, which rebuilds it in a new arena.
+		///
+		/// See the [module-level documentation](self) for an example.
+		pub trait Fold<'new> {
+			/// The arena to allocate the rebuilt nodes in.
+			fn arena(&self) -> &'new Arena;
+
+			$(
+				#[doc = concat!("Fold a [`", stringify!($name), "`]. By default, this rebuilds it from its folded children.")]
+				fn [<fold_ $name:snake>]$(<$($param: FoldWith<'new>),+>)?(
+					&mut self,
+					node: &$name<'_ $($(, $param)+)?>,
+				) -> $name<'new $($(, $param::Output)+)?> {
+					node.fold_children(self)
+				}
+			)*
+
+			$(
+				#[doc = concat!("Fold a [`", stringify!($no_arena_name), "`]. By default, this rebuilds it from its folded children.")]
+				fn [<fold_ $no_arena_name:snake>](&mut self, node: &$no_arena_name) -> $no_arena_name {
+					node.fold_children(self)
+				}
+			)*
+
+			/// Fold a token, such as the `KOhA` in `mi`, along with any `BAhE` before it and indicators after it.
+			/// By default, this rebuilds it with its `BAhE` and indicators folded.
+			fn fold_token<T: FoldWith<'new>>(&mut self, selmaho: Selmaho, node: &T) -> T::Output {
+				let _ = selmaho;
+				node.fold_children(self)
+			}
+		}
+	}};
+}
+
+with_rule_types!(define_fold);
+
+impl<'new, T: FoldWith<'new>> FoldWith<'new> for &T {
+	type Output = &'new T::Output;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		let arena = folder.arena();
+		arena.alloc(T::fold_with(self, folder))
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.fold_with(folder)
+	}
+}
+
+impl<'new, T: FoldWith<'new>> FoldWith<'new> for Box<T> {
+	type Output = Box<T::Output>;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		Box::new(T::fold_with(self, folder))
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.fold_with(folder)
+	}
+}
+
+impl<'new, T: FoldWith<'new>> FoldWith<'new> for Option<T> {
+	type Output = Option<T::Output>;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.as_ref().map(|inner| inner.fold_with(folder))
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.fold_with(folder)
+	}
+}
+
+impl<'new, T: FoldWith<'new>> FoldWith<'new> for &[T] {
+	type Output = &'new [T::Output];
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		let arena = folder.arena();
+		arena.alloc_slice_fill_iter(self.iter().map(|item| item.fold_with(folder)))
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.fold_with(folder)
+	}
+}
+
+impl<'new, T: FoldWith<'new>> FoldWith<'new> for Box<[T]> {
+	type Output = Box<[T::Output]>;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.iter().map(|item| item.fold_with(folder)).collect()
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.fold_with(folder)
+	}
+}
+
+macro_rules! tuple_impls {
+	// base case
+	() => {};
+	(@single $($idents:ident),*) => {
+		#[allow(non_snake_case)]
+		impl<'new, $($idents: FoldWith<'new>),*> FoldWith<'new> for ($($idents,)*) {
+			type Output = ($($idents::Output,)*);
+
+			fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+				let ($($idents,)*) = self;
+				($($idents.fold_with(folder),)*)
+			}
+
+			fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+				self.fold_with(folder)
+			}
+		}
+	};
+	($first:ident $(, $idents:ident)*) => {
+		tuple_impls!(@single $first $(, $idents)*);
+		tuple_impls!($($idents),*);
+	};
+}
+
+tuple_impls![T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15];
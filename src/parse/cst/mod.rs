@@ -1,18 +1,34 @@
 use crate::lex::Token;
+use crate::parse::tree_node::TreeNode;
 use crate::parse::Arena;
 
 pub mod error;
+pub mod fold;
+pub mod node;
 mod parse_trait;
 pub mod rules;
+pub mod visit;
 
 pub use error::Error;
 
 #[cfg(test)]
 mod tests;
 
+/// A grammar rule that can be used as the start symbol for parsing, with [`Cst::parse_as`].
+///
+/// This is implemented for all of the node types in [`rules`] and cannot be implemented outside of this crate.
+pub trait Rule<'arena>: TreeNode + parse_trait::Parse<'arena> {}
+
+impl<'arena, T: TreeNode + parse_trait::Parse<'arena>> Rule<'arena> for T {}
+
+/// A concrete syntax tree, whose root is the rule `R`.
+///
+/// By default the root is the rule for an entire text, but trees for other rules can be parsed with [`Cst::parse_as`].
+/// Tokens in the tree are stored as [`TokenIndex`](rules::TokenIndex)es into the tokens it was parsed from, which are kept as [`Cst::tokens`].
 #[derive(Debug)]
-pub struct Cst<'arena> {
-	root: &'arena rules::Root<'arena>,
+pub struct Cst<'arena, R = rules::Root<'arena>> {
+	root: &'arena R,
+	tokens: &'arena [Token],
 }
 
 impl<'arena> Cst<'arena> {
@@ -22,21 +38,124 @@ impl<'arena> Cst<'arena> {
 		input: &'a [Token],
 		arena: &'arena Arena,
 	) -> Result<Self, error::WithLocation<'a>> {
+		Self::parse_as(input, arena)
+	}
+
+	/// Parse tokens into a concrete syntax tree whose root is the rule `R` rather than an entire text.
+	///
+	/// All of the tokens must be consumed by the rule.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use sneturfahi::parse::cst::rules;
+	/// # use sneturfahi::parse::tree_node::TreeNode as _;
+	/// let tokens: Vec<_> = sneturfahi::lex("lo nu mi klama")
+	/// 	.collect::<Result<_, _>>()
+	/// 	.unwrap();
+	/// let arena = sneturfahi::Arena::new();
+	/// let sumti = sneturfahi::Cst::parse_as::<rules::Sumti>(&tokens, &arena).unwrap();
+	/// assert_eq!(sumti.root().name(), "Sumti");
+	///
+	/// let tokens: Vec<_> = sneturfahi::lex("pu ba").collect::<Result<_, _>>().unwrap();
+	/// assert!(sneturfahi::Cst::parse_as::<rules::TagWords>(&tokens, &arena).is_ok());
+	/// assert!(sneturfahi::Cst::parse_as::<rules::Sumti>(&tokens, &arena).is_err());
+	/// ```
+	#[allow(clippy::missing_errors_doc)] // obvious
+	pub fn parse_as<'a: 'arena, R: Rule<'arena>>(
+		input: &'a [Token],
+		arena: &'arena Arena,
+	) -> Result<Cst<'arena, R>, error::WithLocation<'a>> {
+		Self::parse_with_context(input, &parse_trait::Context::new(arena, input.len(), false))
+	}
+
+	/// Parse tokens into a concrete syntax tree like [`Cst::parse`], remembering the result of parsing some rules at each position so that they are not parsed again when backtracking.
+	///
+	/// This gives the same tree or error as [`Cst::parse`].
+	/// The bookkeeping costs little on ordinary texts, and it avoids the exponential time that texts with many nested sumti, relative clauses or abstractions can take otherwise.
+	/// The rules that are memoized are those marked with `#[parse(memoize)]`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// let text = format!("mi klama {}brode", "lo broda poi ".repeat(12));
+	/// let tokens: Vec<_> = sneturfahi::lex(&text).collect::<Result<_, _>>().unwrap();
+	/// let arena = sneturfahi::Arena::new();
+	/// assert!(sneturfahi::Cst::parse_memoized(&tokens, &arena).is_err());
+	/// ```
+	#[allow(clippy::missing_errors_doc)] // obvious
+	pub fn parse_memoized<'a: 'arena>(
+		input: &'a [Token],
+		arena: &'arena Arena,
+	) -> Result<Self, error::WithLocation<'a>> {
+		Self::parse_as_memoized(input, arena)
+	}
+
+	/// Parse tokens into a concrete syntax tree whose root is the rule `R`, with memoization like [`Cst::parse_memoized`].
+	#[allow(clippy::missing_errors_doc)] // obvious
+	pub fn parse_as_memoized<'a: 'arena, R: Rule<'arena>>(
+		input: &'a [Token],
+		arena: &'arena Arena,
+	) -> Result<Cst<'arena, R>, error::WithLocation<'a>> {
+		Self::parse_with_context(input, &parse_trait::Context::new(arena, input.len(), true))
+	}
+
+	fn parse_with_context<'a: 'arena, R: Rule<'arena>>(
+		input: &'a [Token],
+		context: &parse_trait::Context<'arena>,
+	) -> Result<Cst<'arena, R>, error::WithLocation<'a>> {
 		let parsed = nom::Finish::finish(nom::combinator::all_consuming(|input| {
-			<rules::Root<'arena> as parse_trait::Parse>::parse(input, &arena.0)
+			<R as parse_trait::Parse>::parse(input, context)
 		})(input))
 		.map(|(rest, root)| {
 			debug_assert!(rest.is_empty());
 			root
 		});
-		parsed.map(|root| Self {
-			root: arena.0.alloc(root),
+		parsed.map(|root| Cst {
+			root: context.arena.alloc(root),
+			tokens: input,
 		})
 	}
+}
 
+impl<'arena, R> Cst<'arena, R> {
 	/// Get the root of the CST, which allows traversing the entire CST.
 	#[must_use]
-	pub fn root(&self) -> &'arena rules::Root<'arena> {
+	pub fn root(&self) -> &'arena R {
 		self.root
 	}
+
+	/// Get the tokens that the CST was parsed from, which the [`TokenIndex`](rules::TokenIndex)es in it refer to.
+	///
+	/// These are needed to find the [span](TreeNode::span) of a node.
+	#[must_use]
+	pub fn tokens(&self) -> &'arena [Token] {
+		self.tokens
+	}
+
+	/// Traverse the CST with `visitor`, starting at the root.
+	///
+	/// See the [`visit`] module for more information.
+	pub fn visit<V: visit::Visitor<'arena> + ?Sized>(&self, visitor: &mut V)
+	where
+		R: visit::Visit<'arena>,
+	{
+		self.root.visit(visitor);
+	}
+
+	/// Rebuild the CST with `folder`, starting at the root.
+	/// The rebuilt CST refers to the same tokens.
+	///
+	/// See the [`fold`] module for more information.
+	pub fn fold<'new, F: fold::Fold<'new> + ?Sized>(&self, folder: &mut F) -> Cst<'new, R::Output>
+	where
+		'arena: 'new,
+		R: fold::FoldWith<'new>,
+	{
+		let arena = folder.arena();
+		Cst {
+			root: arena.alloc(self.root.fold_with(folder)),
+			tokens: self.tokens,
+		}
+	}
 }
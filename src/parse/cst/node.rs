@@ -0,0 +1,175 @@
+//! The kinds of nodes in the CST, for switching from generic code over [`TreeNode`]s into typed code.
+//!
+//! [`TreeNode::kind`] identifies the type of a node, and [`TreeNode::downcast`] gets a reference to it as that type:
+//!
+//! ```rust
+//! use sneturfahi::parse::cst::node::{NodeKind, NodeRef};
+//! use sneturfahi::parse::tree_node::TreeNode;
+//!
+//! fn find_gadri_sumti<'a>(node: &'a dyn TreeNode, found: &mut Vec<&'a dyn TreeNode>) {
+//! 	if node.kind() == NodeKind::GadriSumti {
+//! 		found.push(node);
+//! 	}
+//! 	node.for_each_child(&mut |child| find_gadri_sumti(child, found));
+//! }
+//!
+//! let tokens: Vec<_> = sneturfahi::lex("mi klama lo zdani ku")
+//! 	.collect::<Result<_, _>>()
+//! 	.unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//! let mut found = Vec::new();
+//! find_gadri_sumti(cst.root(), &mut found);
+//! assert_eq!(found.len(), 1);
+//!
+//! let NodeRef::GadriSumti(sumti) = found[0].downcast() else {
+//! 	unreachable!();
+//! };
+//! assert!(sumti.ku.is_some());
+//! ```
+//!
+//! Generic rule types such as [`Separated`] cannot be named without their type parameters, so their variants of [`NodeRef`] hold a `&dyn TreeNode`.
+
+#![allow(clippy::wildcard_imports)]
+
+use crate::lex::Selmaho;
+use crate::parse::cst::rules::connectives::*;
+use crate::parse::cst::rules::mekso::*;
+use crate::parse::cst::rules::selmaho::{with_token_types, *};
+use crate::parse::cst::rules::*;
+use crate::parse::cst::visit::with_rule_types;
+use crate::parse::owned::OwnedNode;
+use crate::parse::tree_node::TreeNode;
+
+/// The type of the reference to a rule type in [`NodeRef`].
+macro_rules! rule_ref_type {
+	($lifetime:lifetime, $name:ident) => {
+		&$lifetime $name<$lifetime>
+	};
+	($lifetime:lifetime, $name:ident<$($param:ident),+>) => {
+		&$lifetime dyn TreeNode
+	};
+}
+
+/// The type of the reference to a token type in [`NodeRef`].
+macro_rules! token_ref_type {
+	($lifetime:lifetime, #[raw] $name:ident) => {
+		&$lifetime $name
+	};
+	($lifetime:lifetime, $(#[$attr:ident])? $name:ident) => {
+		&$lifetime $name<$lifetime>
+	};
+}
+
+macro_rules! define_nodes {
+	(
+		arena: [$($name:ident $(<$($param:ident),+>)?),* $(,)?],
+		no_arena: [$($no_arena_name:ident),* $(,)?],
+		$($(#[$token_attr:ident])? $token_name:ident),* $(,)?
+	) => {
+		/// The kind of a node in the CST, as returned by [`TreeNode::kind`].
+		///
+		/// There is a variant for each rule type and each token type, as well as for the few other types that can appear in the tree.
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		pub enum NodeKind {
+			$(
+				#[doc = concat!("A [`", stringify!($name), "`].")]
+				$name,
+			)*
+			$(
+				#[doc = concat!("A [`", stringify!($no_arena_name), "`].")]
+				$no_arena_name,
+			)*
+			$(
+				#[doc = concat!("A [`", stringify!($token_name), "`] token.")]
+				$token_name,
+			)*
+			/// A [`Bu`] token.
+			Bu,
+			/// A [`ZoiDelimiter`].
+			ZoiDelimiter,
+			/// A [`ZoiText`].
+			ZoiText,
+			/// An [`AnyToken`], which can be a token of any selmaho.
+			Token,
+			/// The inner part of a token type, without its `BAhE` or indicators, such as [`KohaInner`].
+			TokenInner(Selmaho),
+		}
+
+		/// A reference to a node in the CST as its concrete type, as returned by [`TreeNode::downcast`].
+		///
+		/// The variants correspond to those of [`NodeKind`], except for [`NodeRef::Owned`], which refers to an [`OwnedNode`] of any kind.
+		#[derive(Clone, Copy)]
+		pub enum NodeRef<'a> {
+			$(
+				#[doc = concat!("A [`", stringify!($name), "`].")]
+				$name(rule_ref_type!('a, $name $(<$($param),+>)?)),
+			)*
+			$(
+				#[doc = concat!("A [`", stringify!($no_arena_name), "`].")]
+				$no_arena_name(&'a $no_arena_name),
+			)*
+			$(
+				#[doc = concat!("A [`", stringify!($token_name), "`] token.")]
+				$token_name(token_ref_type!('a, $(#[$token_attr])? $token_name)),
+			)*
+			/// A [`Bu`] token.
+			Bu(&'a Bu),
+			/// A [`ZoiDelimiter`].
+			ZoiDelimiter(&'a ZoiDelimiter),
+			/// A [`ZoiText`].
+			ZoiText(&'a ZoiText),
+			/// An [`AnyToken`], which can be a token of any selmaho.
+			Token(&'a AnyToken),
+			/// The inner part of a token type, without its `BAhE` or indicators, such as [`KohaInner`].
+			TokenInner(Selmaho, &'a dyn TreeNode),
+			/// A node that was copied out of the CST into an owned tree, which keeps its kind but not its concrete type.
+			Owned(&'a OwnedNode),
+		}
+
+		impl<'a> NodeRef<'a> {
+			/// Get the kind of the referenced node.
+			#[must_use]
+			pub fn kind(&self) -> NodeKind {
+				match self {
+					$(Self::$name(..) => NodeKind::$name,)*
+					$(Self::$no_arena_name(..) => NodeKind::$no_arena_name,)*
+					$(Self::$token_name(..) => NodeKind::$token_name,)*
+					Self::Bu(..) => NodeKind::Bu,
+					Self::ZoiDelimiter(..) => NodeKind::ZoiDelimiter,
+					Self::ZoiText(..) => NodeKind::ZoiText,
+					Self::Token(..) => NodeKind::Token,
+					Self::TokenInner(selmaho, ..) => NodeKind::TokenInner(*selmaho),
+					Self::Owned(node) => node.kind,
+				}
+			}
+
+			/// Get the referenced node as a [`TreeNode`].
+			#[must_use]
+			pub fn as_tree_node(self) -> &'a dyn TreeNode {
+				match self {
+					$(Self::$name(node) => node,)*
+					$(Self::$no_arena_name(node) => node,)*
+					$(Self::$token_name(node) => node,)*
+					Self::Bu(node) => node,
+					Self::ZoiDelimiter(node) => node,
+					Self::ZoiText(node) => node,
+					Self::Token(node) => node,
+					Self::TokenInner(_, node) => node,
+					Self::Owned(node) => node,
+				}
+			}
+		}
+	};
+}
+
+with_rule_types!(with_token_types define_nodes);
+
+impl std::fmt::Debug for NodeRef<'_> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		formatter
+			.debug_tuple("NodeRef")
+			.field(&self.kind())
+			.finish()
+	}
+}
@@ -1,45 +1,158 @@
-use bumpalo::Bump as Arena;
+use std::cell::RefCell;
+use std::collections::HashMap;
 
 use crate::lex::Token;
 use crate::parse::cst::error::{Error, WithLocation};
+use crate::parse::cst::node::{NodeKind, NodeRef};
+use crate::parse::cst::rules::selmaho::AnyToken;
+use crate::parse::cst::rules::TokenIndex;
+use crate::parse::Arena;
 
 pub(in crate::parse::cst) type Result<'a, T> = nom::IResult<&'a [Token], T, WithLocation<'a>>;
 
-pub(in crate::parse::cst) trait Parse<'arena>: Sized {
-	fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> Result<'a, Self>;
+/// The outcome of parsing a memoized rule at some position, with the remaining tokens and error locations stored as the number of tokens left.
+type Memoized<'arena> = std::result::Result<(usize, NodeRef<'arena>), nom::Err<(usize, Error)>>;
+
+/// What the parsers of every rule need besides their input: the arena to allocate nodes in and, if enabled, the memo of rules that were already parsed.
+pub struct Context<'arena> {
+	pub(in crate::parse::cst) arena: &'arena Arena,
+	/// The number of tokens being parsed, which gives the index of the next token from the number of tokens left.
+	len: usize,
+	/// The results of the rules marked `#[parse(memoize)]`, keyed by the rule and the number of tokens left where it was parsed.
+	///
+	/// This is only valid for a single token slice, so a context must not be reused across parses.
+	memo: Option<RefCell<HashMap<(NodeKind, usize), Memoized<'arena>>>>,
+}
+
+impl std::fmt::Debug for Context<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Context")
+			.field("arena", &self.arena)
+			.field("len", &self.len)
+			.field(
+				"memoized",
+				&self.memo.as_ref().map(|memo| memo.borrow().len()),
+			)
+			.finish()
+	}
+}
+
+impl<'arena> Context<'arena> {
+	pub(in crate::parse::cst) fn new(arena: &'arena Arena, len: usize, memoize: bool) -> Self {
+		Self {
+			arena,
+			len,
+			memo: memoize.then(RefCell::default),
+		}
+	}
+
+	/// The index of the first token of `input`, which must not be empty.
+	pub(in crate::parse::cst) fn token_index(&self, input: &[Token]) -> TokenIndex {
+		TokenIndex::new(self.len - input.len(), input[0].experimental)
+	}
+
+	/// Parse the rule of kind `kind` at `input` and allocate it, or reuse the result of doing so earlier.
+	///
+	/// `to_node` and `from_node` convert between the rule type and a [`NodeRef`], which is how the results of all rules are stored in one memo.
+	pub(in crate::parse::cst) fn memoized<'a: 'arena, T>(
+		&self,
+		input: &'a [Token],
+		kind: NodeKind,
+		to_node: fn(&'arena T) -> NodeRef<'arena>,
+		from_node: fn(NodeRef<'arena>) -> Option<&'arena T>,
+		parser: impl FnOnce(&'a [Token]) -> Result<'a, T>,
+	) -> Result<'a, &'arena T> {
+		let Some(memo) = &self.memo else {
+			return parse_ref(input, self.arena, parser);
+		};
+
+		let key = (kind, input.len());
+		let suffix = |len: usize| &input[input.len() - len..];
+		if let Some(memoized) = memo.borrow().get(&key) {
+			return match memoized {
+				Ok((rest, node)) => Ok((
+					suffix(*rest),
+					from_node(*node).expect("memoized node has the kind of its key"),
+				)),
+				Err(error) => Err(
+					error
+						.clone()
+						.map(|(location, error)| error.with_location(suffix(location))),
+				),
+			};
+		}
+
+		let result = parse_ref(input, self.arena, parser);
+		let memoized = match &result {
+			Ok((rest, parsed)) => Ok((rest.len(), to_node(parsed))),
+			Err(error) => Err(
+				error
+					.clone()
+					.map(|error| (error.location.len(), error.error)),
+			),
+		};
+		memo.borrow_mut().insert(key, memoized);
+		result
+	}
+}
+
+/// Run `parser` and allocate what it parsed in `arena`.
+fn parse_ref<'a: 'arena, 'arena, T>(
+	input: &'a [Token],
+	arena: &'arena Arena,
+	parser: impl FnOnce(&'a [Token]) -> Result<'a, T>,
+) -> Result<'a, &'arena T> {
+	parser(input).map(|(rest, parsed)| (rest, &*arena.alloc(parsed)))
+}
+
+// `pub` within a private module so that it can bound the public `Rule` trait without being nameable outside this crate.
+pub trait Parse<'arena>: Sized {
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> Result<'a, Self>;
+
+	/// Parse this rule and allocate it in the arena, which rules marked `#[parse(memoize)]` override to go through the memo.
+	fn parse_ref<'a: 'arena>(
+		input: &'a [Token],
+		context: &Context<'arena>,
+	) -> Result<'a, &'arena Self> {
+		parse_ref(input, context.arena, |input| Self::parse(input, context))
+	}
 }
 
 impl<'arena, T: Parse<'arena>> Parse<'arena> for Option<T> {
-	fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> Result<'a, Self> {
-		nom::combinator::opt(|input| Parse::parse(input, arena))(input)
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> Result<'a, Self> {
+		nom::combinator::opt(|input| Parse::parse(input, context))(input)
 	}
 }
 
 impl<'arena, T: Parse<'arena>> Parse<'arena> for &'arena T {
-	fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> Result<'a, Self> {
-		nom::combinator::map(
-			|input| Parse::parse(input, arena),
-			|parsed| &*arena.alloc(parsed),
-		)(input)
-	}
-}
-
-impl<'arena> Parse<'arena> for Token {
-	fn parse<'a: 'arena>(input: &'a [Token], _: &'arena Arena) -> Result<'a, Self> {
-		let mut input = input.iter();
-		input
-			.next()
-			.map(|&token| (input.as_slice(), token))
-			.ok_or(nom::Err::Error(WithLocation {
-				location: input.as_slice(),
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> Result<'a, Self> {
+		T::parse_ref(input, context)
+	}
+}
+
+impl<'arena> Parse<'arena> for TokenIndex {
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> Result<'a, Self> {
+		match input {
+			[] => Err(nom::Err::Error(WithLocation {
+				location: input,
 				error: Error::Nom(nom::error::ErrorKind::Eof),
-			}))
+			})),
+			[_, rest @ ..] => Ok((rest, context.token_index(input))),
+		}
 	}
 }
 
-impl<'arena> Parse<'arena> for crate::Span {
-	fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> Result<'a, Self> {
-		Token::parse(input, arena).map(|(rest, matched)| (rest, matched.span))
+impl<'arena> Parse<'arena> for AnyToken {
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> Result<'a, Self> {
+		TokenIndex::parse(input, context).map(|(rest, token)| {
+			(
+				rest,
+				Self {
+					selmaho: input[0].selmaho,
+					token,
+				},
+			)
+		})
 	}
 }
 
@@ -48,8 +161,8 @@ macro_rules! tuple_impls {
 	() => {};
 	(@single $($idents:ident),*) => {
 		impl<'arena, $($idents: Parse<'arena>),*> Parse<'arena> for ($($idents,)*) {
-			fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> Result<'a, Self> {
-				nom::sequence::tuple(($(|input| <$idents as Parse>::parse(input, arena),)*))(input)
+			fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> Result<'a, Self> {
+				nom::sequence::tuple(($(|input| <$idents as Parse>::parse(input, context),)*))(input)
 			}
 		}
 	};
@@ -4,10 +4,10 @@ use super::{Bihi, Frees, Ga, Gaho, Gi, Giha, Guha, Ja, Joi, Na, Nai, Parse, Se,
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct NaSeTNai<'arena, T>(
-	pub Option<Na<'arena>>,
-	pub Option<Se<'arena>>,
-	pub T,
-	pub Option<Nai<'arena>>,
+	#[tree_node(label = "na")] pub Option<Na<'arena>>,
+	#[tree_node(label = "se")] pub Option<Se<'arena>>,
+	#[tree_node(label = "connective")] pub T,
+	#[tree_node(label = "nai")] pub Option<Nai<'arena>>,
 );
 
 macro_rules! _ks {
@@ -15,7 +15,7 @@ macro_rules! _ks {
 		$(
 			#[derive(Debug, Parse, TreeNode)]
 			#[repr(transparent)]
-			pub struct $name<'arena>(NaSeTNai<'arena, $inner<'arena>>);
+			pub struct $name<'arena>(pub NaSeTNai<'arena, $inner<'arena>>);
 		)*
 	}
 }
@@ -24,39 +24,60 @@ _ks![Ek(A), Jek(Ja), Gihek(Giha)];
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Guhek<'arena>(
-	pub Option<Se<'arena>>,
-	pub Guha<'arena>,
-	pub Option<Nai<'arena>>,
-	pub Frees<'arena>,
+	#[tree_node(label = "se")] pub Option<Se<'arena>>,
+	#[tree_node(label = "guha")] pub Guha<'arena>,
+	#[tree_node(label = "nai")] pub Option<Nai<'arena>>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Interval<'arena>(
-	pub Option<Se<'arena>>,
-	pub Bihi<'arena>,
-	pub Option<Nai<'arena>>,
+	#[tree_node(label = "se")] pub Option<Se<'arena>>,
+	#[tree_node(label = "bihi")] pub Bihi<'arena>,
+	#[tree_node(label = "nai")] pub Option<Nai<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Joik<'arena> {
-	SeJoiNai(Option<Se<'arena>>, Joi<'arena>, Option<Nai<'arena>>),
+	SeJoiNai(
+		#[tree_node(label = "se")] Option<Se<'arena>>,
+		#[tree_node(label = "joi")] Joi<'arena>,
+		#[tree_node(label = "nai")] Option<Nai<'arena>>,
+	),
 	Interval(Interval<'arena>),
-	Gaho(Gaho<'arena>, Interval<'arena>, #[cut] Gaho<'arena>),
+	Gaho(
+		#[tree_node(label = "start")] Gaho<'arena>,
+		#[tree_node(label = "interval")] Interval<'arena>,
+		#[cut]
+		#[tree_node(label = "end")]
+		Gaho<'arena>,
+	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct Gik<'arena>(Gi<'arena>, Option<Nai<'arena>>, Frees<'arena>);
+pub struct Gik<'arena>(
+	#[tree_node(label = "gi")] pub Gi<'arena>,
+	#[tree_node(label = "nai")] pub Option<Nai<'arena>>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Gek<'arena> {
 	SeGaNai(
-		Option<Se<'arena>>,
-		Ga<'arena>,
-		Option<Nai<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "se")] Option<Se<'arena>>,
+		#[tree_node(label = "ga")] Ga<'arena>,
+		#[tree_node(label = "nai")] Option<Nai<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
+	JoikGi(
+		#[tree_node(label = "joik")] Joik<'arena>,
+		#[tree_node(label = "gi")] Gi<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
+	TagGik(
+		#[tree_node(label = "tag")] TagWords<'arena>,
+		#[tree_node(label = "gik")] Gik<'arena>,
 	),
-	JoikGi(Joik<'arena>, Gi<'arena>, Frees<'arena>),
-	TagGik(TagWords<'arena>, Gik<'arena>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
@@ -67,6 +88,12 @@ pub enum JoikJek<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum JoikEk<'arena> {
-	Joik(Joik<'arena>, Frees<'arena>),
-	Ek(Ek<'arena>, Frees<'arena>),
+	Joik(
+		#[tree_node(label = "joik")] Joik<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
+	Ek(
+		#[tree_node(label = "ek")] Ek<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
 }
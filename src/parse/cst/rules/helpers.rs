@@ -1,38 +1,182 @@
-use bumpalo::Bump as Arena;
 use macros::{Parse, TreeNode};
 
 use crate::lex::Token;
 use crate::parse::cst::error::WithLocation;
-use crate::parse::tree_node::{TreeNode, TreeNodeChild};
+use crate::parse::cst::fold::{Fold, FoldWith};
+use crate::parse::cst::node::NodeRef;
+use crate::parse::cst::parse_trait::Context;
+use crate::parse::cst::visit::{Visit, Visitor};
+use crate::parse::tree_node::{Child, TreeNode, TreeNodeChild};
+use crate::parse::Arena;
 
 pub(super) fn many0<'a, 'arena, T: 'arena>(
 	parser: impl nom::Parser<&'a [Token], T, WithLocation<'a>>,
 	arena: &'arena Arena,
-) -> impl nom::Parser<&'a [Token], &'arena [T], WithLocation<'a>> {
-	nom::combinator::map(nom::multi::many0(parser), |parsed| {
-		&*arena.alloc_slice_fill_iter(parsed.into_iter())
-	})
+) -> impl nom::Parser<&'a [Token], List<'arena, T>, WithLocation<'a>> {
+	nom::combinator::map(nom::multi::many0(parser), |parsed| List::new(parsed, arena))
 }
 
 pub(super) fn many1<'a, 'arena, T: 'arena>(
 	parser: impl nom::Parser<&'a [Token], T, WithLocation<'a>>,
 	arena: &'arena Arena,
-) -> impl nom::Parser<&'a [Token], &'arena [T], WithLocation<'a>> {
-	nom::combinator::map(nom::multi::many1(parser), |parsed| {
-		&*arena.alloc_slice_fill_iter(parsed.into_iter())
-	})
+) -> impl nom::Parser<&'a [Token], List<'arena, T>, WithLocation<'a>> {
+	nom::combinator::map(nom::multi::many1(parser), |parsed| List::new(parsed, arena))
 }
 
-pub(super) struct PassArena<'arena, T>(&'arena Arena, std::marker::PhantomData<fn() -> T>);
+/// A sequence of nodes in the arena, which dereferences to a slice.
+///
+/// This is half the size of a slice reference and does not allocate when it is empty, which matters because most lists in the tree, such as the frees after each word, are empty.
+pub struct List<'arena, T>(Option<&'arena &'arena [T]>);
 
-impl<'arena, T> PassArena<'arena, T> {
-	pub(super) fn new(arena: &'arena Arena) -> Self {
-		Self(arena, std::marker::PhantomData)
+impl<T> List<'_, T> {
+	/// The empty list.
+	pub const EMPTY: Self = Self(None);
+}
+
+impl<'arena, T> List<'arena, T> {
+	pub(in crate::parse) fn new(items: Vec<T>, arena: &'arena Arena) -> Self {
+		if items.is_empty() {
+			Self::EMPTY
+		} else {
+			let items: &[T] = arena.alloc_slice_fill_iter(items);
+			Self(Some(arena.alloc(items)))
+		}
+	}
+
+	/// Get the items of the list as a slice that lives as long as the arena.
+	#[must_use]
+	pub fn as_slice(&self) -> &'arena [T] {
+		self.0.map_or(&[], |items| items)
+	}
+}
+
+impl<T> Clone for List<'_, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for List<'_, T> {}
+
+impl<T> Default for List<'_, T> {
+	fn default() -> Self {
+		Self::EMPTY
+	}
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for List<'_, T> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.as_slice().fmt(formatter)
+	}
+}
+
+impl<T> std::ops::Deref for List<'_, T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
+		self.as_slice()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a List<'_, T> {
+	type Item = &'a T;
+	type IntoIter = std::slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'ast, T: Visit<'ast>> Visit<'ast> for List<'_, T> {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		self.as_slice().visit(visitor);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		self.visit(visitor);
+	}
+}
+
+impl<'new, T: FoldWith<'new>> FoldWith<'new> for List<'_, T> {
+	type Output = List<'new, T::Output>;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		let arena = folder.arena();
+		List::new(
+			self.iter().map(|item| item.fold_with(folder)).collect(),
+			arena,
+		)
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		self.fold_with(folder)
+	}
+}
+
+/// A token in the tree, stored as its index in the tokens that the tree was parsed from rather than as a copy of the token.
+///
+/// The top bit records whether the token is experimental, so that [`TreeNode::experimental`] does not need the tokens.
+/// The rest of the token can be found with [`Cst::tokens`](crate::Cst::tokens).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenIndex(u32);
+
+impl TokenIndex {
+	const EXPERIMENTAL: u32 = 1 << 31;
+
+	pub(in crate::parse::cst) fn new(index: usize, experimental: bool) -> Self {
+		let index = u32::try_from(index)
+			.ok()
+			.filter(|&index| index < Self::EXPERIMENTAL)
+			.expect("there are fewer than 2^31 tokens");
+		Self(if experimental {
+			index | Self::EXPERIMENTAL
+		} else {
+			index
+		})
+	}
+
+	/// The index of the token.
+	#[must_use]
+	pub fn index(self) -> u32 {
+		self.0 & !Self::EXPERIMENTAL
+	}
+
+	/// Whether the token is experimental.
+	#[must_use]
+	pub fn experimental(self) -> bool {
+		self.0 & Self::EXPERIMENTAL != 0
+	}
+
+	/// Get the token from `tokens`, which must be the tokens that the tree was parsed from.
+	#[must_use]
+	pub fn get(self, tokens: &[Token]) -> Token {
+		tokens[self.index() as usize]
+	}
+}
+
+impl std::fmt::Debug for TokenIndex {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(formatter, "#{}", self.index())?;
+		if self.experimental() {
+			formatter.write_str(" (experimental)")?;
+		}
+		Ok(())
+	}
+}
+
+pub(super) struct PassContext<'context, 'arena, T>(
+	&'context Context<'arena>,
+	std::marker::PhantomData<fn() -> T>,
+);
+
+impl<'context, 'arena, T> PassContext<'context, 'arena, T> {
+	pub(super) fn new(context: &'context Context<'arena>) -> Self {
+		Self(context, std::marker::PhantomData)
 	}
 }
 
 impl<'a: 'arena, 'arena, T: super::super::parse_trait::Parse<'arena>>
-	nom::Parser<&'a [Token], T, WithLocation<'a>> for PassArena<'arena, T>
+	nom::Parser<&'a [Token], T, WithLocation<'a>> for PassContext<'_, 'arena, T>
 {
 	fn parse(&mut self, input: &'a [Token]) -> super::ParseResult<'a, T> {
 		T::parse(input, self.0)
@@ -43,7 +187,7 @@ impl<'a: 'arena, 'arena, T: super::super::parse_trait::Parse<'arena>>
 pub struct Separated<'arena, Item, Separator> {
 	pub first: &'arena Item,
 	#[parse(with = "many0")]
-	pub rest: &'arena [(Separator, Item)],
+	pub rest: List<'arena, (Separator, Item)>,
 }
 
 impl<Item: TreeNodeChild, Separator: TreeNodeChild> TreeNode for Separated<'_, Item, Separator> {
@@ -59,31 +203,86 @@ impl<Item: TreeNodeChild, Separator: TreeNodeChild> TreeNode for Separated<'_, I
 				.any(|(separator, item)| separator.experimental() || item.experimental())
 	}
 
-	fn start_location(&self) -> Option<crate::span::Location> {
-		self.first.start_location().or_else(|| {
+	fn start_token(&self) -> Option<u32> {
+		self.first.start_token().or_else(|| {
 			self
 				.rest
 				.iter()
-				.find_map(|(separator, item)| separator.start_location().or_else(|| item.start_location()))
+				.find_map(|(separator, item)| separator.start_token().or_else(|| item.start_token()))
 		})
 	}
 
-	fn end_location(&self) -> Option<crate::span::Location> {
+	fn end_token(&self) -> Option<u32> {
 		self
 			.rest
 			.iter()
 			.rev()
-			.find_map(|(separator, item)| item.end_location().or_else(|| separator.end_location()))
-			.or_else(|| self.first.end_location())
+			.find_map(|(separator, item)| item.end_token().or_else(|| separator.end_token()))
+			.or_else(|| self.first.end_token())
 	}
 
 	fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode)) {
 		self.first.invoke_with_self(f);
-		for (separator, item) in self.rest.iter() {
+		for (separator, item) in &self.rest {
 			separator.invoke_with_self(f);
 			item.invoke_with_self(f);
 		}
 	}
+
+	fn for_each_child_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		self.first.invoke_with_self_or_elided(f);
+		for (separator, item) in &self.rest {
+			separator.invoke_with_self_or_elided(f);
+			item.invoke_with_self_or_elided(f);
+		}
+	}
+
+	fn for_each_labeled_child<'a>(
+		&'a self,
+		f: &mut dyn FnMut(Option<&'static str>, &'a dyn TreeNode),
+	) {
+		self
+			.first
+			.invoke_with_self(&mut |child| f(Some("item"), child));
+		for (separator, item) in &self.rest {
+			separator.invoke_with_self(&mut |child| f(Some("separator"), child));
+			item.invoke_with_self(&mut |child| f(Some("item"), child));
+		}
+	}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		NodeRef::Separated(self)
+	}
+}
+
+impl<'ast, Item: Visit<'ast>, Separator: Visit<'ast>> Visit<'ast>
+	for Separated<'_, Item, Separator>
+{
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		visitor.visit_separated(self);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		self.first.visit(visitor);
+		self.rest.visit(visitor);
+	}
+}
+
+impl<'new, Item: FoldWith<'new>, Separator: FoldWith<'new>> FoldWith<'new>
+	for Separated<'_, Item, Separator>
+{
+	type Output = Separated<'new, Item::Output, Separator::Output>;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		folder.fold_separated(self)
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self::Output {
+		Separated {
+			first: FoldWith::fold_with(&self.first, folder),
+			rest: FoldWith::fold_with(&self.rest, folder),
+		}
+	}
 }
 
 // print as a single list with the separators interleaved. obviously this would not be valid rust, but it cuts down indentation.
@@ -94,7 +293,7 @@ impl<Item: std::fmt::Debug, Separator: std::fmt::Debug> std::fmt::Debug
 		formatter.write_str("Separated ")?;
 		let mut list = formatter.debug_list();
 		list.entry(&self.first);
-		for (separator, item) in self.rest.iter() {
+		for (separator, item) in &self.rest {
 			list.entry(separator);
 			list.entry(item);
 		}
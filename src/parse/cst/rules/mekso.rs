@@ -1,6 +1,6 @@
 use macros::TreeNode;
 
-use super::helpers::{many0, many1};
+use super::helpers::{many0, many1, List};
 use super::{
 	Bihe, Bo, Boi, Frees, Fuha, Gek, Gik, Johi, JoikEk, JoikJek, Ke, Kehe, Kuhe, Luhu, Maho,
 	MiscNumbers, Mohe, Moi, Nahe, NaheGuhekTGik, Nahu, Nihe, Parse, Peho, Se, Selbri,
@@ -10,7 +10,10 @@ use super::{
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Expression<'arena> {
-	ReversePolish(WithFree<'arena, Fuha<'arena>>, ReversePolish<'arena>),
+	ReversePolish(
+		#[tree_node(label = "fuha")] WithFree<'arena, Fuha<'arena>>,
+		#[tree_node(label = "expression")] ReversePolish<'arena>,
+	),
 	Normal(
 		Separated<
 			'arena,
@@ -20,15 +23,20 @@ pub enum Expression<'arena> {
 	),
 }
 
-// this representation is quite clunky and does not match the semantic hierarchy of the RP expression, but that's a problem for the AST.
+// this representation is quite clunky and does not match the semantic hierarchy of the RP expression, but lowering it into an `ast::mekso::Expression` turns it into one.
 #[derive(Debug, Parse, TreeNode)]
 pub struct ReversePolish<'arena>(
-	pub Operand<'arena>,
-	#[parse(with = "many0")] pub &'arena [RPTail<'arena>],
+	#[tree_node(label = "first")] pub Operand<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "rest")]
+	pub List<'arena, RPTail<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct RPTail<'arena>(pub ReversePolish<'arena>, pub Operator<'arena>);
+pub struct RPTail<'arena>(
+	#[tree_node(label = "operand")] pub ReversePolish<'arena>,
+	#[tree_node(label = "operator")] pub Operator<'arena>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Expression1<'arena> {
@@ -41,77 +49,85 @@ pub struct ForethoughtExpression<'arena> {
 	pub peho: Option<WithFree<'arena, Peho<'arena>>>,
 	pub operator: Operator<'arena>,
 	#[parse(with = "many1")]
-	pub operands: &'arena [Expression1<'arena>],
+	pub operands: List<'arena, Expression1<'arena>>,
 	pub kuhe: Option<Kuhe<'arena>>,
 	pub frees: Frees<'arena>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Operand<'arena>(
-	pub Operand1<'arena>,
-	#[parse(with = "many0")] pub &'arena [ConnectedOperand<'arena>],
+	#[tree_node(label = "first")] pub Operand1<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, ConnectedOperand<'arena>>,
 );
 
 pub type ConnectedOperand<'arena> =
 	SumtiLikeConnectedPost<'arena, Operand1<'arena>, Operand<'arena>>;
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct Operand1<'arena>(
-	pub  Separated<
-		'arena,
-		Operand2<'arena>,
-		(
-			JoikEk<'arena>,
-			Option<TagWords<'arena>>,
-			WithFree<'arena, Bo<'arena>>,
-		),
-	>,
+pub struct Operand1<'arena>(pub Separated<'arena, Operand2<'arena>, Operand1Connective<'arena>>);
+
+pub type Operand1Connective<'arena> = (
+	JoikEk<'arena>,
+	Option<TagWords<'arena>>,
+	WithFree<'arena, Bo<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Operand2<'arena>(
-	#[parse(with = "many0")] pub &'arena [Operand2ConnectedPre<'arena>],
-	pub Operand3<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, Operand2ConnectedPre<'arena>>,
+	#[tree_node(label = "last")] pub Operand3<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct Operand2ConnectedPre<'arena>(pub Gek<'arena>, pub Operand<'arena>, pub Gik<'arena>);
+pub struct Operand2ConnectedPre<'arena>(
+	#[tree_node(label = "gek")] pub Gek<'arena>,
+	#[tree_node(label = "first")] pub Operand<'arena>,
+	#[tree_node(label = "gik")] pub Gik<'arena>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Operand3<'arena> {
 	Nihe(
-		WithFree<'arena, Nihe<'arena>>,
-		Selbri<'arena>,
-		Option<Tehu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "nihe")] WithFree<'arena, Nihe<'arena>>,
+		#[tree_node(label = "selbri")] Selbri<'arena>,
+		#[tree_node(label = "tehu")] Option<Tehu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Mohe(
-		WithFree<'arena, Mohe<'arena>>,
-		Sumti<'arena>,
-		Option<Tehu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "mohe")] WithFree<'arena, Mohe<'arena>>,
+		#[tree_node(label = "sumti")] Sumti<'arena>,
+		#[tree_node(label = "tehu")] Option<Tehu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Johi(
-		WithFree<'arena, Johi<'arena>>,
-		#[parse(with = "many1")] &'arena [Expression1<'arena>],
-		Option<Tehu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "johi")] WithFree<'arena, Johi<'arena>>,
+		#[parse(with = "many1")]
+		#[tree_node(label = "operands")]
+		List<'arena, Expression1<'arena>>,
+		#[tree_node(label = "tehu")] Option<Tehu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Modified(
-		OperandModifier<'arena>,
-		Operand<'arena>,
-		Option<Luhu<'arena>>,
+		#[tree_node(label = "modifier")] OperandModifier<'arena>,
+		#[tree_node(label = "operand")] Operand<'arena>,
+		#[tree_node(label = "luhu")] Option<Luhu<'arena>>,
 	),
 	Parenthesized(
-		WithFree<'arena, Vei<'arena>>,
-		Expression<'arena>,
-		Option<Veho<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "vei")] WithFree<'arena, Vei<'arena>>,
+		#[tree_node(label = "expression")] Expression<'arena>,
+		#[tree_node(label = "veho")] Option<Veho<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Number(
-		MiscNumbers<'arena>,
-		#[parse(not = "Moi<'_>")] Option<Boi<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "number")] MiscNumbers<'arena>,
+		#[parse(not = "Moi<'_>")]
+		#[tree_node(label = "boi")]
+		Option<Boi<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
@@ -119,8 +135,10 @@ pub type OperandModifier<'arena> = SumtiModifier<'arena>;
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Operator<'arena>(
-	pub Operator1<'arena>,
-	#[parse(with = "many0")] pub &'arena [ConnectedOperator<'arena>],
+	#[tree_node(label = "first")] pub Operator1<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, ConnectedOperator<'arena>>,
 );
 
 pub type ConnectedOperator<'arena> =
@@ -128,38 +146,38 @@ pub type ConnectedOperator<'arena> =
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Operator1<'arena>(
-	#[parse(with = "many0")] pub &'arena [NaheGuhekTGik<'arena, Self>],
-	pub Operator2<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, NaheGuhekTGik<'arena, Self>>,
+	#[tree_node(label = "last")] pub Operator2<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct Operator2<'arena>(
-	pub  Separated<
-		'arena,
-		Operator3<'arena>,
-		(
-			JoikJek<'arena>,
-			Option<TagWords<'arena>>,
-			WithFree<'arena, Bo<'arena>>,
-		),
-	>,
+pub struct Operator2<'arena>(pub Separated<'arena, Operator3<'arena>, Operator2Connective<'arena>>);
+
+pub type Operator2Connective<'arena> = (
+	JoikJek<'arena>,
+	Option<TagWords<'arena>>,
+	WithFree<'arena, Bo<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Operator3<'arena> {
 	Simple(OperatorComponent<'arena>),
 	Grouped(
-		WithFree<'arena, Ke<'arena>>,
-		Operator<'arena>,
-		Kehe<'arena>,
-		Frees<'arena>,
+		#[tree_node(label = "ke")] WithFree<'arena, Ke<'arena>>,
+		#[tree_node(label = "operator")] Operator<'arena>,
+		#[tree_node(label = "kehe")] Kehe<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct OperatorComponent<'arena>(
-	#[parse(with = "many0")] &'arena [OperatorComponentPre<'arena>],
-	OperatorComponent1<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "before")]
+	pub List<'arena, OperatorComponentPre<'arena>>,
+	#[tree_node(label = "inner")] pub OperatorComponent1<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
@@ -171,16 +189,16 @@ pub enum OperatorComponentPre<'arena> {
 #[derive(Debug, Parse, TreeNode)]
 pub enum OperatorComponent1<'arena> {
 	Maho(
-		WithFree<'arena, Maho<'arena>>,
-		Expression<'arena>,
-		Option<Tehu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "maho")] WithFree<'arena, Maho<'arena>>,
+		#[tree_node(label = "expression")] Expression<'arena>,
+		#[tree_node(label = "tehu")] Option<Tehu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Nahu(
-		WithFree<'arena, Nahu<'arena>>,
-		Selbri<'arena>,
-		Option<Tehu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "nahu")] WithFree<'arena, Nahu<'arena>>,
+		#[tree_node(label = "selbri")] Selbri<'arena>,
+		#[tree_node(label = "tehu")] Option<Tehu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Vuhu(WithFree<'arena, Vuhu<'arena>>),
 }
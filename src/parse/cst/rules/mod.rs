@@ -2,11 +2,9 @@
 
 // https://raw.githubusercontent.com/lojban/camxes-py/master/camxes_py/parsers/camxes_ilmen.peg
 
-use bumpalo::Bump as Arena;
 use macros::{Parse, TreeNode};
 
 use crate::lex::{Selmaho, Token};
-use crate::span::Span;
 
 pub mod connectives;
 mod helpers;
@@ -14,12 +12,16 @@ pub mod mekso;
 pub mod selmaho;
 
 use connectives::{Ek, Gek, Gihek, Gik, Guhek, Jek, Joik, JoikEk, JoikJek};
-use helpers::{many0, many1, EitherOrBoth, Separated};
+use helpers::{many0, many1, EitherOrBoth};
+pub use helpers::{List, Separated, TokenIndex};
 use mekso::{Expression as Mekso, Operator as MeksoOperator};
 #[allow(clippy::wildcard_imports)]
 use selmaho::*;
 
-use super::parse_trait::{Parse, Result as ParseResult};
+use super::fold::{Fold, FoldWith};
+use super::node::NodeRef;
+use super::parse_trait::{Context, Parse, Result as ParseResult};
+use super::visit::{Visit, Visitor};
 use crate::parse::tree_node::TreeNode;
 
 #[derive(Debug, Parse, TreeNode)]
@@ -31,7 +33,7 @@ pub struct WithFree<'arena, Inner> {
 
 #[derive(Debug, Parse, TreeNode)]
 #[tree_node(passthrough_child)]
-pub struct Frees<'arena>(#[parse(with = "many0")] pub &'arena [Free<'arena>]);
+pub struct Frees<'arena>(#[parse(with = "many0")] pub List<'arena, Free<'arena>>);
 
 pub type Root<'arena> = Text<'arena>;
 
@@ -45,7 +47,7 @@ pub struct Text<'arena> {
 }
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct Paragraphs<'arena>(Separated<'arena, Paragraph<'arena>, ParagraphSeparator<'arena>>);
+pub struct Paragraphs<'arena>(pub Separated<'arena, Paragraph<'arena>, ParagraphSeparator<'arena>>);
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Paragraph<'arena> {
@@ -78,54 +80,71 @@ pub enum Fragment<'arena> {
 	// this is Quantifier rather than something that accepts MiscNumber because a lerfu string that starts with a letteral can be parsed as a sumti instead
 	Number(Quantifier<'arena>),
 	// answer to negation question?
-	Na(Na<'arena>, #[parse(not = "Ja<'_>")] Frees<'arena>),
+	Na(
+		#[tree_node(label = "na")] Na<'arena>,
+		#[parse(not = "Ja<'_>")]
+		#[tree_node(label = "frees")]
+		Frees<'arena>,
+	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Sentences1<'arena>(
-	#[parse(with = "many0")] pub &'arena [Prenex<'arena>],
-	pub Separated<'arena, Sentences2<'arena>, ConnectedSentenceSeparator<'arena>>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "prenexes")]
+	pub List<'arena, Prenex<'arena>>,
+	#[tree_node(label = "sentences")] pub ConnectedSentences<'arena>,
 );
+pub type ConnectedSentences<'arena> =
+	Separated<'arena, Sentences2<'arena>, ConnectedSentenceSeparator<'arena>>;
 pub type Sentences2<'arena> = Separated<'arena, Sentences3<'arena>, CloseSentenceSeparator<'arena>>;
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Sentences3<'arena> {
 	Grouped(
-		Option<TagWords<'arena>>,
-		WithFree<'arena, Tuhe<'arena>>,
-		Paragraphs<'arena>,
-		Option<Tuhu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "tag")] Option<TagWords<'arena>>,
+		#[tree_node(label = "tuhe")] WithFree<'arena, Tuhe<'arena>>,
+		#[tree_node(label = "paragraphs")] Paragraphs<'arena>,
+		#[tree_node(label = "tuhu")] Option<Tuhu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Single(&'arena Sentence<'arena>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct ParagraphSeparator<'arena>(
-	#[parse(with = "many1")] &'arena [Niho<'arena>],
-	Frees<'arena>,
+	#[parse(with = "many1")]
+	#[tree_node(label = "niho")]
+	pub List<'arena, Niho<'arena>>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct SentenceSeparator<'arena>(pub I<'arena>, #[parse(not = "Bu")] pub Frees<'arena>);
+pub struct SentenceSeparator<'arena>(
+	#[tree_node(label = "i")] pub I<'arena>,
+	#[parse(not = "Bu")]
+	#[tree_node(label = "frees")]
+	pub Frees<'arena>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct ConnectedSentenceSeparator<'arena>(
-	pub I<'arena>,
-	pub JoikJek<'arena>,
-	pub Frees<'arena>,
+	#[tree_node(label = "i")] pub I<'arena>,
+	#[tree_node(label = "connective")] pub JoikJek<'arena>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct CloseSentenceSeparator<'arena>(
-	pub I<'arena>,
-	pub Option<JoikJek<'arena>>,
-	pub Option<TagWords<'arena>>,
-	pub Bo<'arena>,
-	pub Frees<'arena>,
+	#[tree_node(label = "i")] pub I<'arena>,
+	#[tree_node(label = "connective")] pub Option<JoikJek<'arena>>,
+	#[tree_node(label = "tag")] pub Option<TagWords<'arena>>,
+	#[tree_node(label = "bo")] pub Bo<'arena>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct Sentence<'arena> {
 	pub before_args: Args<'arena>,
 	pub tail: Option<SentenceTail<'arena>>,
@@ -133,52 +152,58 @@ pub struct Sentence<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SentenceTail<'arena>(
-	pub Option<Cu<'arena>>,
-	pub Frees<'arena>,
-	pub SentenceTail1<'arena>,
+	#[tree_node(label = "cu")] pub Option<Cu<'arena>>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
+	#[tree_node(label = "tail")] pub SentenceTail1<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct SentenceTail1<'arena>(
-	pub SentenceTail2<'arena>,
-	pub Option<SentenceTail1After<'arena>>,
+	#[tree_node(label = "first")] pub SentenceTail2<'arena>,
+	#[tree_node(label = "after")] pub Option<&'arena SentenceTail1After<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SentenceTail1After<'arena>(
-	Gihek<'arena>,
-	Option<TagWords<'arena>>,
-	Ke<'arena>,
-	Frees<'arena>,
-	&'arena SentenceTail1<'arena>,
-	Option<Kehe<'arena>>,
-	Frees<'arena>,
-	TailArgs<'arena>,
+	#[tree_node(label = "gihek")] pub Gihek<'arena>,
+	#[tree_node(label = "tag")] pub Option<TagWords<'arena>>,
+	#[tree_node(label = "ke")] pub Ke<'arena>,
+	#[tree_node(label = "ke_frees")] pub Frees<'arena>,
+	#[tree_node(label = "tail")] pub &'arena SentenceTail1<'arena>,
+	#[tree_node(label = "kehe")] pub Option<Kehe<'arena>>,
+	#[tree_node(label = "kehe_frees")] pub Frees<'arena>,
+	#[tree_node(label = "tail_args")] pub TailArgs<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SentenceTail2<'arena>(
-	pub &'arena SentenceTail3<'arena>,
-	pub Option<SentenceTail2After<'arena>>,
+	#[tree_node(label = "first")] pub &'arena SentenceTail3<'arena>,
+	#[tree_node(label = "after")] pub Option<SentenceTail2After<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SentenceTail2Connective<'arena>(
-	pub Gihek<'arena>,
+	#[tree_node(label = "gihek")] pub Gihek<'arena>,
 	// `gi'e ke ...` must be parsed as SentenceTail1After, not a parenthesized tanru
-	#[parse(not = "(Option<TagWords<'_>>, Ke<'_>)")] pub Frees<'arena>,
+	#[parse(not = "(Option<TagWords<'_>>, Ke<'_>)")]
+	#[tree_node(label = "frees")]
+	pub Frees<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SentenceTail2After<'arena>(
-	#[parse(with = "many1")] pub &'arena [(SentenceTail2Connective<'arena>, SentenceTail3<'arena>)],
-	pub TailArgs<'arena>,
+	#[parse(with = "many1")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, (SentenceTail2Connective<'arena>, SentenceTail3<'arena>)>,
+	#[tree_node(label = "tail_args")] pub TailArgs<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct SentenceTail3<'arena>(
-	pub &'arena SentenceTail4<'arena>,
-	pub Option<SentenceTail3After<'arena>>,
+	#[tree_node(label = "first")] pub &'arena SentenceTail4<'arena>,
+	#[tree_node(label = "after")] pub Option<SentenceTail3After<'arena>>,
 );
 
 pub type SentenceTail3Connective<'arena> = (
@@ -190,44 +215,58 @@ pub type SentenceTail3Connective<'arena> = (
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SentenceTail3After<'arena>(
-	#[parse(with = "many1")] &'arena [(SentenceTail3Connective<'arena>, SentenceTail4<'arena>)],
-	TailArgs<'arena>,
+	#[parse(with = "many1")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, (SentenceTail3Connective<'arena>, SentenceTail4<'arena>)>,
+	#[tree_node(label = "tail_args")] pub TailArgs<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub enum SentenceTail4<'arena> {
-	Single(Selbri<'arena>, TailArgs<'arena>),
+	Single(
+		#[tree_node(label = "selbri")] Selbri<'arena>,
+		#[tree_node(label = "tail_args")] TailArgs<'arena>,
+	),
 	Parenthesized(
-		#[parse(with = "many0")] &'arena [WithFree<'arena, Na<'arena>>],
-		Option<TagWords<'arena>>,
-		WithFree<'arena, Ke<'arena>>,
-		&'arena GekSentence<'arena>,
-		Option<Kehe<'arena>>,
-		Frees<'arena>,
+		#[parse(with = "many0")]
+		#[tree_node(label = "na")]
+		List<'arena, WithFree<'arena, Na<'arena>>>,
+		#[tree_node(label = "tag")] Option<TagWords<'arena>>,
+		#[tree_node(label = "ke")] WithFree<'arena, Ke<'arena>>,
+		#[tree_node(label = "sentence")] &'arena GekSentence<'arena>,
+		#[tree_node(label = "kehe")] Option<Kehe<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Connected(&'arena GekSentence<'arena>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct GekSentence<'arena>(
-	#[parse(with = "many0")] pub &'arena [WithFree<'arena, Na<'arena>>],
-	pub Gek<'arena>,
-	pub Subsentence<'arena>,
-	pub Gik<'arena>,
-	pub Subsentence<'arena>,
-	pub TailArgs<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "na")]
+	pub List<'arena, WithFree<'arena, Na<'arena>>>,
+	#[tree_node(label = "gek")] pub Gek<'arena>,
+	#[tree_node(label = "first")] pub Subsentence<'arena>,
+	#[tree_node(label = "gik")] pub Gik<'arena>,
+	#[tree_node(label = "second")] pub Subsentence<'arena>,
+	#[tree_node(label = "tail_args")] pub TailArgs<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct Subsentence<'arena>(
-	#[parse(with = "many0")] pub &'arena [Prenex<'arena>],
-	Args<'arena>,
-	SentenceTail<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "prenexes")]
+	pub List<'arena, Prenex<'arena>>,
+	#[tree_node(label = "before_args")] pub Args<'arena>,
+	#[tree_node(label = "tail")] pub SentenceTail<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 #[tree_node(passthrough_child)]
-pub struct Args<'arena>(#[parse(with = "many0")] pub &'arena [Arg<'arena>]);
+pub struct Args<'arena>(#[parse(with = "many0")] pub List<'arena, Arg<'arena>>);
 
 #[derive(Debug, Parse, TreeNode)]
 #[tree_node(passthrough_child)]
@@ -242,14 +281,19 @@ pub struct Prenex<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Arg<'arena>(
-	Arg1<'arena>,
-	#[parse(with = "many0")] &'arena [SumtiLikeConnectedPost<'arena, Arg1<'arena>, Self>],
+	#[tree_node(label = "first")] pub Arg1<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, SumtiLikeConnectedPost<'arena, Arg1<'arena>, Self>>,
 );
 
 pub type Arg1<'arena> = Separated<'arena, Arg2<'arena>, PeheConnective<'arena>>;
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct PeheConnective<'arena>(WithFree<'arena, Pehe<'arena>>, JoikJek<'arena>);
+pub struct PeheConnective<'arena>(
+	#[tree_node(label = "pehe")] pub WithFree<'arena, Pehe<'arena>>,
+	#[tree_node(label = "connective")] pub JoikJek<'arena>,
+);
 
 pub type Arg2<'arena> = Separated<'arena, Arg3<'arena>, WithFree<'arena, Cehe<'arena>>>;
 
@@ -260,36 +304,47 @@ pub enum Arg3<'arena> {
 		fa: Option<WithFree<'arena, Fa<'arena>>>,
 		sumti: Sumti<'arena>,
 	},
-	Naku(Na<'arena>, Ku<'arena>, Frees<'arena>),
+	Naku(
+		#[tree_node(label = "na")] Na<'arena>,
+		#[tree_node(label = "ku")] Ku<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
 	Termset(&'arena Termset<'arena>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub enum Termset<'arena> {
-	Gek(Gek<'arena>, Args<'arena>, Gik<'arena>, Args<'arena>),
+	Gek(
+		#[tree_node(label = "gek")] Gek<'arena>,
+		#[tree_node(label = "first")] Args<'arena>,
+		#[tree_node(label = "gik")] Gik<'arena>,
+		#[tree_node(label = "second")] Args<'arena>,
+	),
 	NuhiGi(
-		WithFree<'arena, Nuhi<'arena>>,
-		Gek<'arena>,
-		Args<'arena>,
-		Option<Nuhu<'arena>>,
-		Frees<'arena>,
-		Gik<'arena>,
-		Args<'arena>,
-		Option<Nuhu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "nuhi")] WithFree<'arena, Nuhi<'arena>>,
+		#[tree_node(label = "gek")] Gek<'arena>,
+		#[tree_node(label = "first")] Args<'arena>,
+		#[tree_node(label = "first_nuhu")] Option<Nuhu<'arena>>,
+		#[tree_node(label = "first_frees")] Frees<'arena>,
+		#[tree_node(label = "gik")] Gik<'arena>,
+		#[tree_node(label = "second")] Args<'arena>,
+		#[tree_node(label = "second_nuhu")] Option<Nuhu<'arena>>,
+		#[tree_node(label = "second_frees")] Frees<'arena>,
 	),
 	Nuhi(
-		WithFree<'arena, Nuhi<'arena>>,
-		Args<'arena>,
-		Option<Nuhu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "nuhi")] WithFree<'arena, Nuhi<'arena>>,
+		#[tree_node(label = "args")] Args<'arena>,
+		#[tree_node(label = "nuhu")] Option<Nuhu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct Selbri<'arena> {
 	#[parse(with = "many0")]
-	pub before: &'arena [SelbriBefore<'arena>],
+	pub before: List<'arena, SelbriBefore<'arena>>,
 	// all other `Separated` will use `false` for `should_cut`
 	pub components: Selbri1<'arena>,
 }
@@ -302,16 +357,18 @@ pub enum SelbriBefore<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 #[repr(transparent)]
-pub struct Selbri1<'arena>(Separated<'arena, Selbri2<'arena>, WithFree<'arena, Co<'arena>>>);
+pub struct Selbri1<'arena>(pub Separated<'arena, Selbri2<'arena>, WithFree<'arena, Co<'arena>>>);
 
 #[derive(Debug, Parse, TreeNode)]
 #[repr(transparent)]
-pub struct Selbri2<'arena>(#[parse(with = "many1")] &'arena [Selbri3<'arena>]);
+pub struct Selbri2<'arena>(#[parse(with = "many1")] pub List<'arena, Selbri3<'arena>>);
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Selbri3<'arena>(
-	pub Selbri4<'arena>,
-	#[parse(with = "many0")] pub &'arena [Selbri3ConnectedPost<'arena>],
+	#[tree_node(label = "first")] pub Selbri4<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, Selbri3ConnectedPost<'arena>>,
 );
 
 pub type Selbri3ConnectedPost<'arena> =
@@ -319,45 +376,46 @@ pub type Selbri3ConnectedPost<'arena> =
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum SelbriLikeConnectedPost<'arena, Normal, Parenthesized> {
-	Normal(JoikJek<'arena>, Normal),
+	Normal(
+		#[tree_node(label = "connective")] JoikJek<'arena>,
+		#[tree_node(label = "selbri")] Normal,
+	),
 	Parenthesized(
-		JoikJek<'arena>,
-		Option<TagWords<'arena>>,
-		WithFree<'arena, Ke<'arena>>,
-		Parenthesized,
-		Option<Kehe<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "connective")] JoikJek<'arena>,
+		#[tree_node(label = "tag")] Option<TagWords<'arena>>,
+		#[tree_node(label = "ke")] WithFree<'arena, Ke<'arena>>,
+		#[tree_node(label = "selbri")] Parenthesized,
+		#[tree_node(label = "kehe")] Option<Kehe<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 #[repr(transparent)]
-pub struct Selbri4<'arena>(
-	pub  Separated<
-		'arena,
-		Selbri5<'arena>,
-		(
-			JoikJek<'arena>,
-			Option<TagWords<'arena>>,
-			Bo<'arena>,
-			Frees<'arena>,
-		),
-	>,
+pub struct Selbri4<'arena>(pub Separated<'arena, Selbri5<'arena>, Selbri4Connective<'arena>>);
+
+pub type Selbri4Connective<'arena> = (
+	JoikJek<'arena>,
+	Option<TagWords<'arena>>,
+	Bo<'arena>,
+	Frees<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Selbri5<'arena>(
-	#[parse(with = "many0")] pub &'arena [NaheGuhekTGik<'arena, Selbri<'arena>>],
-	pub Selbri6<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, NaheGuhekTGik<'arena, Selbri<'arena>>>,
+	#[tree_node(label = "last")] pub Selbri6<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct NaheGuhekTGik<'arena, T>(
-	pub Option<Nahe<'arena>>,
-	pub Frees<'arena>,
-	pub Guhek<'arena>,
-	pub T,
-	pub Gik<'arena>,
+	#[tree_node(label = "nahe")] pub Option<Nahe<'arena>>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
+	#[tree_node(label = "guhek")] pub Guhek<'arena>,
+	#[tree_node(label = "first")] pub T,
+	#[tree_node(label = "gik")] pub Gik<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
@@ -372,9 +430,9 @@ pub struct TanruUnit<'arena>(
 #[derive(Debug, Parse, TreeNode)]
 pub struct TanruUnit1<'arena> {
 	#[parse(with = "many0")]
-	pub before: &'arena [BeforeTanruUnit<'arena>],
+	pub before: List<'arena, BeforeTanruUnit<'arena>>,
 	pub inner: TanruUnit2<'arena>,
-	pub bound_arguments: Option<BoundArguments<'arena>>,
+	pub bound_arguments: Option<&'arena BoundArguments<'arena>>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
@@ -412,13 +470,17 @@ pub enum TanruUnit2<'arena> {
 		raho: Option<Raho<'arena>>,
 		frees: Frees<'arena>,
 	},
-	Moi(MiscNumbers<'arena>, Moi<'arena>, Frees<'arena>),
+	Moi(
+		#[tree_node(label = "number")] MiscNumbers<'arena>,
+		#[tree_node(label = "moi")] Moi<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
 	Me {
 		me: WithFree<'arena, Me<'arena>>,
-		inner: Sumti<'arena>,
+		inner: &'arena Sumti<'arena>,
 		mehu: Option<Mehu<'arena>>,
 		frees: Frees<'arena>,
-		moi: Option<WithFree<'arena, Moi<'arena>>>,
+		moi: Option<&'arena WithFree<'arena, Moi<'arena>>>,
 	},
 	Nu {
 		nus: Separated<'arena, (Nu<'arena>, Option<Nai<'arena>>, Frees<'arena>), JoikJek<'arena>>,
@@ -434,29 +496,37 @@ pub enum TanruUnit2<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct ZeiClause<'arena>(
-	#[parse(with = "many0")] pub &'arena [Bahe],
-	pub BuInner,
-	#[parse(with = "many0")] pub &'arena [ZeiBuInner<'arena>],
-	pub ZeiTail<'arena>,
-	#[parse(not = "Zei<'_>", not = "Bu")] pub Option<&'arena Indicators<'arena>>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "bahe")]
+	pub List<'arena, Bahe>,
+	#[tree_node(label = "first")] pub BuInner,
+	#[parse(with = "many0")]
+	#[tree_node(label = "bu")]
+	pub List<'arena, ZeiBuInner<'arena>>,
+	#[tree_node(label = "zei")] pub ZeiTail<'arena>,
+	#[parse(not = "Zei<'_>", not = "Bu")]
+	#[tree_node(label = "indicators")]
+	pub Option<&'arena Indicators<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct ZeiBuInner<'arena>(
-	pub Option<ZeiTail<'arena>>,
-	#[parse(with = "many1")] pub &'arena [Bu],
+	#[tree_node(label = "zei")] pub Option<ZeiTail<'arena>>,
+	#[parse(with = "many1")]
+	#[tree_node(label = "bu")]
+	pub List<'arena, Bu>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct ZeiTail<'arena>(#[parse(with = "many1")] pub &'arena [(Zei<'arena>, Token)]);
+pub struct ZeiTail<'arena>(#[parse(with = "many1")] pub List<'arena, (Zei<'arena>, AnyToken)>);
 
 #[test]
 fn test_zei_clause() {
-	let arena = Arena::new();
+	let arena = crate::parse::Arena::new();
 	let tokens = crate::lex("ko'a zei ko'a")
 		.collect::<Result<Vec<_>, _>>()
 		.unwrap();
-	let (rest, _) = ZeiClause::parse(&tokens, &arena).unwrap();
+	let (rest, _) = ZeiClause::parse(&tokens, &Context::new(&arena, tokens.len(), false)).unwrap();
 	assert_eq!(rest, &[]);
 }
 
@@ -489,17 +559,23 @@ pub enum TagWord<'arena> {
 	TimeSpaceCaha {
 		nahe: Option<Nahe<'arena>>,
 		#[parse(with = "many1")]
-		inner: &'arena [TimeSpaceCaha<'arena>],
+		inner: List<'arena, TimeSpaceCaha<'arena>>,
 		ki: Option<Ki<'arena>>,
 		frees: Frees<'arena>,
 	},
-	Ki(Ki<'arena>, Frees<'arena>),
-	Cuhe(Cuhe<'arena>, Frees<'arena>),
+	Ki(
+		#[tree_node(label = "ki")] Ki<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
+	Cuhe(
+		#[tree_node(label = "cuhe")] Cuhe<'arena>,
+		#[tree_node(label = "frees")] Frees<'arena>,
+	),
 	Converted(
-		WithFree<'arena, Fiho<'arena>>,
-		Selbri<'arena>,
-		Option<Fehu<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "fiho")] WithFree<'arena, Fiho<'arena>>,
+		#[tree_node(label = "selbri")] Selbri<'arena>,
+		#[tree_node(label = "fehu")] Option<Fehu<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
@@ -515,10 +591,10 @@ pub enum TimeSpaceCaha<'arena> {
 pub struct Time<'arena> {
 	pub zi: Option<Zi<'arena>>,
 	#[parse(with = "many0")]
-	pub offset: &'arena [TimeOffset<'arena>],
-	pub duration: Option<TimeDuration<'arena>>,
+	pub offset: List<'arena, TimeOffset<'arena>>,
+	pub duration: Option<&'arena TimeDuration<'arena>>,
 	#[parse(with = "many0")]
-	pub properties: &'arena [TimeIntervalProperty<'arena>],
+	pub properties: List<'arena, TimeIntervalProperty<'arena>>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
@@ -537,9 +613,19 @@ pub struct TimeDuration<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum IntervalProperty<'arena> {
-	Roi(Number<'arena>, Roi<'arena>, Option<Nai<'arena>>),
-	Tahe(Tahe<'arena>, Option<Nai<'arena>>),
-	Zaho(Zaho<'arena>, Option<Nai<'arena>>),
+	Roi(
+		#[tree_node(label = "number")] Number<'arena>,
+		#[tree_node(label = "roi")] Roi<'arena>,
+		#[tree_node(label = "nai")] Option<Nai<'arena>>,
+	),
+	Tahe(
+		#[tree_node(label = "tahe")] Tahe<'arena>,
+		#[tree_node(label = "nai")] Option<Nai<'arena>>,
+	),
+	Zaho(
+		#[tree_node(label = "zaho")] Zaho<'arena>,
+		#[tree_node(label = "nai")] Option<Nai<'arena>>,
+	),
 }
 
 pub type TimeIntervalProperty<'arena> = IntervalProperty<'arena>;
@@ -549,13 +635,17 @@ pub type TimeIntervalProperty<'arena> = IntervalProperty<'arena>;
 pub struct Space<'arena> {
 	pub va: Option<Va<'arena>>,
 	#[parse(with = "many0")]
-	pub offset: &'arena [SpaceOffset<'arena>],
-	pub interval: Option<SpaceInterval<'arena>>,
-	pub motion: Option<SpaceMotion<'arena>>,
+	pub offset: List<'arena, SpaceOffset<'arena>>,
+	pub interval: Option<&'arena SpaceInterval<'arena>>,
+	pub motion: Option<&'arena SpaceMotion<'arena>>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct SpaceOffset<'arena>(Faha<'arena>, Option<Nai<'arena>>, Option<Va<'arena>>);
+pub struct SpaceOffset<'arena>(
+	#[tree_node(label = "faha")] Faha<'arena>,
+	#[tree_node(label = "nai")] Option<Nai<'arena>>,
+	#[tree_node(label = "va")] Option<Va<'arena>>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum SpaceInterval<'arena> {
@@ -563,13 +653,16 @@ pub enum SpaceInterval<'arena> {
 		interval: EitherOrBoth<Veha<'arena>, Viha<'arena>>,
 		direction: Option<(Faha<'arena>, Option<Nai<'arena>>)>,
 		#[parse(with = "many0")]
-		properties: &'arena [SpaceIntervalProperty<'arena>],
+		properties: List<'arena, SpaceIntervalProperty<'arena>>,
 	},
-	Properties(#[parse(with = "many1")] &'arena [SpaceIntervalProperty<'arena>]),
+	Properties(#[parse(with = "many1")] List<'arena, SpaceIntervalProperty<'arena>>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct SpaceIntervalProperty<'arena>(Fehe<'arena>, IntervalProperty<'arena>);
+pub struct SpaceIntervalProperty<'arena>(
+	#[tree_node(label = "fehe")] Fehe<'arena>,
+	#[tree_node(label = "property")] IntervalProperty<'arena>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SpaceMotion<'arena> {
@@ -578,6 +671,7 @@ pub struct SpaceMotion<'arena> {
 }
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct Sumti<'arena> {
 	pub inner: Sumti1<'arena>,
 	pub vuho_relative: Option<VuhoRelative<'arena>>,
@@ -585,21 +679,25 @@ pub struct Sumti<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Sumti1<'arena>(
-	pub Sumti2<'arena>,
+	#[tree_node(label = "first")] pub Sumti2<'arena>,
 	#[parse(with = "many0")]
-	pub  &'arena [SumtiLikeConnectedPost<'arena, Sumti2<'arena>, Sumti<'arena>>],
+	#[tree_node(label = "connected")]
+	pub List<'arena, SumtiLikeConnectedPost<'arena, Sumti2<'arena>, Sumti<'arena>>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum SumtiLikeConnectedPost<'arena, Normal, Parenthesized> {
-	Normal(JoikEk<'arena>, Normal),
+	Normal(
+		#[tree_node(label = "connective")] JoikEk<'arena>,
+		#[tree_node(label = "sumti")] Normal,
+	),
 	Grouped(
-		JoikEk<'arena>,
-		Option<TagWords<'arena>>,
-		WithFree<'arena, Ke<'arena>>,
-		Parenthesized,
-		Option<Kehe<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "connective")] JoikEk<'arena>,
+		#[tree_node(label = "tag")] Option<TagWords<'arena>>,
+		#[tree_node(label = "ke")] WithFree<'arena, Ke<'arena>>,
+		#[tree_node(label = "sumti")] Parenthesized,
+		#[tree_node(label = "kehe")] Option<Kehe<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
@@ -622,7 +720,7 @@ pub struct VuhoRelative<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct RelativeClauses<'arena>(
-	Separated<'arena, RelativeClause<'arena>, WithFree<'arena, Zihe<'arena>>>,
+	pub Separated<'arena, RelativeClause<'arena>, WithFree<'arena, Zihe<'arena>>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
@@ -650,22 +748,28 @@ pub struct NoiRelativeClause<'arena> {
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Sumti3<'arena>(
-	#[parse(with = "many0")] pub &'arena [Sumti3ConnectedPre<'arena>],
-	Sumti4<'arena>,
+	#[parse(with = "many0")]
+	#[tree_node(label = "connected")]
+	pub List<'arena, Sumti3ConnectedPre<'arena>>,
+	#[tree_node(label = "last")] pub Sumti4<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct Sumti3ConnectedPre<'arena>(pub Gek<'arena>, pub Sumti<'arena>, pub Gik<'arena>);
+pub struct Sumti3ConnectedPre<'arena>(
+	#[tree_node(label = "gek")] pub Gek<'arena>,
+	#[tree_node(label = "first")] pub Sumti<'arena>,
+	#[tree_node(label = "gik")] pub Gik<'arena>,
+);
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Sumti4<'arena> {
 	Normal {
-		quantifier: Option<Quantifier<'arena>>,
+		quantifier: Option<&'arena Quantifier<'arena>>,
 		inner: &'arena SumtiComponent<'arena>,
 		relative_clauses: Option<RelativeClauses<'arena>>,
 	},
 	SelbriShorthand {
-		quantifier: Quantifier<'arena>,
+		quantifier: &'arena Quantifier<'arena>,
 		inner: Selbri<'arena>,
 		ku: Option<Ku<'arena>>,
 		frees: Frees<'arena>,
@@ -674,17 +778,20 @@ pub enum Sumti4<'arena> {
 }
 
 #[derive(Debug, Parse, TreeNode)] // similar to part of `mekso::Operand3`
+#[parse(memoize)]
 pub enum Quantifier<'arena> {
 	Mekso(
-		WithFree<'arena, Vei<'arena>>,
-		Mekso<'arena>,
-		Option<Veho<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "vei")] WithFree<'arena, Vei<'arena>>,
+		#[tree_node(label = "mekso")] Mekso<'arena>,
+		#[tree_node(label = "veho")] Option<Veho<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Number(
-		Number<'arena>,
-		#[parse(not = "Moi<'_>")] Option<Boi<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "number")] Number<'arena>,
+		#[parse(not = "Moi<'_>")]
+		#[tree_node(label = "boi")]
+		Option<Boi<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
@@ -692,7 +799,7 @@ pub enum Quantifier<'arena> {
 pub struct Number<'arena> {
 	pub first: Pa<'arena>,
 	#[parse(with = "many0")]
-	pub rest: &'arena [NumberRest<'arena>],
+	pub rest: List<'arena, NumberRest<'arena>>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
@@ -705,11 +812,11 @@ pub enum NumberRest<'arena> {
 pub struct LerfuString<'arena> {
 	pub first: LerfuWord<'arena>,
 	#[parse(with = "many0")]
-	pub rest: &'arena [NumberRest<'arena>],
+	pub rest: List<'arena, NumberRest<'arena>>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
-pub struct MiscNumbers<'arena>(#[parse(with = "many1")] &'arena [NumberRest<'arena>]);
+pub struct MiscNumbers<'arena>(#[parse(with = "many1")] pub List<'arena, NumberRest<'arena>>);
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum LerfuWord<'arena> {
@@ -729,10 +836,14 @@ pub enum LerfuWord<'arena> {
 #[derive(Debug, Parse, TreeNode)]
 pub enum Lerfu<'arena> {
 	Bu(
-		Option<Bahe>,
-		BuInner,
-		#[parse(with = "many1")] &'arena [Bu],
-		#[parse(with = "many0")] &'arena [Indicators<'arena>],
+		#[tree_node(label = "bahe")] Option<Bahe>,
+		#[tree_node(label = "inner")] BuInner,
+		#[parse(with = "many1")]
+		#[tree_node(label = "bu")]
+		List<'arena, Bu>,
+		#[parse(with = "many0")]
+		#[tree_node(label = "indicators")]
+		List<'arena, Indicators<'arena>>,
 	),
 	By(By<'arena>),
 }
@@ -741,7 +852,7 @@ pub enum Lerfu<'arena> {
 #[parse(
 	postcond = "|Self(token)| !matches!(token.selmaho, Selmaho::Bu | Selmaho::Zei | Selmaho::Si | Selmaho::Su | Selmaho::Sa | Selmaho::Faho)"
 )]
-pub struct BuInner(Token);
+pub struct BuInner(pub AnyToken);
 
 pub type SumtiComponent<'arena> = WithFree<'arena, SumtiComponent1<'arena>>;
 
@@ -751,44 +862,50 @@ pub enum SumtiComponent1<'arena> {
 	Gadri(&'arena GadriSumti<'arena>),
 	La(LaSumti<'arena>),
 	Lohu(LohuSumti<'arena>),
-	Lu(LuSumti<'arena>),
-	Modified(ModifiedSumti<'arena>),
+	Lu(&'arena LuSumti<'arena>),
+	Modified(&'arena ModifiedSumti<'arena>),
 	LerfuString(
-		LerfuString<'arena>,
-		#[parse(not = "Moi<'_>")] Option<Boi<'arena>>,
+		#[tree_node(label = "lerfu_string")] LerfuString<'arena>,
+		#[parse(not = "Moi<'_>")]
+		#[tree_node(label = "boi")]
+		Option<Boi<'arena>>,
 	),
 	Zo(ZoSumti),
 	Zoi(ZoiSumti),
 	Li(
-		WithFree<'arena, Li<'arena>>,
-		Mekso<'arena>,
-		Option<Loho<'arena>>,
+		#[tree_node(label = "li")] WithFree<'arena, Li<'arena>>,
+		#[tree_node(label = "mekso")] Mekso<'arena>,
+		#[tree_node(label = "loho")] Option<Loho<'arena>>,
 	),
 }
 
 #[derive(Debug, TreeNode)]
 pub struct LohuSumti<'arena> {
 	pub lohu: Lohu,
-	pub inner: &'arena [Token],
+	pub inner: List<'arena, AnyToken>,
 	pub lehu: Lehu<'arena>,
 }
 
 impl<'arena> Parse<'arena> for LohuSumti<'arena> {
-	fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> ParseResult<'a, Self> {
-		nom::combinator::map(
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> ParseResult<'a, Self> {
+		let result = nom::combinator::map(
 			nom::sequence::tuple((
-				|input| Parse::parse(input, arena),
+				|input| Parse::parse(input, context),
 				nom::combinator::cut(nom::multi::many_till(
-					|input| Parse::parse(input, arena),
-					|input| Parse::parse(input, arena),
+					|input| Parse::parse(input, context),
+					|input| Parse::parse(input, context),
 				)),
 			)),
 			|(lohu, (inner, lehu))| Self {
 				lohu,
-				inner: arena.alloc_slice_fill_iter(inner.into_iter()),
+				inner: List::new(inner, context.arena),
 				lehu,
 			},
-		)(input)
+		)(input);
+		if result.is_ok() {
+			context.arena.record_parsed("LohuSumti", size_of::<Self>());
+		}
+		result
 	}
 }
 
@@ -810,10 +927,14 @@ pub struct ModifiedSumti<'arena> {
 #[derive(Debug, Parse, TreeNode)]
 pub enum SumtiModifier<'arena> {
 	Lahe(Lahe<'arena>),
-	NaheBo(Nahe<'arena>, Bo<'arena>),
+	NaheBo(
+		#[tree_node(label = "nahe")] Nahe<'arena>,
+		#[tree_node(label = "bo")] Bo<'arena>,
+	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
+#[parse(memoize)]
 pub struct GadriSumti<'arena> {
 	pub gadri: WithFree<'arena, Gadri<'arena>>,
 	pub pre: GadriSumtiPre<'arena>,
@@ -846,24 +967,27 @@ pub enum GadriSumtiPre<'arena> {
 #[derive(Debug, Parse, TreeNode)]
 pub enum GadriSumtiContents<'arena> {
 	Selbri(
-		Option<Quantifier<'arena>>,
-		Selbri<'arena>,
-		Option<RelativeClauses<'arena>>,
+		#[tree_node(label = "quantifier")] Option<&'arena Quantifier<'arena>>,
+		#[tree_node(label = "selbri")] Selbri<'arena>,
+		#[tree_node(label = "relative_clauses")] Option<RelativeClauses<'arena>>,
+	),
+	Sumti(
+		#[tree_node(label = "quantifier")] &'arena Quantifier<'arena>,
+		#[tree_node(label = "sumti")] Sumti<'arena>,
 	),
-	Sumti(Quantifier<'arena>, Sumti<'arena>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct LaSumti<'arena> {
 	pub la: La<'arena>,
 	#[parse(with = "many1")]
-	pub inner: &'arena [Cmevla<'arena>],
+	pub inner: List<'arena, Cmevla<'arena>>,
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct ZoSumti {
 	pub zo: Zo,
-	pub quoted: Token,
+	pub quoted: AnyToken,
 }
 
 #[derive(Debug, Parse, TreeNode)]
@@ -876,7 +1000,7 @@ pub struct ZoiSumti {
 
 #[derive(Debug, Parse)]
 #[repr(transparent)]
-pub struct ZoiDelimiter(Span);
+pub struct ZoiDelimiter(pub TokenIndex);
 
 impl TreeNode for ZoiDelimiter {
 	fn name(&self) -> &'static str {
@@ -887,19 +1011,43 @@ impl TreeNode for ZoiDelimiter {
 		false
 	}
 
-	fn start_location(&self) -> Option<crate::span::Location> {
-		Some(self.0.start)
+	fn start_token(&self) -> Option<u32> {
+		Some(self.0.index())
 	}
 
-	fn end_location(&self) -> Option<crate::span::Location> {
-		Some(self.0.end)
+	fn end_token(&self) -> Option<u32> {
+		Some(self.0.index() + 1)
 	}
 
 	fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		NodeRef::ZoiDelimiter(self)
+	}
+}
+
+impl<'ast> Visit<'ast> for ZoiDelimiter {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		visitor.visit_token(Selmaho::ZoiDelimiter, self);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+}
+
+impl<'new> FoldWith<'new> for ZoiDelimiter {
+	type Output = Self;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self {
+		folder.fold_token(Selmaho::ZoiDelimiter, self)
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, _: &mut F) -> Self {
+		Self(self.0)
+	}
 }
 
 #[derive(Debug, Parse)]
-pub struct ZoiText(Span);
+pub struct ZoiText(pub TokenIndex);
 
 impl TreeNode for ZoiText {
 	fn name(&self) -> &'static str {
@@ -910,57 +1058,90 @@ impl TreeNode for ZoiText {
 		false
 	}
 
-	fn start_location(&self) -> Option<crate::span::Location> {
-		Some(self.0.start)
+	fn start_token(&self) -> Option<u32> {
+		Some(self.0.index())
 	}
 
-	fn end_location(&self) -> Option<crate::span::Location> {
-		Some(self.0.end)
+	fn end_token(&self) -> Option<u32> {
+		Some(self.0.index() + 1)
 	}
 
 	fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		NodeRef::ZoiText(self)
+	}
+}
+
+impl<'ast> Visit<'ast> for ZoiText {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		visitor.visit_token(Selmaho::AnyText, self);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+}
+
+impl<'new> FoldWith<'new> for ZoiText {
+	type Output = Self;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self {
+		folder.fold_token(Selmaho::AnyText, self)
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, _: &mut F) -> Self {
+		Self(self.0)
+	}
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum Free<'arena> {
 	Sei(
-		WithFree<'arena, Sei<'arena>>,
-		Args<'arena>,
-		Option<SeiTail<'arena>>,
-		Option<WithFree<'arena, Sehu<'arena>>>,
+		#[tree_node(label = "sei")] WithFree<'arena, Sei<'arena>>,
+		#[tree_node(label = "args")] Args<'arena>,
+		#[tree_node(label = "tail")] Option<SeiTail<'arena>>,
+		#[tree_node(label = "sehu")] Option<WithFree<'arena, Sehu<'arena>>>,
 	),
 	Soi(
-		WithFree<'arena, Soi<'arena>>,
-		&'arena (Sumti<'arena>, Option<Sumti<'arena>>),
-		Option<Sehu<'arena>>,
+		#[tree_node(label = "soi")] WithFree<'arena, Soi<'arena>>,
+		#[tree_node(label = "sumti")] &'arena (Sumti<'arena>, Option<Sumti<'arena>>),
+		#[tree_node(label = "sehu")] Option<Sehu<'arena>>,
 	),
 	Vocative(Vocative<'arena>),
-	Mai(MiscNumbers<'arena>, Mai<'arena>),
-	To(To<'arena>, Text<'arena>, Option<Toi<'arena>>),
+	Mai(
+		#[tree_node(label = "number")] MiscNumbers<'arena>,
+		#[tree_node(label = "mai")] Mai<'arena>,
+	),
+	To(
+		#[tree_node(label = "to")] To<'arena>,
+		#[tree_node(label = "text")] Text<'arena>,
+		#[tree_node(label = "toi")] Option<Toi<'arena>>,
+	),
 	Xi(Subscript<'arena>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Vocative<'arena>(
-	pub VocativeWords<'arena>,
-	pub Option<RelativeClauses<'arena>>,
-	pub VocativeValue<'arena>,
-	pub Option<RelativeClauses<'arena>>,
-	pub Option<Dohu<'arena>>,
+	#[tree_node(label = "words")] pub VocativeWords<'arena>,
+	#[tree_node(label = "words_relative_clauses")] pub Option<RelativeClauses<'arena>>,
+	#[tree_node(label = "value")] pub VocativeValue<'arena>,
+	#[tree_node(label = "value_relative_clauses")] pub Option<RelativeClauses<'arena>>,
+	#[tree_node(label = "dohu")] pub Option<Dohu<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct SeiTail<'arena>(
-	pub Option<Cu<'arena>>,
-	pub Frees<'arena>,
-	pub Selbri<'arena>,
+	#[tree_node(label = "cu")] pub Option<Cu<'arena>>,
+	#[tree_node(label = "frees")] pub Frees<'arena>,
+	#[tree_node(label = "selbri")] pub Selbri<'arena>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
 pub enum VocativeWords<'arena> {
 	Coi(
-		#[parse(with = "many1")] &'arena [(Coi<'arena>, Option<Nai<'arena>>)],
-		Option<Doi<'arena>>,
+		#[parse(with = "many1")]
+		#[tree_node(label = "coi")]
+		List<'arena, (Coi<'arena>, Option<Nai<'arena>>)>,
+		#[tree_node(label = "doi")] Option<Doi<'arena>>,
 	),
 	Doi(Doi<'arena>),
 }
@@ -968,35 +1149,39 @@ pub enum VocativeWords<'arena> {
 #[derive(Debug, Parse, TreeNode)]
 pub enum VocativeValue<'arena> {
 	Selbri(&'arena Selbri<'arena>),
-	Cmevla(#[parse(with = "many1")] &'arena [Cmevla<'arena>]),
+	Cmevla(#[parse(with = "many1")] List<'arena, Cmevla<'arena>>),
 	Sumti(Option<&'arena Sumti<'arena>>),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Subscript<'arena>(
-	pub WithFree<'arena, Xi<'arena>>,
-	pub WithFree<'arena, SubscriptValue<'arena>>,
+	#[tree_node(label = "xi")] pub WithFree<'arena, Xi<'arena>>,
+	#[tree_node(label = "value")] pub WithFree<'arena, SubscriptValue<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)] // similar to part of `mekso::Operand3`
 pub enum SubscriptValue<'arena> {
 	Mekso(
-		WithFree<'arena, Vei<'arena>>,
-		Mekso<'arena>,
-		Option<Veho<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "vei")] WithFree<'arena, Vei<'arena>>,
+		#[tree_node(label = "mekso")] Mekso<'arena>,
+		#[tree_node(label = "veho")] Option<Veho<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 	Number(
-		MiscNumbers<'arena>,
-		#[parse(not = "Moi<'_>")] Option<Boi<'arena>>,
-		Frees<'arena>,
+		#[tree_node(label = "number")] MiscNumbers<'arena>,
+		#[parse(not = "Moi<'_>")]
+		#[tree_node(label = "boi")]
+		Option<Boi<'arena>>,
+		#[tree_node(label = "frees")] Frees<'arena>,
 	),
 }
 
 #[derive(Debug, Parse, TreeNode)]
 pub struct Indicators<'arena>(
-	pub Option<Fuhe<'arena>>,
-	#[parse(with = "many1")] pub &'arena [Indicator<'arena>],
+	#[tree_node(label = "fuhe")] pub Option<Fuhe<'arena>>,
+	#[parse(with = "many1")]
+	#[tree_node(label = "indicators")]
+	pub List<'arena, Indicator<'arena>>,
 );
 
 #[derive(Debug, Parse, TreeNode)]
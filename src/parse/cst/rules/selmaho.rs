@@ -1,39 +1,70 @@
-use bumpalo::Bump as Arena;
 use macros::TreeNode;
 
-use super::helpers::{many0, PassArena};
+use super::helpers::{many0, List, PassContext, TokenIndex};
 use super::Indicators;
 use crate::lex::{Selmaho, Token};
 use crate::parse::cst::error::{Error, WithLocation};
-use crate::parse::cst::parse_trait::{Parse, Result as ParseResult};
+use crate::parse::cst::fold::{Fold, FoldWith};
+use crate::parse::cst::node::NodeRef;
+use crate::parse::cst::parse_trait::{Context, Parse, Result as ParseResult};
+use crate::parse::cst::visit::{Visit, Visitor};
 use crate::parse::tree_node::TreeNode;
-use crate::span::{Location, Span};
 
-pub(in crate::parse::cst::rules) trait SelmahoTypeRaw:
-	TryFrom<Token, Error = Error> + TryFrom<Option<Token>, Error = Error>
-{
+/// A token type that can be built from the index of a token of its selmaho.
+pub(in crate::parse::cst::rules) trait SelmahoTypeRaw: Sized {
+	const SELMAHO: Selmaho;
+
+	fn from_token(token: TokenIndex) -> Self;
+}
+
+pub(in crate::parse::cst::rules) fn selmaho_raw<'a, T: SelmahoTypeRaw>(
+	input: &'a [Token],
+	context: &Context<'_>,
+) -> ParseResult<'a, T> {
+	match input.first() {
+		Some(token) if token.selmaho == T::SELMAHO => {
+			Ok((&input[1..], T::from_token(context.token_index(input))))
+		}
+		got => Err(nom::Err::Error(WithLocation {
+			location: input.get(1..).unwrap_or_default(),
+			error: Error::ExpectedGot {
+				expected: (&[T::SELMAHO] as &[Selmaho]).into(),
+				got: got.copied(),
+			},
+		})),
+	}
 }
 
-pub(in crate::parse::cst::rules) fn selmaho_raw<T: SelmahoTypeRaw>(
-	input: &[Token],
-) -> ParseResult<'_, T> {
-	let mut input = input.iter();
-	T::try_from(input.next().copied())
-		.map(|matched| (input.as_slice(), matched))
-		.map_err(|error| {
-			nom::Err::Error(WithLocation {
-				location: input.as_slice(),
-				error,
-			})
-		})
+/// Implement `Visit` and `FoldWith` for a token type that has no children.
+macro_rules! raw_token_traversal_impls {
+	($name:ident) => {
+		impl<'ast> Visit<'ast> for $name {
+			fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+				visitor.visit_token(Selmaho::$name, self);
+			}
+
+			fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+		}
+
+		impl<'new> FoldWith<'new> for $name {
+			type Output = Self;
+
+			fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self {
+				folder.fold_token(Selmaho::$name, self)
+			}
+
+			fn fold_children<F: Fold<'new> + ?Sized>(&self, _: &mut F) -> Self {
+				Self { token: self.token }
+			}
+		}
+	};
 }
 
 macro_rules! token_types {
 	(@inner $name:ident) => {paste::paste!{
 		#[derive(Debug)]
 		pub struct [<$name Inner>] {
-			pub experimental: bool,
-			pub span: Span,
+			pub token: TokenIndex,
 		}
 
 		impl TreeNode for [<$name Inner>] {
@@ -42,65 +73,62 @@ macro_rules! token_types {
 			}
 
 			fn experimental(&self) -> bool {
-				self.experimental
+				self.token.experimental()
 			}
 
-			fn start_location(&self) -> Option<Location> {
-				Some(self.span.start)
+			fn start_token(&self) -> Option<u32> {
+				Some(self.token.index())
 			}
 
-			fn end_location(&self) -> Option<Location> {
-				Some(self.span.end)
+			fn end_token(&self) -> Option<u32> {
+				Some(self.token.index() + 1)
 			}
 
 			fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+
+			fn downcast(&self) -> NodeRef<'_> {
+				NodeRef::TokenInner(Selmaho::$name, self)
+			}
+		}
+
+		impl<'ast> Visit<'ast> for [<$name Inner>] {
+			fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+
+			fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+		}
+
+		impl<'new> FoldWith<'new> for [<$name Inner>] {
+			type Output = Self;
+
+			fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self {
+				self.fold_children(folder)
+			}
+
+			fn fold_children<F: Fold<'new> + ?Sized>(&self, _: &mut F) -> Self {
+				Self { token: self.token }
+			}
 		}
 	}};
 	(@raw $name:ident) => {
 		#[derive(Debug)]
 		pub struct $name {
-			pub experimental: bool,
-			pub span: Span,
+			pub token: TokenIndex,
 		}
 
-		impl TryFrom<Token> for $name {
-			type Error = Error;
-
-			fn try_from(token: Token) -> Result<Self, Error> {
-				if token.selmaho == Selmaho::$name {
-					Ok(Self {
-						experimental: token.experimental,
-						span: token.span,
-					})
-				} else {
-					Err(Error::ExpectedGot {
-						expected: (&[Selmaho::$name] as &[Selmaho]).into(),
-						got: Some(token),
-					})
-				}
-			}
-		}
-
-		impl TryFrom<Option<Token>> for $name {
-			type Error = Error;
+		impl SelmahoTypeRaw for $name {
+			const SELMAHO: Selmaho = Selmaho::$name;
 
-			fn try_from(token: Option<Token>) -> Result<Self, Error> {
-				token
-					.ok_or(Error::ExpectedGot {
-						expected: (&[Selmaho::$name] as &[Selmaho]).into(),
-						got: None,
-					})
-					.and_then(Self::try_from)
+			fn from_token(token: TokenIndex) -> Self {
+				Self { token }
 			}
 		}
 
-		impl SelmahoTypeRaw for $name {}
-
 		impl<'arena> Parse<'arena> for $name {
-			fn parse<'a: 'arena>(input: &'a [Token], arena: &Arena) -> ParseResult<'a, Self> {
-				let (input, value) = selmaho_raw::<$name>(input)?;
-				let (_, ()) = nom::combinator::not(|input| Bu::parse(input, arena))(input)?;
-				let (_, ()) = nom::combinator::not(|input| Zei::parse(input, arena))(input)?;
+			fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> ParseResult<'a, Self> {
+				let (input, value) = selmaho_raw::<$name>(input, context)?;
+				let (_, ()) = nom::combinator::not(|input| Bu::parse(input, context))(input)?;
+				let (_, ()) = nom::combinator::not(|input| Zei::parse(input, context))(input)?;
+				context.arena.record_parsed(stringify!($name), size_of::<Self>());
 				Ok((input, value))
 			}
 		}
@@ -111,67 +139,59 @@ macro_rules! token_types {
 			}
 
 			fn experimental(&self) -> bool {
-				self.experimental
+				self.token.experimental()
 			}
 
-			fn start_location(&self) -> Option<Location> {
-				Some(self.span.start)
+			fn start_token(&self) -> Option<u32> {
+				Some(self.token.index())
 			}
 
-			fn end_location(&self) -> Option<Location> {
-				Some(self.span.end)
+			fn end_token(&self) -> Option<u32> {
+				Some(self.token.index() + 1)
 			}
 
 			fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+
+			fn downcast(&self) -> NodeRef<'_> {
+				NodeRef::$name(self)
+			}
+
+			fn elidable_terminator() -> Option<Selmaho> {
+				Selmaho::$name.elidable_terminator().map(|_| Selmaho::$name)
+			}
 		}
+
+		raw_token_traversal_impls!($name);
 	};
 	(@no_indicators $name:ident) => {paste::paste!{
 		token_types!(@inner $name);
 
 		#[derive(Debug, TreeNode)]
+		#[tree_node(selmaho($name))]
 		pub struct $name<'arena> {
-			pub bahe: &'arena [Bahe],
+			pub bahe: List<'arena, Bahe>,
 			pub inner: [<$name Inner>],
 		}
 
-		impl TryFrom<Token> for $name<'_> {
-			type Error = Error;
-
-			fn try_from(token: Token) -> Result<Self, Error> {
-				if token.selmaho == Selmaho::$name {
-					Ok(Self {
-						bahe: &[],
-						inner: [<$name Inner>] {
-							experimental: token.experimental,
-							span: token.span,
-						},
-					})
-				} else {
-					Err(Error::ExpectedGot {
-						expected: (&[Selmaho::$name] as &[Selmaho]).into(),
-						got: Some(token),
-					})
-				}
-			}
-		}
-
-		impl TryFrom<Option<Token>> for $name<'_> {
-			type Error = Error;
+		impl SelmahoTypeRaw for $name<'_> {
+			const SELMAHO: Selmaho = Selmaho::$name;
 
-			fn try_from(token: Option<Token>) -> Result<Self, Error> {
-				token.ok_or(Error::ExpectedGot { expected: (&[Selmaho::$name] as &[Selmaho]).into(), got: None }).and_then(Self::try_from)
+			fn from_token(token: TokenIndex) -> Self {
+				Self {
+					bahe: List::EMPTY,
+					inner: [<$name Inner>] { token },
+				}
 			}
 		}
 
-		impl SelmahoTypeRaw for $name<'_> {}
-
 		impl<'arena> Parse<'arena> for $name<'arena> {
-			fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> ParseResult<'a, Self> {
-				let (input, bahe) = nom::Parser::parse(&mut many0(PassArena::<Bahe>::new(arena), arena), input)?;
-				let (input, mut matched) = selmaho_raw::<Self>(input)?;
-				let (_, ()) = nom::combinator::not(|input| Bu::parse(input, arena))(input)?;
-				let (_, ()) = nom::combinator::not(|input| Zei::parse(input, arena))(input)?;
+			fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> ParseResult<'a, Self> {
+				let (input, bahe) = nom::Parser::parse(&mut many0(PassContext::<Bahe>::new(context), context.arena), input)?;
+				let (input, mut matched) = selmaho_raw::<Self>(input, context)?;
+				let (_, ()) = nom::combinator::not(|input| Bu::parse(input, context))(input)?;
+				let (_, ()) = nom::combinator::not(|input| Zei::parse(input, context))(input)?;
 				matched.bahe = bahe;
+				context.arena.record_parsed(stringify!($name), size_of::<Self>());
 				Ok((input, matched))
 			}
 		}
@@ -180,53 +200,35 @@ macro_rules! token_types {
 		token_types!(@inner $name);
 
 		#[derive(Debug, TreeNode)]
+		#[tree_node(selmaho($name))]
 		pub struct $name<'arena> {
-			pub bahe: &'arena [Bahe],
+			pub bahe: List<'arena, Bahe>,
 			pub inner: [<$name Inner>],
 			pub indicators: Option<&'arena Indicators<'arena>>,
 		}
 
-		impl TryFrom<Token> for $name<'_> {
-			type Error = Error;
-
-			fn try_from(token: Token) -> Result<Self, Error> {
-				if token.selmaho == Selmaho::$name {
-					Ok(Self {
-						bahe: &[],
-						inner: [<$name Inner>] {
-							experimental: token.experimental,
-							span: token.span,
-						},
-						indicators: None,
-					})
-				} else {
-					Err(Error::ExpectedGot {
-						expected: (&[Selmaho::$name] as &[Selmaho]).into(),
-						got: Some(token),
-					})
-				}
-			}
-		}
-
-		impl TryFrom<Option<Token>> for $name<'_> {
-			type Error = Error;
+		impl SelmahoTypeRaw for $name<'_> {
+			const SELMAHO: Selmaho = Selmaho::$name;
 
-			fn try_from(token: Option<Token>) -> Result<Self, Error> {
-				token.ok_or(Error::ExpectedGot { expected: (&[Selmaho::$name] as &[Selmaho]).into(), got: None }).and_then(Self::try_from)
+			fn from_token(token: TokenIndex) -> Self {
+				Self {
+					bahe: List::EMPTY,
+					inner: [<$name Inner>] { token },
+					indicators: None,
+				}
 			}
 		}
 
-		impl SelmahoTypeRaw for $name<'_> {}
-
 		impl<'arena> Parse<'arena> for $name<'arena> {
-			fn parse<'a: 'arena>(input: &'a [Token], arena: &'arena Arena) -> ParseResult<'a, Self> {
-				let (input, bahe) = nom::Parser::parse(&mut many0(PassArena::<Bahe>::new(arena), arena), input)?;
-				let (input, mut matched) = selmaho_raw::<Self>(input)?;
-				let (input, indicators) = <Option<&'arena Indicators<'arena>>>::parse(input, arena)?;
-				let (_, ()) = nom::combinator::not(|input| Bu::parse(input, arena))(input)?;
-				let (_, ()) = nom::combinator::not(|input| Zei::parse(input, arena))(input)?;
+			fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> ParseResult<'a, Self> {
+				let (input, bahe) = nom::Parser::parse(&mut many0(PassContext::<Bahe>::new(context), context.arena), input)?;
+				let (input, mut matched) = selmaho_raw::<Self>(input, context)?;
+				let (input, indicators) = <Option<&'arena Indicators<'arena>>>::parse(input, context)?;
+				let (_, ()) = nom::combinator::not(|input| Bu::parse(input, context))(input)?;
+				let (_, ()) = nom::combinator::not(|input| Zei::parse(input, context))(input)?;
 				matched.bahe = bahe;
 				matched.indicators = indicators;
+				context.arena.record_parsed(stringify!($name), size_of::<Self>());
 				Ok((input, matched))
 			}
 		}
@@ -246,192 +248,238 @@ macro_rules! token_types {
 	};
 }
 
-token_types! {
-	A,
-	#[raw] Bahe,
-	Bai,
-	Be,
-	Beho,
-	Bei,
-	Bihe,
-	Bihi,
-	Bo,
-	Boi,
-	By,
-	Caha,
-	Cai,
-	Cehe,
-	Cei,
-	Cmevla,
-	Co,
-	Coi,
-	Giha,
-	Cu,
-	Cuhe,
-	Daho,
-	Dohu,
-	Doi,
-	Tuhe,
-	Tuhu,
-	Fa,
-	Faha,
-	Faho,
-	Fehe,
-	Fehu,
-	Fiho,
-	Foi,
-	Fuha,
-	#[no_indicators] Fuhe,
-	Fuhivla,
-	Fuho,
-	Ga,
-	Gaho,
-	Gehu,
-	Gi,
-	Gismu,
-	Goha,
-	Goi,
-	Niho,
-	Guha,
-	I,
-	Ja,
-	Jai,
-	Johi,
-	Joi,
-	Ke,
-	Kehe,
-	Kei,
-	Ki,
-	Koha,
-	Ku,
-	Kuhe,
-	Kuho,
-	La,
-	Lahe,
-	Lau,
-	Le,
-	Lehu,
-	Li,
-	Lihu,
-	Loho,
-	#[raw] Lohu,
-	#[raw] Lu,
-	Luhu,
-	Lujvo,
-	Maho,
-	Mai,
-	Me,
-	Mehu,
-	Mohe,
-	Mohi,
-	Moi,
-	Na,
-	Nahe,
-	Nahu,
-	Nai,
-	Nihe,
-	Noi,
-	Nu,
-	Nuha,
-	Nuhi,
-	Nuhu,
-	Pa,
-	Pehe,
-	Peho,
-	Pu,
-	Raho,
-	Roi,
-	Se,
-	Sehu,
-	Sei,
-	Soi,
-	Tahe,
-	Tehu,
-	Tei,
-	To,
-	Toi,
-	Ui,
-	Va,
-	Vau,
-	Veha,
-	Veho,
-	Vei,
-	Viha,
-	Vuho,
-	Vuhu,
-	Xi,
-	Y,
-	Zaho,
-	Zeha,
-	#[no_indicators] Zei,
-	Zi,
-	Zihe,
-	#[raw] Zo,
-	Zohu,
-	#[raw] Zoi,
+/// Calls `$callback!` with the list of token types, after any other arguments.
+///
+/// Tokens marked `#[raw]` have no `BAhE` or indicators and no lifetime parameter, and tokens marked `#[no_indicators]` have no indicators.
+macro_rules! with_token_types {
+	($callback:ident $($args:tt)*) => {
+		$callback! {
+			$($args)*
+			A,
+			#[raw] Bahe,
+			Bai,
+			Be,
+			Beho,
+			Bei,
+			Bihe,
+			Bihi,
+			Bo,
+			Boi,
+			By,
+			Caha,
+			Cai,
+			Cehe,
+			Cei,
+			Cmevla,
+			Co,
+			Coi,
+			Giha,
+			Cu,
+			Cuhe,
+			Daho,
+			Dohu,
+			Doi,
+			Tuhe,
+			Tuhu,
+			Fa,
+			Faha,
+			Faho,
+			Fehe,
+			Fehu,
+			Fiho,
+			Foi,
+			Fuha,
+			#[no_indicators] Fuhe,
+			Fuhivla,
+			Fuho,
+			Ga,
+			Gaho,
+			Gehu,
+			Gi,
+			Gismu,
+			Goha,
+			Goi,
+			Niho,
+			Guha,
+			I,
+			Ja,
+			Jai,
+			Johi,
+			Joi,
+			Ke,
+			Kehe,
+			Kei,
+			Ki,
+			Koha,
+			Ku,
+			Kuhe,
+			Kuho,
+			La,
+			Lahe,
+			Lau,
+			Le,
+			Lehu,
+			Li,
+			Lihu,
+			Loho,
+			#[raw] Lohu,
+			#[raw] Lu,
+			Luhu,
+			Lujvo,
+			Maho,
+			Mai,
+			Me,
+			Mehu,
+			Mohe,
+			Mohi,
+			Moi,
+			Na,
+			Nahe,
+			Nahu,
+			Nai,
+			Nihe,
+			Noi,
+			Nu,
+			Nuha,
+			Nuhi,
+			Nuhu,
+			Pa,
+			Pehe,
+			Peho,
+			Pu,
+			Raho,
+			Roi,
+			Se,
+			Sehu,
+			Sei,
+			Soi,
+			Tahe,
+			Tehu,
+			Tei,
+			To,
+			Toi,
+			Ui,
+			Va,
+			Vau,
+			Veha,
+			Veho,
+			Vei,
+			Viha,
+			Vuho,
+			Vuhu,
+			Xi,
+			Y,
+			Zaho,
+			Zeha,
+			#[no_indicators] Zei,
+			Zi,
+			Zihe,
+			#[raw] Zo,
+			Zohu,
+			#[raw] Zoi,
+		}
+	};
 }
+pub(in crate::parse) use with_token_types;
+
+with_token_types!(token_types);
 
 #[derive(Debug)]
 pub struct Bu {
-	pub experimental: bool,
-	pub span: Span,
+	pub token: TokenIndex,
 }
 
-impl TryFrom<Token> for Bu {
-	type Error = Error;
-
-	fn try_from(token: Token) -> Result<Self, Error> {
-		if token.selmaho == Selmaho::Bu {
-			Ok(Self {
-				experimental: token.experimental,
-				span: token.span,
-			})
-		} else {
-			Err(Error::ExpectedGot {
-				expected: (&[Selmaho::Bu] as &[Selmaho]).into(),
-				got: Some(token),
-			})
+impl SelmahoTypeRaw for Bu {
+	const SELMAHO: Selmaho = Selmaho::Bu;
+
+	fn from_token(token: TokenIndex) -> Self {
+		Self { token }
+	}
+}
+
+impl<'arena> Parse<'arena> for Bu {
+	fn parse<'a: 'arena>(input: &'a [Token], context: &Context<'arena>) -> ParseResult<'a, Self> {
+		let result = selmaho_raw::<Bu>(input, context);
+		if result.is_ok() {
+			context.arena.record_parsed("Bu", size_of::<Self>());
 		}
+		result
 	}
 }
 
-impl TryFrom<Option<Token>> for Bu {
-	type Error = Error;
+impl TreeNode for Bu {
+	fn name(&self) -> &'static str {
+		Selmaho::Bu.as_repr()
+	}
 
-	fn try_from(token: Option<Token>) -> Result<Self, Error> {
-		token
-			.ok_or(Error::ExpectedGot {
-				expected: (&[Selmaho::Bu] as &[Selmaho]).into(),
-				got: None,
-			})
-			.and_then(Self::try_from)
+	fn experimental(&self) -> bool {
+		self.token.experimental()
 	}
-}
 
-impl SelmahoTypeRaw for Bu {}
+	fn start_token(&self) -> Option<u32> {
+		Some(self.token.index())
+	}
 
-impl<'arena> Parse<'arena> for Bu {
-	fn parse<'a: 'arena>(input: &'a [Token], _: &Arena) -> ParseResult<'a, Self> {
-		selmaho_raw::<Bu>(input)
+	fn end_token(&self) -> Option<u32> {
+		Some(self.token.index() + 1)
+	}
+
+	fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		NodeRef::Bu(self)
 	}
 }
 
-impl TreeNode for Bu {
+raw_token_traversal_impls!(Bu);
+
+/// Any token, such as the word quoted by `zo` or the words in `lo'u … le'u`.
+#[derive(Debug, Clone, Copy)]
+pub struct AnyToken {
+	pub selmaho: Selmaho,
+	pub token: TokenIndex,
+}
+
+impl TreeNode for AnyToken {
 	fn name(&self) -> &'static str {
-		Selmaho::Bu.as_repr()
+		self.selmaho.as_repr()
 	}
 
 	fn experimental(&self) -> bool {
-		self.experimental
+		self.token.experimental()
 	}
 
-	fn start_location(&self) -> Option<Location> {
-		Some(self.span.start)
+	fn start_token(&self) -> Option<u32> {
+		Some(self.token.index())
 	}
 
-	fn end_location(&self) -> Option<Location> {
-		Some(self.span.end)
+	fn end_token(&self) -> Option<u32> {
+		Some(self.token.index() + 1)
 	}
 
 	fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		NodeRef::Token(self)
+	}
+}
+
+impl<'ast> Visit<'ast> for AnyToken {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		visitor.visit_token(self.selmaho, self);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, _: &mut V) {}
+}
+
+impl<'new> FoldWith<'new> for AnyToken {
+	type Output = Self;
+
+	fn fold_with<F: Fold<'new> + ?Sized>(&self, folder: &mut F) -> Self {
+		folder.fold_token(self.selmaho, self)
+	}
+
+	fn fold_children<F: Fold<'new> + ?Sized>(&self, _: &mut F) -> Self {
+		*self
+	}
 }
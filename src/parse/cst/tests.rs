@@ -1879,3 +1879,27 @@ assert_parse![
 	// 21.1
 	"ni'o la berti brife jo'u la solri .i la berti brife jo'u la solri pu troci leka djuno ledu'u makau traji leka vlipa vau fo le'i me lenei .icabo le pa litru noi dasni lo glare kosta cu mo'u klama .i lu'i le remei pu simxu leka tugni fi lenu traji leka vlipa fa le traji be leka clira fa lonu ce'u snada leka gasnu lenu le pa litru co'u dasni le kosta .i baku la berti brife co'a traji cupra le brife .i ku'i lonu by.by. zenba leka cupra le xokau brife cu rinka lonu le pa litru cu zukte leka zenba leka sela'u li xokau se tagji le kosta .ibazabo la berti brife co'u troci .i baku la solri co'a dirce lo milxe glare .ibazibo le pa litru co'u dasni le kosta .iseki'ubo la berti brife co'a bilga tugni fi lonu la solri cu traji leka vlipa vau fo la berti brife ce la solri",
 ];
+
+macro_rules! assert_memoized_error {
+	($($name:ident: $sentence:expr),* $(,)?) => {
+		$(
+			#[test]
+			fn $name() {
+				let sentence: String = $sentence;
+				let tokens = crate::lex(&sentence).collect::<Result<Vec<_>, _>>().unwrap();
+				let arena = crate::Arena::new();
+				let plain = super::Cst::parse(&tokens, &arena).unwrap_err();
+				let memoized = super::Cst::parse_memoized(&tokens, &arena).unwrap_err();
+				assert_eq!(plain.location.len(), memoized.location.len());
+				assert_eq!(format!("{:?}", plain.error), format!("{:?}", memoized.error));
+			}
+		)*
+	};
+}
+
+assert_memoized_error![
+	memoized_error_stray_terminator: "mi klama ku'o".to_owned(),
+	memoized_error_unclosed_relative_clauses: format!("mi klama {}brode", "lo broda poi ".repeat(4)),
+	memoized_error_unclosed_abstractions: format!("{}mi klama", "lo nu ".repeat(4)),
+	memoized_error_extra_terminators: format!("mi djica {}mi klama{}", "lo nu ".repeat(2), " kei ku".repeat(3)),
+];
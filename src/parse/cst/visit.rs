@@ -0,0 +1,296 @@
+//! Typed traversal of the CST.
+//!
+//! This module centers around [`Visitor`], which has a method for each rule type, and [`Visit`], which is implemented by every node and dispatches to the appropriate method.
+//! The default implementation of each method visits the node's children, so a visitor only needs to override the methods for the nodes it is interested in.
+//! Tokens are all handled by [`Visitor::visit_token`].
+//!
+//! For example, to count the sumti in a text:
+//!
+//! ```rust
+//! use sneturfahi::parse::cst::rules::Sumti;
+//! use sneturfahi::parse::cst::visit::{Visit as _, Visitor};
+//!
+//! struct CountSumti(usize);
+//!
+//! impl<'ast> Visitor<'ast> for CountSumti {
+//! 	fn visit_sumti(&mut self, sumti: &'ast Sumti<'_>) {
+//! 		self.0 += 1;
+//! 		// keep counting within relative clauses and the like
+//! 		sumti.walk(self);
+//! 	}
+//! }
+//!
+//! let tokens: Vec<_> = sneturfahi::lex("mi klama lo zdani poi do nelci ke'a")
+//! 	.collect::<Result<_, _>>()
+//! 	.unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//! let mut counter = CountSumti(0);
+//! cst.visit(&mut counter);
+//! assert_eq!(counter.0, 4);
+//! ```
+
+#![allow(clippy::wildcard_imports)]
+
+use crate::lex::Selmaho;
+use crate::parse::cst::rules::connectives::*;
+use crate::parse::cst::rules::mekso::*;
+use crate::parse::cst::rules::*;
+use crate::parse::tree_node::TreeNode;
+
+/// A node, or a container of nodes, that can be visited by a [`Visitor`].
+pub trait Visit<'ast> {
+	/// Call the method of `visitor` that corresponds to this node.
+	///
+	/// For containers such as `Option` and slices, this visits each contained node.
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V);
+
+	/// Visit each child of this node.
+	///
+	/// This is the default behavior of the methods of [`Visitor`].
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V);
+}
+
+/// Calls `$callback!` with the list of rule types, separated by whether they have a lifetime parameter, after any other arguments.
+///
+/// Types that derive `TreeNode` without `passthrough_child` must be listed here, or the derived code will fail to compile.
+macro_rules! with_rule_types {
+	($callback:ident $($args:tt)*) => {
+		$callback! {
+			$($args)*
+			arena: [
+				Text,
+				Paragraphs,
+				Paragraph,
+				ParagraphItem,
+				Fragment,
+				Sentences1,
+				Sentences3,
+				ParagraphSeparator,
+				SentenceSeparator,
+				ConnectedSentenceSeparator,
+				CloseSentenceSeparator,
+				Sentence,
+				SentenceTail,
+				SentenceTail1,
+				SentenceTail1After,
+				SentenceTail2,
+				SentenceTail2Connective,
+				SentenceTail2After,
+				SentenceTail3,
+				SentenceTail3After,
+				SentenceTail4,
+				GekSentence,
+				Subsentence,
+				Prenex,
+				Arg,
+				PeheConnective,
+				Arg3,
+				Termset,
+				Selbri,
+				SelbriBefore,
+				Selbri1,
+				Selbri2,
+				Selbri3,
+				SelbriLikeConnectedPost<Normal, Parenthesized>,
+				Selbri4,
+				Selbri5,
+				NaheGuhekTGik<T>,
+				Selbri6,
+				TanruUnit,
+				TanruUnit1,
+				BeforeTanruUnit,
+				BoundArguments,
+				TanruUnit2,
+				ZeiClause,
+				ZeiBuInner,
+				ZeiTail,
+				Tag,
+				TagValue,
+				TagWord,
+				TimeSpaceCaha,
+				Time,
+				TimeOffset,
+				TimeDuration,
+				IntervalProperty,
+				Space,
+				SpaceOffset,
+				SpaceInterval,
+				SpaceIntervalProperty,
+				SpaceMotion,
+				Sumti,
+				Sumti1,
+				SumtiLikeConnectedPost<Normal, Parenthesized>,
+				VuhoRelative,
+				RelativeClauses,
+				RelativeClause,
+				GoiRelativeClause,
+				NoiRelativeClause,
+				Sumti3,
+				Sumti3ConnectedPre,
+				Sumti4,
+				Quantifier,
+				Number,
+				NumberRest,
+				LerfuString,
+				MiscNumbers,
+				LerfuWord,
+				Lerfu,
+				SumtiComponent1,
+				LohuSumti,
+				LuSumti,
+				ModifiedSumti,
+				SumtiModifier,
+				GadriSumti,
+				Gadri,
+				GadriSumtiContents,
+				LaSumti,
+				Free,
+				Vocative,
+				SeiTail,
+				VocativeWords,
+				VocativeValue,
+				Subscript,
+				SubscriptValue,
+				Indicators,
+				Indicator,
+				Separated<Item, Separator>,
+				// connectives
+				NaSeTNai<T>,
+				Ek,
+				Jek,
+				Gihek,
+				Guhek,
+				Interval,
+				Joik,
+				Gik,
+				Gek,
+				JoikJek,
+				JoikEk,
+				// mekso
+				Expression,
+				ReversePolish,
+				RPTail,
+				Expression1,
+				ForethoughtExpression,
+				Operand,
+				Operand1,
+				Operand2,
+				Operand2ConnectedPre,
+				Operand3,
+				Operator,
+				Operator1,
+				Operator2,
+				Operator3,
+				OperatorComponent,
+				OperatorComponentPre,
+				OperatorComponent1,
+			],
+			no_arena: [BuInner, ZoSumti, ZoiSumti],
+		}
+	};
+}
+pub(in crate::parse) use with_rule_types;
+
+macro_rules! define_visitor {
+	(
+		arena: [$($name:ident $(<$($param:ident),+>)?),* $(,)?],
+		no_arena: [$($no_arena_name:ident),* $(,)?],
+	) => {paste::paste! {
+		/// A typed traversal of the CST.
+		///
+		/// See the [module-level documentation](self) for an example.
+		pub trait Visitor<'ast> {
+			$(
+				#[doc = concat!("Visit a [`", stringify!($name), "`]. By default, this visits each of its children.")]
+				fn [<visit_ $name:snake>]$(<$($param: Visit<'ast>),+>)?(&mut self, node: &'ast $name<'_ $($(, $param)+)?>) {
+					node.walk(self);
+				}
+			)*
+
+			$(
+				#[doc = concat!("Visit a [`", stringify!($no_arena_name), "`]. By default, this visits each of its children.")]
+				fn [<visit_ $no_arena_name:snake>](&mut self, node: &'ast $no_arena_name) {
+					node.walk(self);
+				}
+			)*
+
+			/// Visit a token, such as the `KOhA` in `mi`, along with any `BAhE` before it and indicators after it.
+			/// By default, this visits the `BAhE` and indicators.
+			fn visit_token<T: Visit<'ast> + TreeNode>(&mut self, selmaho: Selmaho, node: &'ast T) {
+				let _ = selmaho;
+				node.walk(self);
+			}
+		}
+	}};
+}
+
+with_rule_types!(define_visitor);
+
+impl<'ast, T: Visit<'ast> + ?Sized> Visit<'ast> for &T {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		T::visit(self, visitor);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		T::walk(self, visitor);
+	}
+}
+
+impl<'ast, T: Visit<'ast> + ?Sized> Visit<'ast> for Box<T> {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		T::visit(self, visitor);
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		T::walk(self, visitor);
+	}
+}
+
+impl<'ast, T: Visit<'ast>> Visit<'ast> for Option<T> {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		if let Some(inner) = self {
+			inner.visit(visitor);
+		}
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		self.visit(visitor);
+	}
+}
+
+impl<'ast, T: Visit<'ast>> Visit<'ast> for [T] {
+	fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		for item in self {
+			item.visit(visitor);
+		}
+	}
+
+	fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+		self.visit(visitor);
+	}
+}
+
+macro_rules! tuple_impls {
+	// base case
+	() => {};
+	(@single $($idents:ident),*) => {
+		#[allow(non_snake_case)]
+		impl<'ast, $($idents: Visit<'ast>),*> Visit<'ast> for ($($idents,)*) {
+			fn visit<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+				let ($($idents,)*) = self;
+				$($idents.visit(visitor);)*
+			}
+
+			fn walk<V: Visitor<'ast> + ?Sized>(&'ast self, visitor: &mut V) {
+				self.visit(visitor);
+			}
+		}
+	};
+	($first:ident $(, $idents:ident)*) => {
+		tuple_impls!(@single $first $(, $idents)*);
+		tuple_impls!($($idents),*);
+	};
+}
+
+tuple_impls![T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15];
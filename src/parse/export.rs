@@ -0,0 +1,220 @@
+//! Export of CST nodes to other tree formats, for visualization and teaching.
+//!
+//! [`dot`] produces a [Graphviz](https://graphviz.org) graph, and [`sexp`] produces an S-expression.
+//! In both, nodes are labeled with their [name](TreeNode::name), leaves are labeled with their text as well, and experimental nodes are highlighted.
+//! Only the nodes that are experimental themselves are highlighted, rather than every node that contains them, so a node is highlighted if it is experimental and none of its children are.
+//!
+//! ```rust
+//! # use sneturfahi::parse::export;
+//! let input = "mi klama";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//! let options = export::Options { collapse: true };
+//! assert_eq!(
+//! 	export::sexp(cst.root(), cst.tokens(), input, options),
+//! 	r#"(Sentence (KOhA "mi") (gismu "klama"))"#
+//! );
+//! assert_eq!(
+//! 	export::dot(cst.root(), cst.tokens(), input, options),
+//! 	r#"digraph cst {
+//! 	node [shape=box];
+//! 	n0 [label="Sentence"];
+//! 	n1 [label="KOhA\n\"mi\""];
+//! 	n0 -> n1;
+//! 	n2 [label="gismu\n\"klama\""];
+//! 	n0 -> n2;
+//! }
+//! "#
+//! );
+//! ```
+
+use std::fmt::Write as _;
+
+use crate::lex::Token;
+use crate::parse::tree_node::TreeNode;
+
+/// Options for [`dot`] and [`sexp`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+	/// Skip over nodes that have exactly one child, exporting that child in their place.
+	///
+	/// The grammar produces long chains of wrapper nodes, so this makes the output much smaller, at the cost of hiding some structure.
+	pub collapse: bool,
+}
+
+fn children(node: &dyn TreeNode) -> Vec<&dyn TreeNode> {
+	let mut children = Vec::new();
+	node.for_each_child(&mut |child| children.push(child));
+	children
+}
+
+/// Get the node to export in place of `node`, along with its children.
+fn resolve(mut node: &dyn TreeNode, options: Options) -> (&dyn TreeNode, Vec<&dyn TreeNode>) {
+	let mut node_children = children(node);
+	if options.collapse {
+		while let [only_child] = node_children[..] {
+			node = only_child;
+			node_children = children(node);
+		}
+	}
+	(node, node_children)
+}
+
+/// Whether `node` is experimental itself, rather than because one of its `children` is.
+fn experimental(node: &dyn TreeNode, children: &[&dyn TreeNode]) -> bool {
+	node.experimental() && !children.iter().any(|child| child.experimental())
+}
+
+/// Escape `text` for a quoted string in the DOT language, where backslashes start escapes such as `\n` for a line break.
+fn dot_escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for letter in text.chars() {
+		match letter {
+			'"' => escaped.push_str(r#"\""#),
+			'\\' => escaped.push_str(r"\\"),
+			'\n' => escaped.push_str(r"\n"),
+			other => escaped.push(other),
+		}
+	}
+	escaped
+}
+
+/// The text of a leaf node, or `None` if the node has children or does not correspond to any text.
+fn leaf_text<'input>(
+	node: &dyn TreeNode,
+	children: &[&dyn TreeNode],
+	tokens: &[Token],
+	input: &'input str,
+) -> Option<&'input str> {
+	if !children.is_empty() {
+		return None;
+	}
+	node.span(tokens)?.slice(input)
+}
+
+/// Render `root`, which was parsed from `tokens` and `input`, as a Graphviz graph in the DOT language.
+///
+/// Nodes that are experimental themselves are filled in red.
+/// See the [module-level documentation](self) for an example.
+#[must_use]
+pub fn dot(root: &dyn TreeNode, tokens: &[Token], input: &str, options: Options) -> String {
+	fn inner(
+		node: &dyn TreeNode,
+		tokens: &[Token],
+		input: &str,
+		options: Options,
+		next_id: &mut usize,
+		out: &mut String,
+	) -> usize {
+		let (node, children) = resolve(node, options);
+		let id = *next_id;
+		*next_id += 1;
+
+		let mut label = dot_escape(node.name());
+		if let Some(text) = leaf_text(node, &children, tokens, input) {
+			write!(label, r#"\n\"{}\""#, dot_escape(text)).unwrap();
+		}
+		write!(out, "\tn{id} [label=\"{label}\"").unwrap();
+		if experimental(node, &children) {
+			out.push_str(", style=filled, fillcolor=\"#ffcccc\"");
+		}
+		out.push_str("];\n");
+
+		for child in children {
+			let child_id = inner(child, tokens, input, options, next_id, out);
+			writeln!(out, "\tn{id} -> n{child_id};").unwrap();
+		}
+		id
+	}
+
+	let mut out = String::from("digraph cst {\n\tnode [shape=box];\n");
+	inner(root, tokens, input, options, &mut 0, &mut out);
+	out.push_str("}\n");
+	out
+}
+
+/// Render `root`, which was parsed from `tokens` and `input`, as an S-expression.
+///
+/// Each node becomes a list of its name followed by its children, or by its text if it is a leaf.
+/// The names of nodes that are experimental themselves are marked with a trailing `*`.
+/// See the [module-level documentation](self) for an example.
+#[must_use]
+pub fn sexp(root: &dyn TreeNode, tokens: &[Token], input: &str, options: Options) -> String {
+	fn inner(node: &dyn TreeNode, tokens: &[Token], input: &str, options: Options, out: &mut String) {
+		let (node, children) = resolve(node, options);
+		write!(out, "({}", node.name()).unwrap();
+		if experimental(node, &children) {
+			out.push('*');
+		}
+		if let Some(text) = leaf_text(node, &children, tokens, input) {
+			write!(out, " {text:?}").unwrap();
+		}
+		for child in children {
+			out.push(' ');
+			inner(child, tokens, input, options, out);
+		}
+		out.push(')');
+	}
+
+	let mut out = String::new();
+	inner(root, tokens, input, options, &mut out);
+	out
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	macro_rules! make_test {
+		($name:ident, $input:literal, $export:ident, $expected:expr) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let arena = crate::Arena::new();
+				let cst = crate::parse_in(input, &arena).unwrap();
+				let options = Options { collapse: true };
+				assert_eq!($export(cst.root(), cst.tokens(), input, options), $expected);
+			}
+		};
+	}
+
+	make_test!(
+		sexp_experimental,
+		"mi klama bo'a",
+		sexp,
+		r#"(Sentence (KOhA "mi") (SentenceTail4::Single (gismu "klama") (KOhA* "bo'a")))"#
+	);
+	make_test!(
+		dot_experimental,
+		"bo'a klama",
+		dot,
+		r##"digraph cst {
+	node [shape=box];
+	n0 [label="Sentence"];
+	n1 [label="KOhA\n\"bo'a\"", style=filled, fillcolor="#ffcccc"];
+	n0 -> n1;
+	n2 [label="gismu\n\"klama\""];
+	n0 -> n2;
+}
+"##
+	);
+	make_test!(
+		dot_escape,
+		r#"zoi gy "a\b" gy"#,
+		dot,
+		r#"digraph cst {
+	node [shape=box];
+	n0 [label="ZoiSumti"];
+	n1 [label="ZOI\n\"zoi\""];
+	n0 -> n1;
+	n2 [label="ZoiDelimiter\n\"gy\""];
+	n0 -> n2;
+	n3 [label="ZoiText\n\"\"a\\b\"\""];
+	n0 -> n3;
+	n4 [label="ZoiDelimiter\n\"gy\""];
+	n0 -> n4;
+}
+"#
+	);
+}
@@ -0,0 +1,233 @@
+//! Finding nodes by their position in the input, for editor features such as hover and selection expansion.
+//!
+//! [`path_to_location`] finds the deepest node that contains a location, and [`path_to_span`] finds the smallest node that covers a span.
+//! Both return a [`NodePath`], the chain of nodes from the root down to the found node, since nodes have no links to their parents.
+//! The tree is only borrowed, so this works directly on a [`Cst`](crate::Cst) in its arena.
+//!
+//! ```rust
+//! # use sneturfahi::parse::locate;
+//! # use sneturfahi::Span;
+//! let input = "mi klama lo zdani";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//!
+//! let path = locate::path_to_location(cst.root(), cst.tokens(), 14).unwrap();
+//! assert_eq!(path.node().name(), "gismu");
+//! assert_eq!(path.span(), Span::new(12, 17));
+//! // expanding the selection goes through each ancestor in turn
+//! let gadri_sumti = path
+//! 	.entries()
+//! 	.iter()
+//! 	.rev()
+//! 	.find(|entry| entry.node.name() == "GadriSumti")
+//! 	.unwrap();
+//! assert_eq!(gadri_sumti.span, Span::new(9, 17));
+//!
+//! let path = locate::path_to_span(cst.root(), cst.tokens(), Span::new(10, 14)).unwrap();
+//! assert_eq!(path.node().name(), "GadriSumti");
+//! ```
+
+use crate::lex::Token;
+use crate::parse::tree_node::TreeNode;
+use crate::span::{Location, Span};
+
+/// A node on a [`NodePath`].
+#[derive(Clone, Copy)]
+pub struct PathEntry<'a> {
+	/// The node.
+	pub node: &'a dyn TreeNode,
+	/// The [label](TreeNode::for_each_labeled_child) of the node within its parent, or `None` if it is unlabeled or the root.
+	pub label: Option<&'static str>,
+	/// The index of the node among the children of its parent, or 0 for the root.
+	pub index: usize,
+	/// The span of the node, which is never empty because nodes are only found if they contain the location or span that was searched for.
+	pub span: Span,
+}
+
+impl std::fmt::Debug for PathEntry<'_> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		formatter
+			.debug_struct("PathEntry")
+			.field("node", &self.node.name())
+			.field("label", &self.label)
+			.field("index", &self.index)
+			.field("span", &self.span)
+			.finish()
+	}
+}
+
+/// The chain of nodes from the root of a tree down to a particular node, as returned by [`path_to_location`] and [`path_to_span`].
+///
+/// There is always at least one entry, the root.
+#[derive(Clone)]
+pub struct NodePath<'a> {
+	entries: Vec<PathEntry<'a>>,
+}
+
+impl std::fmt::Debug for NodePath<'_> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		formatter.debug_list().entries(&self.entries).finish()
+	}
+}
+
+fn children(node: &dyn TreeNode) -> Vec<&dyn TreeNode> {
+	let mut children = Vec::new();
+	node.for_each_child(&mut |child| children.push(child));
+	children
+}
+
+impl<'a> NodePath<'a> {
+	/// The nodes on the path, starting with the root and ending with the [found node](Self::node).
+	#[must_use]
+	pub fn entries(&self) -> &[PathEntry<'a>] {
+		&self.entries
+	}
+
+	/// The found node, which is the last node on the path.
+	#[must_use]
+	pub fn node(&self) -> &'a dyn TreeNode {
+		self.entries[self.entries.len() - 1].node
+	}
+
+	/// The label of the found node within its parent, or `None` if it is unlabeled or the root.
+	#[must_use]
+	pub fn label(&self) -> Option<&'static str> {
+		self.entries[self.entries.len() - 1].label
+	}
+
+	/// The span of the found node.
+	#[must_use]
+	pub fn span(&self) -> Span {
+		self.entries[self.entries.len() - 1].span
+	}
+
+	/// The parent of the found node, or `None` if it is the root.
+	#[must_use]
+	pub fn parent(&self) -> Option<&'a dyn TreeNode> {
+		let parent = self.entries.len().checked_sub(2)?;
+		Some(self.entries[parent].node)
+	}
+
+	/// The path to the parent of the found node, or `None` if it is the root.
+	#[must_use]
+	pub fn parent_path(&self) -> Option<Self> {
+		let len = self.entries.len().checked_sub(1).filter(|&len| len > 0)?;
+		Some(Self {
+			entries: self.entries[..len].to_vec(),
+		})
+	}
+
+	/// The other children of the parent of the found node, in order, or nothing if it is the root.
+	///
+	/// ```rust
+	/// # use sneturfahi::parse::locate::{self, NodePath};
+	/// let input = "lo mlatu ku cu klama";
+	/// let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+	/// let arena = sneturfahi::Arena::new();
+	/// let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+	///
+	/// let path = locate::path_to_location(cst.root(), cst.tokens(), 4).unwrap();
+	/// let contents = std::iter::successors(Some(path), NodePath::parent_path)
+	/// 	.find(|path| path.label() == Some("contents"))
+	/// 	.unwrap();
+	/// let siblings: Vec<_> = contents
+	/// 	.siblings()
+	/// 	.iter()
+	/// 	.map(|sibling| sibling.name())
+	/// 	.collect();
+	/// assert_eq!(siblings, ["Gadri::Le", "Ku"]);
+	/// assert_eq!(contents.previous_sibling().unwrap().name(), "Gadri::Le");
+	/// assert_eq!(contents.next_sibling().unwrap().name(), "Ku");
+	/// ```
+	#[must_use]
+	pub fn siblings(&self) -> Vec<&'a dyn TreeNode> {
+		let Some(parent) = self.parent() else {
+			return Vec::new();
+		};
+		let index = self.entries[self.entries.len() - 1].index;
+		let mut siblings = children(parent);
+		siblings.remove(index);
+		siblings
+	}
+
+	/// The child of the parent of the found node that comes before it, if any.
+	#[must_use]
+	pub fn previous_sibling(&self) -> Option<&'a dyn TreeNode> {
+		let index = self.entries[self.entries.len() - 1].index.checked_sub(1)?;
+		children(self.parent()?).get(index).copied()
+	}
+
+	/// The child of the parent of the found node that comes after it, if any.
+	#[must_use]
+	pub fn next_sibling(&self) -> Option<&'a dyn TreeNode> {
+		let index = self.entries[self.entries.len() - 1].index + 1;
+		children(self.parent()?).get(index).copied()
+	}
+
+	/// Descend from `root` through the first child whose span satisfies `predicate` for as long as possible.
+	fn descend(
+		root: &'a dyn TreeNode,
+		tokens: &[Token],
+		predicate: impl Fn(Span) -> bool,
+	) -> Option<Self> {
+		let span = root.span(tokens).filter(|&span| predicate(span))?;
+		let mut entries = vec![PathEntry {
+			node: root,
+			label: None,
+			index: 0,
+			span,
+		}];
+		loop {
+			let node = entries[entries.len() - 1].node;
+			let mut next = None;
+			let mut index = 0;
+			node.for_each_labeled_child(&mut |label, child| {
+				if next.is_none() {
+					if let Some(span) = child.span(tokens).filter(|&span| predicate(span)) {
+						next = Some(PathEntry {
+							node: child,
+							label,
+							index,
+							span,
+						});
+					}
+				}
+				index += 1;
+			});
+			match next {
+				Some(next) => entries.push(next),
+				None => break,
+			}
+		}
+		Some(Self { entries })
+	}
+}
+
+/// Find the deepest node under `root`, which was parsed from `tokens`, that contains `location`, which is usually a token.
+///
+/// A node contains the locations from its start up to but not including its end.
+/// Returns `None` if `root` does not contain `location`.
+/// See the [module-level documentation](self) for an example.
+#[must_use]
+pub fn path_to_location<'a>(
+	root: &'a dyn TreeNode,
+	tokens: &[Token],
+	location: Location,
+) -> Option<NodePath<'a>> {
+	NodePath::descend(root, tokens, |span| span.contains(location))
+}
+
+/// Find the smallest node under `root`, which was parsed from `tokens`, that covers all of `span`.
+///
+/// When a chain of nodes have the same span, this is the deepest one.
+/// Returns `None` if `root` does not cover `span`.
+/// See the [module-level documentation](self) for an example.
+#[must_use]
+pub fn path_to_span<'a>(
+	root: &'a dyn TreeNode,
+	tokens: &[Token],
+	span: Span,
+) -> Option<NodePath<'a>> {
+	NodePath::descend(root, tokens, |node_span| node_span.contains_span(span))
+}
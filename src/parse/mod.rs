@@ -1,6 +1,107 @@
 pub mod arena;
+pub mod ast;
+pub mod camxes;
 pub mod cst;
+pub mod export;
+pub mod locate;
+pub mod owned;
+pub mod select;
+#[cfg(feature = "serde")]
+pub mod serialize;
 pub mod tree_node;
 
-pub use arena::Arena;
+pub use arena::{Arena, RuleStats};
 pub use cst::Cst;
+pub use owned::ParsedText;
+
+use crate::lex::Token;
+
+/// Options for [`parse_with`] and [`parse_in_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+	/// Remove the words erased by `si`, `sa` and `su` with [`erase`](crate::erase()) before parsing.
+	///
+	/// Without this, those cmavo fail to parse.
+	pub erase: bool,
+}
+
+/// Lex and parse `input` as an entire text, into a [`ParsedText`] that owns the input, tokens and tree.
+///
+/// This is the simplest way to parse a text.
+/// Use [`parse_in`] instead to get a [`Cst`] with the concrete node types.
+///
+/// Erasure with `si`, `sa` and `su` is not performed, so those cmavo fail to parse.
+/// Use [`parse_with`] to perform it.
+///
+/// ```rust
+/// use sneturfahi::parse::tree_node::TreeNode as _;
+///
+/// let parsed = sneturfahi::parse("mi klama lo zdani").unwrap();
+/// assert_eq!(parsed.tokens().len(), 4);
+/// assert_eq!(parsed.root().name(), "Text");
+/// ```
+#[allow(clippy::missing_errors_doc)] // obvious
+pub fn parse(input: &str) -> Result<ParsedText, crate::Error> {
+	ParsedText::parse(input)
+}
+
+/// Like [`parse`], but with [`Options`].
+///
+/// ```rust
+/// use sneturfahi::parse::Options;
+///
+/// let options = Options { erase: true };
+/// let parsed = sneturfahi::parse_with("mi klama si cadzu", options).unwrap();
+/// assert_eq!(parsed.tokens().len(), 2);
+/// assert!(sneturfahi::parse("mi klama si cadzu").is_err());
+/// ```
+#[allow(clippy::missing_errors_doc)] // obvious
+pub fn parse_with(input: &str, options: Options) -> Result<ParsedText, crate::Error> {
+	ParsedText::parse_with(input, options)
+}
+
+/// Lex and parse `input` as an entire text, allocating both the tokens and the [`Cst`] in `arena`.
+///
+/// ```rust
+/// use sneturfahi::parse::cst::node::NodeRef;
+/// use sneturfahi::parse::tree_node::TreeNode as _;
+///
+/// let arena = sneturfahi::Arena::new();
+/// let cst = sneturfahi::parse_in("lo mlatu ku", &arena).unwrap();
+/// assert!(matches!(cst.root().downcast(), NodeRef::Text(_)));
+/// ```
+#[allow(clippy::missing_errors_doc)] // obvious
+pub fn parse_in<'arena>(input: &str, arena: &'arena Arena) -> Result<Cst<'arena>, crate::Error> {
+	parse_in_with(input, arena, Options::default())
+}
+
+/// Like [`parse_in`], but with [`Options`].
+///
+/// ```rust
+/// use sneturfahi::parse::Options;
+///
+/// let arena = sneturfahi::Arena::new();
+/// let options = Options { erase: true };
+/// let cst = sneturfahi::parse_in_with("mi klama su do cadzu", &arena, options).unwrap();
+/// assert_eq!(cst.tokens().len(), 2);
+/// ```
+#[allow(clippy::missing_errors_doc)] // obvious
+pub fn parse_in_with<'arena>(
+	input: &str,
+	arena: &'arena Arena,
+	options: Options,
+) -> Result<Cst<'arena>, crate::Error> {
+	let tokens = lex(input, options)?;
+	let tokens = arena.alloc_slice_copy(&tokens);
+	Cst::parse_memoized(tokens, arena).map_err(|error| crate::Error::from_parse(error, input))
+}
+
+/// Lex `input`, erasing words if `options` asks to.
+fn lex(input: &str, options: Options) -> Result<Vec<Token>, crate::Error> {
+	let tokens = crate::lex(input).collect::<Result<Vec<_>, _>>()?;
+	Ok(if options.erase {
+		crate::erase(&tokens)
+	} else {
+		tokens
+	})
+}
@@ -0,0 +1,283 @@
+//! Trees that own all of their data, for keeping parses around without keeping their [`Arena`] and tokens borrowed.
+//!
+//! A [`Cst`] borrows both its arena and the tokens it was parsed from, which makes it awkward to store or send to another thread.
+//! [`OwnedNode`] is a copy of a tree that does not borrow anything, and [`ParsedText`] bundles one with the input and tokens that it refers to.
+//! Both are `Send`, `Sync` and `'static`.
+//!
+//! ```rust
+//! use sneturfahi::parse::tree_node::TreeNode as _;
+//! use sneturfahi::ParsedText;
+//!
+//! let parsed = ParsedText::parse("mi klama lo zdani").unwrap();
+//! let handle = std::thread::spawn(move || {
+//! 	let root = parsed.root();
+//! 	let text = root
+//! 		.span(parsed.tokens())
+//! 		.unwrap()
+//! 		.slice(parsed.input())
+//! 		.unwrap();
+//! 	(root.name(), text.to_owned())
+//! });
+//! assert_eq!(
+//! 	handle.join().unwrap(),
+//! 	("Text", "mi klama lo zdani".to_owned())
+//! );
+//! ```
+//!
+//! [`OwnedNode`] implements [`TreeNode`], so everything that works on a [`Cst`] through [`TreeNode`], such as [`export`](crate::parse::export) and [`select`](crate::parse::select), also works on it.
+//! It keeps the [`NodeKind`] of each node, but not the concrete node types, so [`TreeNode::downcast`] returns [`NodeRef::Owned`].
+
+use crate::lex::{Selmaho, Token};
+use crate::parse::cst::node::{NodeKind, NodeRef};
+use crate::parse::tree_node::{Child, TreeNode};
+use crate::parse::{Arena, Cst, Options};
+use crate::Error;
+
+/// A node of a tree that owns its children, copied from any [`TreeNode`] with [`OwnedNode::new`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedNode {
+	/// The [name](TreeNode::name) of the original node.
+	pub name: &'static str,
+	/// The [kind](TreeNode::kind) of the original node.
+	pub kind: NodeKind,
+	/// Whether the original node contains any experimental cmavo.
+	pub experimental: bool,
+	/// The indices of the tokens of the original node, or `None` if it is empty.
+	pub tokens: Option<std::ops::Range<u32>>,
+	/// The children of the original node, including elided terminators, in order.
+	pub children: Vec<OwnedChild>,
+}
+
+/// A child of an [`OwnedNode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnedChild {
+	/// A node that is present in the input, with its [label](TreeNode::for_each_labeled_child).
+	Node {
+		/// The label of the node within its parent.
+		label: Option<&'static str>,
+		/// The node itself.
+		node: OwnedNode,
+	},
+	/// A terminator that was elided from the input.
+	Elided(Selmaho),
+}
+
+impl OwnedNode {
+	/// Copy `node` and all of its descendants into an owned tree.
+	#[must_use]
+	pub fn new(node: &dyn TreeNode) -> Self {
+		let mut labels = Vec::new();
+		node.for_each_labeled_child(&mut |label, _| labels.push(label));
+		let mut labels = labels.into_iter();
+
+		let mut children = Vec::new();
+		node.for_each_child_or_elided(&mut |child| {
+			children.push(match child {
+				Child::Node(child) => OwnedChild::Node {
+					label: labels.next().flatten(),
+					node: Self::new(child),
+				},
+				Child::Elided(selmaho) => OwnedChild::Elided(selmaho),
+			});
+		});
+
+		Self {
+			name: node.name(),
+			kind: node.kind(),
+			experimental: node.experimental(),
+			tokens: node
+				.start_token()
+				.zip(node.end_token())
+				.map(|(start, end)| start..end),
+			children,
+		}
+	}
+
+	/// Iterate over the children of this node that are present in the input, with their labels.
+	pub fn child_nodes(&self) -> impl Iterator<Item = (Option<&'static str>, &Self)> + '_ {
+		self.children.iter().filter_map(|child| match child {
+			OwnedChild::Node { label, node } => Some((*label, node)),
+			OwnedChild::Elided(_) => None,
+		})
+	}
+}
+
+impl TreeNode for OwnedNode {
+	fn name(&self) -> &'static str {
+		self.name
+	}
+
+	fn experimental(&self) -> bool {
+		self.experimental
+	}
+
+	fn start_token(&self) -> Option<u32> {
+		self.tokens.as_ref().map(|tokens| tokens.start)
+	}
+
+	fn end_token(&self) -> Option<u32> {
+		self.tokens.as_ref().map(|tokens| tokens.end)
+	}
+
+	fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode)) {
+		for (_, node) in self.child_nodes() {
+			f(node);
+		}
+	}
+
+	fn for_each_child_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		for child in &self.children {
+			f(match child {
+				OwnedChild::Node { node, .. } => Child::Node(node),
+				OwnedChild::Elided(selmaho) => Child::Elided(*selmaho),
+			});
+		}
+	}
+
+	fn for_each_labeled_child<'a>(
+		&'a self,
+		f: &mut dyn FnMut(Option<&'static str>, &'a dyn TreeNode),
+	) {
+		for (label, node) in self.child_nodes() {
+			f(label, node);
+		}
+	}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		NodeRef::Owned(self)
+	}
+
+	fn kind(&self) -> NodeKind {
+		self.kind
+	}
+}
+
+impl<R: TreeNode> From<&Cst<'_, R>> for OwnedNode {
+	fn from(cst: &Cst<'_, R>) -> Self {
+		Self::new(cst.root())
+	}
+}
+
+/// A parsed text that owns its input, its tokens and its tree.
+///
+/// See the [module-level documentation](self) for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedText {
+	input: String,
+	tokens: Vec<Token>,
+	root: OwnedNode,
+}
+
+impl ParsedText {
+	/// Lex and parse `input` as an entire text.
+	///
+	/// The arena used for parsing is dropped before this returns, so only the owned tree is kept.
+	#[allow(clippy::missing_errors_doc)] // obvious
+	pub fn parse(input: impl Into<String>) -> Result<Self, Error> {
+		Self::parse_with(input, Options::default())
+	}
+
+	/// Like [`parse`](Self::parse), but with [`Options`].
+	///
+	/// The tokens of a text with erasure are only those left after it.
+	#[allow(clippy::missing_errors_doc)] // obvious
+	pub fn parse_with(input: impl Into<String>, options: Options) -> Result<Self, Error> {
+		let input = input.into();
+		let tokens = super::lex(&input, options)?;
+		let arena = Arena::new();
+		let root = match Cst::parse_memoized(&tokens, &arena) {
+			Ok(cst) => OwnedNode::from(&cst),
+			Err(error) => return Err(Error::from_parse(error, &input)),
+		};
+		Ok(Self {
+			input,
+			tokens,
+			root,
+		})
+	}
+
+	/// The text that was parsed.
+	#[must_use]
+	pub fn input(&self) -> &str {
+		&self.input
+	}
+
+	/// The tokens of the text.
+	#[must_use]
+	pub fn tokens(&self) -> &[Token] {
+		&self.tokens
+	}
+
+	/// The root of the tree, whose token indices index into [`tokens`](Self::tokens).
+	#[must_use]
+	pub fn root(&self) -> &OwnedNode {
+		&self.root
+	}
+
+	/// Take the input, tokens and tree apart.
+	#[must_use]
+	pub fn into_parts(self) -> (String, Vec<Token>, OwnedNode) {
+		(self.input, self.tokens, self.root)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::parse::{camxes, export};
+
+	macro_rules! make_test {
+		($name:ident, $input:literal) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let tokens: Vec<_> = crate::lex(input).collect::<Result<_, _>>().unwrap();
+				let arena = Arena::new();
+				let cst = Cst::parse(&tokens, &arena).unwrap();
+				let owned = OwnedNode::from(&cst);
+				for collapse in [false, true] {
+					let options = export::Options { collapse };
+					assert_eq!(
+						export::sexp(&owned, &tokens, input, options),
+						export::sexp(cst.root(), &tokens, input, options),
+					);
+				}
+				for terminators in [false, true] {
+					let options = camxes::Options {
+						terminators,
+						selmaho: true,
+					};
+					assert_eq!(
+						camxes::brackets(&owned, &tokens, input, options),
+						camxes::brackets(cst.root(), &tokens, input, options),
+					);
+				}
+			}
+		};
+	}
+
+	make_test!(simple, "mi klama lo zdani");
+	make_test!(elided, "lo mlatu cu klama");
+	make_test!(quote, "zoi gy. abc .gy cu xamgu");
+	make_test!(relative_clause, "ko'a noi barda vau ku'o cu klama");
+
+	#[test]
+	fn send_sync() {
+		fn assert_send_sync<T: Send + Sync + 'static>() {}
+		assert_send_sync::<ParsedText>();
+		assert_send_sync::<OwnedNode>();
+		assert_send_sync::<Error>();
+	}
+
+	#[test]
+	fn errors() {
+		assert!(matches!(ParsedText::parse("zoi"), Err(Error::Lex(_))));
+		assert!(matches!(
+			ParsedText::parse("mi ku'o"),
+			Err(Error::Parse {
+				selmaho: Some(_),
+				..
+			})
+		));
+	}
+}
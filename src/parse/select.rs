@@ -0,0 +1,716 @@
+//! Searching the CST with selectors, in the style of CSS.
+//!
+//! A selector is made of compound selectors separated by combinators.
+//! A compound selector matches a node by its [name](TreeNode::name), followed by any number of predicates:
+//!
+//! - `GadriSumti` matches nodes with that name. Nodes of enum types are named like `TagWord::Bai`, and are matched by both `TagWord::Bai` and `TagWord`.
+//! - `*` matches any node, and may be omitted before a predicate.
+//! - `[label=ku]` matches nodes with that [label](TreeNode::for_each_labeled_child) in their parent.
+//! - `[text="lo mlatu"]` matches nodes whose text is exactly `lo mlatu`. `[text^=...]`, `[text$=...]`, and `[text*=...]` match text that starts with, ends with, or contains the value.
+//! - `[experimental]` matches nodes that contain experimental cmavo.
+//! - `:has(...)` matches nodes that have a descendant matching the selector in parentheses, or a child if that selector starts with `>`.
+//!
+//! The combinators are whitespace, which matches descendants, and `>`, which matches children.
+//! Several selectors can be separated by commas to match any of them.
+//!
+//! ```rust
+//! # use sneturfahi::parse::select::select;
+//! let input = "lo gerku poi lo mlatu cu citka ke'a cu klama lo zdani";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//!
+//! let texts = |selector| {
+//! 	select(cst.root(), cst.tokens(), input, selector)
+//! 		.unwrap()
+//! 		.iter()
+//! 		.map(|found| found.span.unwrap().slice(input).unwrap())
+//! 		.collect::<Vec<_>>()
+//! };
+//! assert_eq!(texts("NoiRelativeClause GadriSumti"), ["lo mlatu"]);
+//! assert_eq!(
+//! 	texts("GadriSumti > [label=contents]"),
+//! 	["gerku poi lo mlatu cu citka ke'a", "mlatu", "zdani"]
+//! );
+//! assert_eq!(
+//! 	texts("GadriSumti:has(> [label=contents][text=zdani])"),
+//! 	["lo zdani"]
+//! );
+//! ```
+//!
+//! To run the same selector many times, parse it into a [`Selector`] once.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::lex::Token;
+use crate::parse::tree_node::TreeNode;
+use crate::span::Span;
+
+/// A node that matched a selector, as returned by [`select`] and [`Selector::select`].
+#[derive(Clone, Copy)]
+pub struct Match<'a> {
+	/// The node that matched.
+	pub node: &'a dyn TreeNode,
+	/// The label of the node within its parent, or `None` if it is unlabeled or the root.
+	pub label: Option<&'static str>,
+	/// The span of the node, or `None` if it is empty.
+	pub span: Option<Span>,
+}
+
+impl std::fmt::Debug for Match<'_> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		formatter
+			.debug_struct("Match")
+			.field("node", &self.node.name())
+			.field("label", &self.label)
+			.field("span", &self.span)
+			.finish()
+	}
+}
+
+/// An error in the syntax of a selector.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{kind} at byte {position} of the selector")]
+pub struct Error {
+	/// The byte offset in the selector where the error occurred.
+	pub position: usize,
+	/// What went wrong.
+	pub kind: ErrorKind,
+}
+
+/// The ways in which a selector can be invalid.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// The selector ended where more was expected, such as after a combinator.
+	#[error("unexpected end of selector")]
+	UnexpectedEnd,
+	/// A character appeared where it is not allowed.
+	#[error("unexpected character {0:?}")]
+	UnexpectedChar(char),
+	/// An attribute predicate used an attribute other than `label`, `text`, and `experimental`, or an operator that the attribute does not support.
+	#[error("unknown attribute {0:?}")]
+	UnknownAttribute(String),
+	/// A pseudo-class other than `:has` was used.
+	#[error("unknown pseudo-class {0:?}")]
+	UnknownPseudoClass(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+	Descendant,
+	Child,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextOperator {
+	Equals,
+	StartsWith,
+	EndsWith,
+	Contains,
+}
+
+#[derive(Debug, Clone)]
+enum Predicate {
+	Label(String),
+	Text(TextOperator, String),
+	Experimental,
+	/// `:has`, with the combinator between the node that it is relative to and the selector in parentheses.
+	Has(Combinator, Complex),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Compound {
+	/// The name to match, or `None` for any.
+	name: Option<String>,
+	predicates: Vec<Predicate>,
+}
+
+/// Compound selectors separated by combinators. `combinators[i]` is between `compounds[i]` and `compounds[i + 1]`.
+#[derive(Debug, Clone)]
+struct Complex {
+	compounds: Vec<Compound>,
+	combinators: Vec<Combinator>,
+}
+
+/// A parsed selector.
+///
+/// See the [module-level documentation](self) for the syntax.
+#[derive(Debug, Clone)]
+pub struct Selector {
+	alternatives: Vec<Complex>,
+}
+
+struct Parser<'a> {
+	raw: &'a str,
+	position: usize,
+}
+
+impl Parser<'_> {
+	fn peek(&self) -> Option<char> {
+		self.raw[self.position..].chars().next()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let ch = self.peek()?;
+		self.position += ch.len_utf8();
+		Some(ch)
+	}
+
+	fn error(&self, kind: ErrorKind) -> Error {
+		Error {
+			position: self.position,
+			kind,
+		}
+	}
+
+	fn unexpected(&self) -> Error {
+		self.error(
+			self
+				.peek()
+				.map_or(ErrorKind::UnexpectedEnd, ErrorKind::UnexpectedChar),
+		)
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), Error> {
+		if self.peek() == Some(expected) {
+			self.bump();
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	/// Skip whitespace, returning whether there was any.
+	fn skip_whitespace(&mut self) -> bool {
+		let start = self.position;
+		while self.peek().is_some_and(char::is_whitespace) {
+			self.bump();
+		}
+		self.position > start
+	}
+
+	fn identifier(&mut self) -> Result<&str, Error> {
+		let start = self.position;
+		loop {
+			match self.peek() {
+				Some(ch) if ch.is_alphanumeric() || ch == '_' || ch == '\'' => {
+					self.bump();
+				}
+				Some(':') if self.raw[self.position..].starts_with("::") => {
+					self.position += 2;
+				}
+				_ => break,
+			}
+		}
+		if self.position == start {
+			Err(self.unexpected())
+		} else {
+			Ok(&self.raw[start..self.position])
+		}
+	}
+
+	/// A quoted string or an identifier.
+	fn value(&mut self) -> Result<String, Error> {
+		match self.peek() {
+			Some(quote @ ('"' | '\'')) => {
+				self.bump();
+				let start = self.position;
+				loop {
+					match self.bump() {
+						Some(ch) if ch == quote => break,
+						Some(_) => {}
+						None => return Err(self.error(ErrorKind::UnexpectedEnd)),
+					}
+				}
+				Ok(self.raw[start..self.position - 1].to_owned())
+			}
+			_ => self.identifier().map(str::to_owned),
+		}
+	}
+
+	fn attribute(&mut self) -> Result<Predicate, Error> {
+		self.expect('[')?;
+		self.skip_whitespace();
+		let start = self.position;
+		let attribute = self.identifier()?.to_owned();
+		self.skip_whitespace();
+		let operator = match self.peek() {
+			Some(']') => None,
+			Some('=') => Some(TextOperator::Equals),
+			Some('^') => Some(TextOperator::StartsWith),
+			Some('$') => Some(TextOperator::EndsWith),
+			Some('*') => Some(TextOperator::Contains),
+			_ => return Err(self.unexpected()),
+		};
+		if let Some(operator) = operator {
+			if operator != TextOperator::Equals {
+				self.bump();
+			}
+			self.expect('=')?;
+			self.skip_whitespace();
+		}
+		let predicate = match (attribute.as_str(), operator) {
+			("experimental", None) => Predicate::Experimental,
+			("label", Some(TextOperator::Equals)) => Predicate::Label(self.value()?),
+			("text", Some(operator)) => Predicate::Text(operator, self.value()?),
+			_ => {
+				return Err(Error {
+					position: start,
+					kind: ErrorKind::UnknownAttribute(attribute),
+				})
+			}
+		};
+		self.skip_whitespace();
+		self.expect(']')?;
+		Ok(predicate)
+	}
+
+	fn pseudo_class(&mut self) -> Result<Predicate, Error> {
+		self.expect(':')?;
+		let start = self.position;
+		let name = self.identifier()?;
+		if name != "has" {
+			return Err(Error {
+				position: start,
+				kind: ErrorKind::UnknownPseudoClass(name.to_owned()),
+			});
+		}
+		self.expect('(')?;
+		self.skip_whitespace();
+		let combinator = if self.peek() == Some('>') {
+			self.bump();
+			Combinator::Child
+		} else {
+			Combinator::Descendant
+		};
+		let relative = self.complex()?;
+		self.expect(')')?;
+		Ok(Predicate::Has(combinator, relative))
+	}
+
+	fn compound(&mut self) -> Result<Compound, Error> {
+		let mut compound = Compound::default();
+		match self.peek() {
+			Some('*') => {
+				self.bump();
+			}
+			Some('[' | ':') => {}
+			_ => compound.name = Some(self.identifier()?.to_owned()),
+		}
+		loop {
+			match self.peek() {
+				Some('[') => compound.predicates.push(self.attribute()?),
+				Some(':') => compound.predicates.push(self.pseudo_class()?),
+				_ => break,
+			}
+		}
+		Ok(compound)
+	}
+
+	fn complex(&mut self) -> Result<Complex, Error> {
+		self.skip_whitespace();
+		let mut complex = Complex {
+			compounds: vec![self.compound()?],
+			combinators: Vec::new(),
+		};
+		loop {
+			let whitespace = self.skip_whitespace();
+			let combinator = match self.peek() {
+				Some('>') => {
+					self.bump();
+					self.skip_whitespace();
+					Combinator::Child
+				}
+				Some(',' | ')') | None => break,
+				Some(_) if whitespace => Combinator::Descendant,
+				Some(_) => return Err(self.unexpected()),
+			};
+			complex.combinators.push(combinator);
+			complex.compounds.push(self.compound()?);
+		}
+		Ok(complex)
+	}
+
+	fn selector(&mut self) -> Result<Selector, Error> {
+		let mut alternatives = vec![self.complex()?];
+		while self.peek() == Some(',') {
+			self.bump();
+			alternatives.push(self.complex()?);
+		}
+		if self.peek().is_some() {
+			return Err(self.unexpected());
+		}
+		Ok(Selector { alternatives })
+	}
+}
+
+impl FromStr for Selector {
+	type Err = Error;
+
+	fn from_str(raw: &str) -> Result<Self, Error> {
+		Parser { raw, position: 0 }.selector()
+	}
+}
+
+/// A node together with its label, as an element of the path from the root to the node being matched.
+#[derive(Clone, Copy)]
+struct Entry<'a> {
+	node: &'a dyn TreeNode,
+	label: Option<&'static str>,
+	/// The position of the node in a pre-order walk from the root.
+	index: usize,
+}
+
+/// What matching needs besides the path to the node being matched.
+struct Context<'a, 's> {
+	/// The root of the tree being searched, from which `:has` predicates are evaluated.
+	root: Entry<'a>,
+	/// The tokens that the tree was parsed from, which are needed for `text` predicates.
+	tokens: &'s [Token],
+	/// The input that the tree was parsed from, which is needed for `text` predicates.
+	input: &'s str,
+	/// For each `:has` predicate that has been evaluated, whether each node matches it, by [`Entry::index`].
+	has: RefCell<HashMap<*const Complex, Vec<bool>>>,
+}
+
+impl<'a, 's> Context<'a, 's> {
+	fn new(root: &'a dyn TreeNode, tokens: &'s [Token], input: &'s str) -> Self {
+		Self {
+			root: Entry {
+				node: root,
+				label: None,
+				index: 0,
+			},
+			tokens,
+			input,
+			has: RefCell::default(),
+		}
+	}
+
+	fn text(&self, node: &dyn TreeNode) -> Option<&'s str> {
+		node.span(self.tokens)?.slice(self.input)
+	}
+
+	/// Whether `entry` has nodes below it that match `relative` after `combinator`.
+	///
+	/// The first time a predicate is evaluated, it is evaluated for every node at once, by matching `relative` against each node and marking the ancestors that it is relative to.
+	fn has(&self, entry: Entry<'a>, combinator: Combinator, relative: &Complex) -> bool {
+		let key: *const Complex = relative;
+		if let Some(found) = self.has.borrow().get(&key) {
+			return found.get(entry.index).copied().unwrap_or(false);
+		}
+		let mut found = Vec::new();
+		walk(&mut vec![self.root], &mut 1, &mut |path| {
+			let mut matching = Matching::new(relative, path, self);
+			for (position, ancestor) in path[..path.len() - 1].iter().enumerate() {
+				if matching.below(combinator, position, 0) {
+					let index = ancestor.index;
+					if found.len() <= index {
+						found.resize(index + 1, false);
+					}
+					found[index] = true;
+				}
+			}
+		});
+		let result = found.get(entry.index).copied().unwrap_or(false);
+		self.has.borrow_mut().insert(key, found);
+		result
+	}
+}
+
+fn matches_name(pattern: &str, name: &str) -> bool {
+	name == pattern
+		|| name
+			.strip_prefix(pattern)
+			.is_some_and(|rest| rest.starts_with("::"))
+}
+
+impl Compound {
+	fn matches<'a>(&self, entry: Entry<'a>, context: &Context<'a, '_>) -> bool {
+		if let Some(name) = &self.name {
+			if !matches_name(name, entry.node.name()) {
+				return false;
+			}
+		}
+		self.predicates.iter().all(|predicate| match predicate {
+			Predicate::Label(label) => entry.label == Some(label.as_str()),
+			Predicate::Text(operator, value) => {
+				let Some(text) = context.text(entry.node) else {
+					return false;
+				};
+				match operator {
+					TextOperator::Equals => text == value,
+					TextOperator::StartsWith => text.starts_with(value.as_str()),
+					TextOperator::EndsWith => text.ends_with(value.as_str()),
+					TextOperator::Contains => text.contains(value.as_str()),
+				}
+			}
+			Predicate::Experimental => entry.node.experimental(),
+			Predicate::Has(combinator, relative) => context.has(entry, *combinator, relative),
+		})
+	}
+}
+
+/// Matching a complex selector against one path, from the right, so that the compounds after each one are matched first.
+///
+/// The results are memoized by compound and position, so descendant combinators take time linear in the length of the path instead of backtracking.
+struct Matching<'m, 'a, 's> {
+	complex: &'m Complex,
+	path: &'m [Entry<'a>],
+	context: &'m Context<'a, 's>,
+	/// Whether `complex.compounds[compound..]` match from `path[position]` to the end of the path, at `compound * path.len() + position`.
+	from: Vec<Option<bool>>,
+	/// Whether that is true at `position` or any position after it, indexed likewise.
+	from_or_after: Vec<Option<bool>>,
+}
+
+impl<'m, 'a, 's> Matching<'m, 'a, 's> {
+	fn new(complex: &'m Complex, path: &'m [Entry<'a>], context: &'m Context<'a, 's>) -> Self {
+		let cells = complex.compounds.len() * path.len();
+		Self {
+			complex,
+			path,
+			context,
+			from: vec![None; cells],
+			from_or_after: vec![None; cells],
+		}
+	}
+
+	/// Whether the last node of the path matches.
+	fn matches(&mut self) -> bool {
+		let last = self.complex.compounds.len() - 1;
+		self.matches_from(last, self.path.len() - 1) && self.matches_from_or_after(0, 0)
+	}
+
+	/// Whether `complex.compounds[compound..]` match from `path[position]` to the end of the path.
+	fn matches_from(&mut self, compound: usize, position: usize) -> bool {
+		let cell = compound * self.path.len() + position;
+		if let Some(result) = self.from[cell] {
+			return result;
+		}
+		let matches =
+			|this: &Self| this.complex.compounds[compound].matches(this.path[position], this.context);
+		let result = if compound + 1 == self.complex.compounds.len() {
+			position + 1 == self.path.len() && matches(self)
+		} else {
+			matches(self) && self.below(self.complex.combinators[compound], position, compound + 1)
+		};
+		self.from[cell] = Some(result);
+		result
+	}
+
+	/// Whether [`matches_from`](Self::matches_from) is true for `compound` at `position` or any position after it.
+	fn matches_from_or_after(&mut self, compound: usize, position: usize) -> bool {
+		if position >= self.path.len() {
+			return false;
+		}
+		let cell = compound * self.path.len() + position;
+		if let Some(result) = self.from_or_after[cell] {
+			return result;
+		}
+		let result =
+			self.matches_from(compound, position) || self.matches_from_or_after(compound, position + 1);
+		self.from_or_after[cell] = Some(result);
+		result
+	}
+
+	/// Whether `complex.compounds[compound..]` match below `path[position]` as `combinator` requires.
+	fn below(&mut self, combinator: Combinator, position: usize, compound: usize) -> bool {
+		match combinator {
+			Combinator::Child => {
+				position + 1 < self.path.len() && self.matches_from(compound, position + 1)
+			}
+			Combinator::Descendant => self.matches_from_or_after(compound, position + 1),
+		}
+	}
+}
+
+/// Call `f` with the path to each node below the last node of `path`, in pre-order, numbering the nodes from `next_index`.
+fn walk<'a>(path: &mut Vec<Entry<'a>>, next_index: &mut usize, f: &mut dyn FnMut(&[Entry<'a>])) {
+	let node = path.last().unwrap().node;
+	node.for_each_labeled_child(&mut |label, child| {
+		path.push(Entry {
+			node: child,
+			label,
+			index: *next_index,
+		});
+		*next_index += 1;
+		f(path);
+		walk(path, next_index, f);
+		path.pop();
+	});
+}
+
+impl Selector {
+	/// Find the nodes under `root`, including `root` itself, that match this selector, in the order they appear in the tree.
+	///
+	/// `tokens` and `input` are what `root` was parsed from, which are needed for spans and `text` predicates.
+	#[must_use]
+	pub fn select<'a>(
+		&self,
+		root: &'a dyn TreeNode,
+		tokens: &[Token],
+		input: &str,
+	) -> Vec<Match<'a>> {
+		let context = Context::new(root, tokens, input);
+		let mut found = Vec::new();
+		let mut check = |path: &[Entry<'a>]| {
+			if self
+				.alternatives
+				.iter()
+				.any(|alternative| Matching::new(alternative, path, &context).matches())
+			{
+				let entry = path[path.len() - 1];
+				found.push(Match {
+					node: entry.node,
+					label: entry.label,
+					span: entry.node.span(tokens),
+				});
+			}
+		};
+		let mut path = vec![context.root];
+		check(&path);
+		walk(&mut path, &mut 1, &mut check);
+		found
+	}
+}
+
+/// Find the nodes under `root`, which was parsed from `tokens` and `input`, that match `selector`.
+///
+/// This is a shorthand for parsing `selector` and calling [`Selector::select`].
+/// See the [module-level documentation](self) for an example.
+///
+/// # Errors
+///
+/// Fails if `selector` is not a valid selector.
+pub fn select<'a>(
+	root: &'a dyn TreeNode,
+	tokens: &[Token],
+	input: &str,
+	selector: &str,
+) -> Result<Vec<Match<'a>>, Error> {
+	Ok(selector.parse::<Selector>()?.select(root, tokens, input))
+}
+
+#[cfg(test)]
+mod test {
+	use super::{select, Error, ErrorKind, Selector};
+
+	macro_rules! make_test {
+		($name:ident, $input:expr, $selector:expr, [$($expected:expr),* $(,)?]) => {
+			#[test]
+			fn $name() {
+				let input = $input;
+				let tokens: Vec<_> = crate::lex(input).collect::<Result<_, _>>().unwrap();
+				let arena = crate::Arena::new();
+				let cst = crate::Cst::parse(&tokens, &arena).unwrap();
+				let found: Vec<_> = select(cst.root(), cst.tokens(), input, $selector)
+					.unwrap()
+					.iter()
+					.map(|found| found.span.unwrap().slice(input).unwrap())
+					.collect();
+				let expected: &[&str] = &[$($expected),*];
+				assert_eq!(found, expected);
+			}
+		};
+	}
+
+	make_test!(
+		descendant,
+		"lo mlatu cu citka lo finpe",
+		"Sentence GadriSumti",
+		["lo mlatu", "lo finpe"]
+	);
+	make_test!(
+		child_of_label,
+		"lo mlatu ku cu klama",
+		"GadriSumti > [label=ku]",
+		["ku"]
+	);
+	make_test!(enum_prefix, "mi pu klama", "TagWord", ["pu"]);
+	make_test!(enum_variant, "mi pu klama", "TagWord::Ki", []);
+	make_test!(
+		text_equals,
+		"lo mlatu cu citka lo finpe",
+		"GadriSumti[text='lo finpe']",
+		["lo finpe"]
+	);
+	make_test!(
+		text_starts_with,
+		"lo mlatu cu citka lo finpe",
+		"GadriSumti[text^=lo][text$=tu]",
+		["lo mlatu"]
+	);
+	make_test!(
+		has_child,
+		"lo mlatu ku cu citka lo finpe",
+		"GadriSumti:has(> [label=ku])",
+		["lo mlatu ku"]
+	);
+	make_test!(
+		has_descendant,
+		"lo se prami be mi cu klama lo zdani",
+		"GadriSumti:has(Koha)",
+		["lo se prami be mi"]
+	);
+	make_test!(
+		nested_has,
+		"lo se prami be mi cu klama lo zdani .i do citka",
+		"Sentence:has(GadriSumti:has(Koha))",
+		["lo se prami be mi cu klama lo zdani"]
+	);
+	make_test!(
+		many_descendants,
+		"lo mlatu poi lo gerku poi lo mlatu poi mi nelci ke'a cu citka ke'a cu klama",
+		"* * * * * * * * * * * * * * * * * * * * Koha",
+		["mi", "ke'a", "ke'a"]
+	);
+	make_test!(
+		many_descendants_unmatched,
+		"lo mlatu poi lo gerku poi lo mlatu poi mi nelci ke'a cu citka ke'a cu klama",
+		"Bogus * * * * * * * * * * * * * * * Koha",
+		[]
+	);
+	make_test!(
+		alternatives,
+		"lo mlatu cu citka mi",
+		"GadriSumti, Koha",
+		["lo mlatu", "mi"]
+	);
+
+	#[test]
+	fn errors() {
+		let error = |selector: &str| selector.parse::<Selector>().unwrap_err();
+		assert_eq!(
+			error("GadriSumti >"),
+			Error {
+				position: 12,
+				kind: ErrorKind::UnexpectedEnd
+			}
+		);
+		assert_eq!(
+			error("Sumti[name=ku]"),
+			Error {
+				position: 6,
+				kind: ErrorKind::UnknownAttribute("name".to_owned())
+			}
+		);
+		assert_eq!(
+			error("Sumti:not(Ku)"),
+			Error {
+				position: 6,
+				kind: ErrorKind::UnknownPseudoClass("not".to_owned())
+			}
+		);
+		assert_eq!(
+			error("Sumti)"),
+			Error {
+				position: 5,
+				kind: ErrorKind::UnexpectedChar(')')
+			}
+		);
+	}
+}
@@ -0,0 +1,158 @@
+//! Serialization of CST nodes with [`serde`], enabled by the `serde` feature.
+//!
+//! This module centers around [`SerializeNode`], which serializes any [`TreeNode`] as a generic tree.
+//! Each node becomes a structure with the fields `name`, `label`, `experimental`, `start_location`, `end_location`, `text`, and `children`.
+//! The `label` is the [label](TreeNode::for_each_labeled_child) of the node within its parent, or `null` for the root and for unlabeled children:
+//!
+//! ```rust
+//! # use sneturfahi::parse::serialize::SerializeNode;
+//! let input = "mi klama";
+//! let tokens: Vec<_> = sneturfahi::lex(input).collect::<Result<_, _>>().unwrap();
+//! let arena = sneturfahi::Arena::new();
+//! let cst = sneturfahi::Cst::parse(&tokens, &arena).unwrap();
+//! let json = serde_json::to_value(SerializeNode::new(cst.root(), cst.tokens(), input).collapse(true)).unwrap();
+//! assert_eq!(
+//! 	json,
+//! 	serde_json::json!({
+//! 		"name": "Sentence",
+//! 		"label": null,
+//! 		"experimental": false,
+//! 		"start_location": 0,
+//! 		"end_location": 8,
+//! 		"text": "mi klama",
+//! 		"children": [
+//! 			{
+//! 				"name": "KOhA",
+//! 				"label": "before_args",
+//! 				"experimental": false,
+//! 				"start_location": 0,
+//! 				"end_location": 2,
+//! 				"text": "mi",
+//! 				"children": [],
+//! 			},
+//! 			{
+//! 				"name": "gismu",
+//! 				"label": "tail",
+//! 				"experimental": false,
+//! 				"start_location": 3,
+//! 				"end_location": 8,
+//! 				"text": "klama",
+//! 				"children": [],
+//! 			},
+//! 		],
+//! 	})
+//! );
+//! ```
+//!
+//! The lexer output can be serialized directly, since [`Token`], [`Selmaho`], and [`Span`] implement [`serde::Serialize`] and [`serde::Deserialize`]:
+//!
+//! ```rust
+//! # use sneturfahi::lex::Token;
+//! let tokens: Vec<Token> = sneturfahi::lex("coi").collect::<Result<_, _>>().unwrap();
+//! let json = serde_json::to_string(&tokens).unwrap();
+//! assert_eq!(
+//! 	json,
+//! 	r#"[{"experimental":false,"selmaho":"Coi","span":{"start":0,"end":3}}]"#
+//! );
+//! assert_eq!(serde_json::from_str::<Vec<Token>>(&json).unwrap(), tokens);
+//! ```
+//!
+//! [`Token`]: crate::lex::Token
+//! [`Selmaho`]: crate::lex::Selmaho
+//! [`Span`]: crate::Span
+
+use serde::ser::{Serialize, SerializeStruct as _, Serializer};
+
+use crate::lex::Token;
+use crate::parse::tree_node::TreeNode;
+
+/// A [`TreeNode`] together with the tokens and input it was parsed from, so that it can be serialized with its source text.
+///
+/// See the [module-level documentation](self) for the format.
+#[derive(Clone, Copy)]
+pub struct SerializeNode<'a> {
+	node: &'a dyn TreeNode,
+	label: Option<&'static str>,
+	tokens: &'a [Token],
+	input: &'a str,
+	collapse: bool,
+}
+
+impl std::fmt::Debug for SerializeNode<'_> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		formatter
+			.debug_struct("SerializeNode")
+			.field("node", &self.node.name())
+			.field("collapse", &self.collapse)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<'a> SerializeNode<'a> {
+	/// Prepare `node`, which was parsed from `tokens` and `input`, for serialization.
+	#[must_use]
+	pub fn new(node: &'a dyn TreeNode, tokens: &'a [Token], input: &'a str) -> Self {
+		Self {
+			node,
+			label: None,
+			tokens,
+			input,
+			collapse: false,
+		}
+	}
+
+	/// Whether to skip over nodes that have exactly one child, serializing that child in their place.
+	///
+	/// This removes the long chains of wrapper nodes that the grammar produces, at the cost of hiding some structure.
+	#[must_use]
+	pub fn collapse(self, collapse: bool) -> Self {
+		Self { collapse, ..self }
+	}
+
+	fn children(node: &'a dyn TreeNode) -> Vec<(Option<&'static str>, &'a dyn TreeNode)> {
+		let mut children = Vec::new();
+		node.for_each_labeled_child(&mut |label, child| children.push((label, child)));
+		children
+	}
+
+	fn with_node(self, label: Option<&'static str>, node: &'a dyn TreeNode) -> Self {
+		Self {
+			node,
+			label,
+			..self
+		}
+	}
+}
+
+impl Serialize for SerializeNode<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut node = self.node;
+		let mut children = Self::children(node);
+		if self.collapse {
+			// the label of a collapsed chain is the label of its outermost node
+			while let [(_, only_child)] = children[..] {
+				node = only_child;
+				children = Self::children(node);
+			}
+		}
+
+		let span = node.span(self.tokens);
+		let start_location = span.map(|span| span.start);
+		let end_location = span.map(|span| span.end);
+		let text = span.and_then(|span| span.slice(self.input));
+
+		let mut state = serializer.serialize_struct("TreeNode", 7)?;
+		state.serialize_field("name", node.name())?;
+		state.serialize_field("label", &self.label)?;
+		state.serialize_field("experimental", &node.experimental())?;
+		state.serialize_field("start_location", &start_location)?;
+		state.serialize_field("end_location", &end_location)?;
+		state.serialize_field("text", &text)?;
+		let children: Vec<_> = children
+			.into_iter()
+			.map(|(label, child)| self.with_node(label, child))
+			.collect();
+		state.serialize_field("children", &children)?;
+		state.end()
+	}
+}
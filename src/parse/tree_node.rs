@@ -1,4 +1,24 @@
-use crate::span::Location;
+use crate::lex::{Selmaho, Token};
+use crate::parse::cst::node::{NodeKind, NodeRef};
+use crate::span::Span;
+
+/// A child passed to [`TreeNode::for_each_child_or_elided`].
+#[derive(Clone, Copy)]
+pub enum Child<'a> {
+	/// A node that is present in the input.
+	Node(&'a dyn TreeNode),
+	/// A terminator that was elided from the input, such as the `ku` in `lo mlatu cu klama`.
+	Elided(Selmaho),
+}
+
+impl std::fmt::Debug for Child<'_> {
+	fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Node(node) => formatter.debug_tuple("Node").field(&node.name()).finish(),
+			Self::Elided(selmaho) => formatter.debug_tuple("Elided").field(selmaho).finish(),
+		}
+	}
+}
 
 /// An abstraction over nodes in the CST.
 pub trait TreeNode {
@@ -10,40 +30,103 @@ pub trait TreeNode {
 	#[must_use]
 	fn experimental(&self) -> bool;
 
-	/// Get the starting location of the node, or `None` if the node is empty.
+	/// Get the index of the first token of the node in the tokens it was parsed from, or `None` if the node is empty.
 	///
-	/// `start_location` and `end_location` should both return Some or both return None.
+	/// `start_token` and `end_token` should both return Some or both return None.
 	#[must_use]
-	fn start_location(&self) -> Option<Location>;
+	fn start_token(&self) -> Option<u32>;
 
-	/// Get the ending location of the node, or `None` if the node is empty.
+	/// Get the index just past the last token of the node in the tokens it was parsed from, or `None` if the node is empty.
 	///
-	/// `start_location` and `end_location` should both return Some or both return None.
+	/// `start_token` and `end_token` should both return Some or both return None.
 	#[must_use]
-	fn end_location(&self) -> Option<Location>;
+	fn end_token(&self) -> Option<u32>;
+
+	/// Get the span of the node in the input, or `None` if the node is empty.
+	///
+	/// `tokens` must be the tokens that the node was parsed from, such as [`Cst::tokens`](crate::Cst::tokens).
+	#[must_use]
+	fn span(&self, tokens: &[Token]) -> Option<Span> {
+		let start = tokens[self.start_token()? as usize].span.start;
+		let end = tokens[self.end_token()? as usize - 1].span.end;
+		Some(Span::new(start, end))
+	}
 
 	/// Run the passed closure for each child of the node.
 	fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode));
-}
 
-impl TreeNode for crate::lex::Token {
-	fn name(&self) -> &'static str {
-		self.selmaho.as_repr()
+	/// Like [`for_each_child`](Self::for_each_child), but also run the closure for each terminator that was elided from the input, in the position where it would have appeared.
+	fn for_each_child_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		self.for_each_child(&mut |child| f(Child::Node(child)));
 	}
 
-	fn experimental(&self) -> bool {
-		self.experimental
+	/// Like [`for_each_child`](Self::for_each_child), but also pass the label of each child.
+	///
+	/// The label of a child is the name of the field of the node that it comes from, or the label set with `#[tree_node(label = "...")]` for fields that have no name.
+	/// The only field of a tuple variant, such as `SumtiComponent1::Koha`, is labeled with the name of the variant in snake case, here `koha`, and the only field of a tuple struct is labeled `inner`.
+	/// Children from the same field, such as the items of a slice, share its label.
+	/// Children from fields without a label get `None`.
+	fn for_each_labeled_child<'a>(
+		&'a self,
+		f: &mut dyn FnMut(Option<&'static str>, &'a dyn TreeNode),
+	) {
+		self.for_each_child(&mut |child| f(None, child));
 	}
 
-	fn start_location(&self) -> Option<Location> {
-		Some(self.span.start)
+	/// Get the first child with the given label, such as the `ku` of a [`GadriSumti`](crate::parse::cst::rules::GadriSumti).
+	///
+	/// See [`for_each_labeled_child`](Self::for_each_labeled_child) for how children are labeled.
+	///
+	/// ```rust
+	/// # use sneturfahi::parse::cst::node::NodeKind;
+	/// # use sneturfahi::parse::cst::rules;
+	/// # use sneturfahi::parse::tree_node::TreeNode as _;
+	/// let tokens: Vec<_> = sneturfahi::lex("lo mlatu ku")
+	/// 	.collect::<Result<_, _>>()
+	/// 	.unwrap();
+	/// let arena = sneturfahi::Arena::new();
+	/// let sumti = sneturfahi::Cst::parse_as::<rules::GadriSumti>(&tokens, &arena).unwrap();
+	/// let ku = sumti.root().child_by_label("ku").unwrap();
+	/// assert_eq!(ku.kind(), NodeKind::Ku);
+	/// assert!(sumti.root().child_by_label("vau").is_none());
+	/// // `GadriSumtiContents::Selbri` is a tuple variant with labeled fields
+	/// let contents = sumti.root().child_by_label("contents").unwrap();
+	/// assert_eq!(
+	/// 	contents.child_by_label("selbri").unwrap().kind(),
+	/// 	NodeKind::Selbri
+	/// );
+	/// ```
+	#[must_use]
+	fn child_by_label(&self, label: &str) -> Option<&dyn TreeNode> {
+		let mut found = None;
+		self.for_each_labeled_child(&mut |child_label, child| {
+			if found.is_none() && child_label == Some(label) {
+				found = Some(child);
+			}
+		});
+		found
 	}
 
-	fn end_location(&self) -> Option<Location> {
-		Some(self.span.end)
+	/// Get a reference to the node as its concrete type.
+	///
+	/// See [the `node` module](crate::parse::cst::node) for an example.
+	#[must_use]
+	fn downcast(&self) -> NodeRef<'_>;
+
+	/// Get the kind of the node, which identifies its concrete type.
+	#[must_use]
+	fn kind(&self) -> NodeKind {
+		self.downcast().kind()
 	}
 
-	fn for_each_child<'a>(&'a self, _: &mut dyn FnMut(&'a dyn TreeNode)) {}
+	/// If nodes of this type are terminators that may be elided, such as KU, the selmaho of the terminator.
+	#[must_use]
+	fn elidable_terminator() -> Option<Selmaho>
+	where
+		Self: Sized,
+	{
+		None
+	}
 }
 
 impl<T: TreeNode> TreeNode for &T {
@@ -55,17 +138,40 @@ impl<T: TreeNode> TreeNode for &T {
 		T::experimental(self)
 	}
 
-	fn start_location(&self) -> Option<Location> {
-		T::start_location(self)
+	fn start_token(&self) -> Option<u32> {
+		T::start_token(self)
 	}
 
-	fn end_location(&self) -> Option<Location> {
-		T::end_location(self)
+	fn end_token(&self) -> Option<u32> {
+		T::end_token(self)
 	}
 
 	fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode)) {
 		T::for_each_child(self, f);
 	}
+
+	fn for_each_child_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		T::for_each_child_or_elided(self, f);
+	}
+
+	fn for_each_labeled_child<'a>(
+		&'a self,
+		f: &mut dyn FnMut(Option<&'static str>, &'a dyn TreeNode),
+	) {
+		T::for_each_labeled_child(self, f);
+	}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		T::downcast(self)
+	}
+
+	fn kind(&self) -> NodeKind {
+		T::kind(self)
+	}
+
+	fn elidable_terminator() -> Option<Selmaho> {
+		T::elidable_terminator()
+	}
 }
 
 impl<T: TreeNode> TreeNode for Box<T> {
@@ -77,24 +183,55 @@ impl<T: TreeNode> TreeNode for Box<T> {
 		T::experimental(self)
 	}
 
-	fn start_location(&self) -> Option<Location> {
-		T::start_location(self)
+	fn start_token(&self) -> Option<u32> {
+		T::start_token(self)
 	}
 
-	fn end_location(&self) -> Option<Location> {
-		T::end_location(self)
+	fn end_token(&self) -> Option<u32> {
+		T::end_token(self)
 	}
 
 	fn for_each_child<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode)) {
 		T::for_each_child(self, f);
 	}
+
+	fn for_each_child_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		T::for_each_child_or_elided(self, f);
+	}
+
+	fn for_each_labeled_child<'a>(
+		&'a self,
+		f: &mut dyn FnMut(Option<&'static str>, &'a dyn TreeNode),
+	) {
+		T::for_each_labeled_child(self, f);
+	}
+
+	fn downcast(&self) -> NodeRef<'_> {
+		T::downcast(self)
+	}
+
+	fn kind(&self) -> NodeKind {
+		T::kind(self)
+	}
+
+	fn elidable_terminator() -> Option<Selmaho> {
+		T::elidable_terminator()
+	}
 }
 
 pub(in crate::parse) trait TreeNodeChild {
 	fn invoke_with_self<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode));
+	fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>));
 	fn experimental(&self) -> bool;
-	fn start_location(&self) -> Option<Location>;
-	fn end_location(&self) -> Option<Location>;
+	fn start_token(&self) -> Option<u32>;
+	fn end_token(&self) -> Option<u32>;
+
+	fn elidable_terminator() -> Option<Selmaho>
+	where
+		Self: Sized,
+	{
+		None
+	}
 }
 
 impl<T: TreeNode> TreeNodeChild for T {
@@ -102,16 +239,24 @@ impl<T: TreeNode> TreeNodeChild for T {
 		f(self);
 	}
 
+	fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		f(Child::Node(self));
+	}
+
+	fn elidable_terminator() -> Option<Selmaho> {
+		<T as TreeNode>::elidable_terminator()
+	}
+
 	fn experimental(&self) -> bool {
 		<Self as TreeNode>::experimental(self)
 	}
 
-	fn start_location(&self) -> Option<Location> {
-		<Self as TreeNode>::start_location(self)
+	fn start_token(&self) -> Option<u32> {
+		<Self as TreeNode>::start_token(self)
 	}
 
-	fn end_location(&self) -> Option<Location> {
-		<Self as TreeNode>::end_location(self)
+	fn end_token(&self) -> Option<u32> {
+		<Self as TreeNode>::end_token(self)
 	}
 }
 
@@ -122,16 +267,24 @@ macro_rules! box_impl {
 				<$actual>::invoke_with_self(self, f)
 			}
 
+			fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+				<$actual>::invoke_with_self_or_elided(self, f)
+			}
+
+			fn elidable_terminator() -> Option<Selmaho> {
+				<$actual>::elidable_terminator()
+			}
+
 			fn experimental(&self) -> bool {
 				<$actual>::experimental(self)
 			}
 
-			fn start_location(&self) -> Option<Location> {
-				<$actual>::start_location(self)
+			fn start_token(&self) -> Option<u32> {
+				<$actual>::start_token(self)
 			}
 
-			fn end_location(&self) -> Option<Location> {
-				<$actual>::end_location(self)
+			fn end_token(&self) -> Option<u32> {
+				<$actual>::end_token(self)
 			}
 		}
 	};
@@ -151,16 +304,22 @@ macro_rules! iterator_impls {
 				}
 			}
 
+			fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+				for child in self.iter() {
+					child.invoke_with_self_or_elided(f);
+				}
+			}
+
 			fn experimental(&self) -> bool {
 				self.iter().any(|item| item.experimental())
 			}
 
-			fn start_location(&self) -> Option<Location> {
-				self.iter().find_map(|child| child.start_location())
+			fn start_token(&self) -> Option<u32> {
+				self.iter().find_map(|child| child.start_token())
 			}
 
-			fn end_location(&self) -> Option<Location> {
-				self.iter().rev().find_map(|child| child.end_location())
+			fn end_token(&self) -> Option<u32> {
+				self.iter().rev().find_map(|child| child.end_token())
 			}
 		}
 		box_impl!((T) => $ty);
@@ -170,7 +329,39 @@ macro_rules! iterator_impls {
 	}
 }
 
-iterator_impls![Option<T>, Box<[T]>, &[T]];
+iterator_impls![Box<[T]>, &[T], crate::parse::cst::rules::List<'_, T>];
+
+impl<T: TreeNodeChild> TreeNodeChild for Option<T> {
+	fn invoke_with_self<'a>(&'a self, f: &mut dyn FnMut(&'a dyn TreeNode)) {
+		if let Some(child) = self {
+			child.invoke_with_self(f);
+		}
+	}
+
+	fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+		match self {
+			Some(child) => child.invoke_with_self_or_elided(f),
+			None => {
+				if let Some(terminator) = T::elidable_terminator() {
+					f(Child::Elided(terminator));
+				}
+			}
+		}
+	}
+
+	fn experimental(&self) -> bool {
+		self.as_ref().is_some_and(TreeNodeChild::experimental)
+	}
+
+	fn start_token(&self) -> Option<u32> {
+		self.as_ref().and_then(TreeNodeChild::start_token)
+	}
+
+	fn end_token(&self) -> Option<u32> {
+		self.as_ref().and_then(TreeNodeChild::end_token)
+	}
+}
+box_impl!((T) => Option<T>);
 
 macro_rules! tuple_impls {
 	// base case
@@ -183,25 +374,30 @@ macro_rules! tuple_impls {
 				$($idents.invoke_with_self(f);)*
 			}
 
+			fn invoke_with_self_or_elided<'a>(&'a self, f: &mut dyn FnMut(Child<'a>)) {
+				let ($($idents,)*) = self;
+				$($idents.invoke_with_self_or_elided(f);)*
+			}
+
 			fn experimental(&self) -> bool {
 				let ($($idents,)*) = self;
 				false $(|| $idents.experimental())*
 			}
 
-			fn start_location(&self) -> Option<Location> {
+			fn start_token(&self) -> Option<u32> {
 				let ($($idents,)*) = self;
 				$(
-					if let Some(location) = $idents.start_location() {
-						return Some(location);
+					if let Some(token) = $idents.start_token() {
+						return Some(token);
 					}
 				)*
 				None
 			}
 
-			fn end_location(&self) -> Option<Location> {
+			fn end_token(&self) -> Option<u32> {
 				let ($($idents,)*) = self;
 				let children = [$($idents as &dyn TreeNodeChild,)*];
-				children.iter().rev().find_map(|child| child.end_location())
+				children.iter().rev().find_map(|child| child.end_token())
 			}
 		}
 
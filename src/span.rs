@@ -15,6 +15,7 @@ pub type Location = u32;
 	doc = "It is also smaller than a string slice, though it requires the original input to get an actual `&str` substring."
 )]
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
 	/// The index of the first byte of the span. This is included in the span.
 	pub start: Location,
@@ -288,6 +289,24 @@ impl Span {
 		(self.start..self.end).contains(&location)
 	}
 
+	/// Checks if `other` is entirely within this span.
+	/// Empty spans are within a span if they are at its start, its end, or anywhere between.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use sneturfahi::span::Span;
+	/// let span = Span::new(3, 6);
+	/// assert!(span.contains_span(Span::new(3, 6)));
+	/// assert!(span.contains_span(Span::new(4, 5)));
+	/// assert!(span.contains_span(Span::at(6, 0)));
+	/// assert!(!span.contains_span(Span::new(5, 7)));
+	/// ```
+	#[must_use]
+	pub fn contains_span(self, other: Self) -> bool {
+		self.start <= other.start && other.end <= self.end
+	}
+
 	/// Checks if two spans overlap.
 	/// This operation is commutative.
 	///